@@ -1,5 +1,6 @@
-use crate::{error::*, spec::BRASIL_API_URL};
+use crate::{client, endpoint::ListEndpoint, error::*};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Bank {
@@ -11,6 +12,57 @@ pub struct Bank {
     pub fullname: Option<String>,
 }
 
+/// Bancos indexados pelo ISPB e pelo código COMPE, montados a partir de uma única consulta.
+///
+/// Bancos sem código COMPE ficam disponíveis apenas pelo ISPB.
+#[derive(Debug)]
+pub struct BankIndex {
+    banks: Vec<Bank>,
+    by_ispb: HashMap<String, usize>,
+    by_code: HashMap<i32, usize>,
+}
+
+impl BankIndex {
+    pub fn new(banks: Vec<Bank>) -> Self {
+        let mut by_ispb = HashMap::with_capacity(banks.len());
+        let mut by_code = HashMap::with_capacity(banks.len());
+
+        for (position, bank) in banks.iter().enumerate() {
+            by_ispb.entry(bank.ispb.clone()).or_insert(position);
+
+            if let Some(code) = bank.code {
+                by_code.entry(code).or_insert(position);
+            }
+        }
+
+        Self {
+            banks,
+            by_ispb,
+            by_code,
+        }
+    }
+
+    /// Banco com o ISPB informado, por exemplo `00000000`.
+    pub fn get_by_ispb(&self, ispb: &str) -> Option<&Bank> {
+        self.by_ispb
+            .get(ispb)
+            .map(|&position| &self.banks[position])
+    }
+
+    /// Banco com o código COMPE informado, por exemplo `1`.
+    pub fn get_by_code(&self, code: i32) -> Option<&Bank> {
+        self.by_code
+            .get(&code)
+            .map(|&position| &self.banks[position])
+    }
+
+    /// Todos os bancos, na ordem retornada pela API.
+    pub fn get_banks(&self) -> &[Bank] {
+        &self.banks
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct BankService {
     base_url: String,
 }
@@ -18,26 +70,30 @@ pub struct BankService {
 impl BankService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_all_banks(&self) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/banks/v1", self.base_url);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 
     async fn get_bank_by_code(&self, code: i32) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/banks/v1/{}", self.base_url, code);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
+    }
+}
+
+impl ListEndpoint<Bank> for BankService {
+    async fn fetch_all(&self) -> Result<Vec<Bank>, Error> {
+        let response = self.get_all_banks().await?;
+
+        let banks: Vec<Bank> = client::read_list(response).await?;
+
+        Ok(banks)
     }
 }
 
@@ -58,14 +114,9 @@ impl BankService {
 ///    let banks:Vec<Bank> = bank::get_all_banks().await.unwrap();
 /// }
 pub async fn get_all_banks() -> Result<Vec<Bank>, Error> {
-    let bank_service = BankService::new(BRASIL_API_URL);
+    let bank_service = BankService::new(client::base_url());
 
-    let response = bank_service.get_all_banks().await?;
-
-    let body = response.text().await.unwrap();
-    let banks: Vec<Bank> = serde_json::from_str(&body).unwrap();
-
-    Ok(banks)
+    bank_service.fetch_all().await
 }
 
 /// #### `get_bank(code: i32)`
@@ -87,20 +138,93 @@ pub async fn get_all_banks() -> Result<Vec<Bank>, Error> {
 /// }
 /// ```
 pub async fn get_bank(code: i32) -> Result<Bank, Error> {
-    let bank_service = BankService::new(BRASIL_API_URL);
+    let bank_service = BankService::new(client::base_url());
 
     let response = bank_service.get_bank_by_code(code).await?;
 
-    let body = response.text().await.unwrap();
-    let bank: Bank = serde_json::from_str(&body).unwrap();
+    let bank: Bank = client::read_json(response).await?;
+
+    Ok(bank)
+}
+
+/// #### `find_by_ispb(ispb: &str)`
+/// Busca as informações de um banco a partir do ISPB.
+///
+/// Algumas instituições não possuem código COMPE, apenas o ISPB, que é o identificador usado pelo PIX.
+/// A lista completa de bancos é consultada e filtrada pelo ISPB informado.
+///
+/// ### Argumentos
+/// * `ispb:&str` => ISPB do banco, por exemplo `00000000`.
+///
+/// ### Retorno
+/// * `Result<Option<Bank>, Error>`
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::bank::{self, Bank};
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let bank: Option<Bank> = bank::find_by_ispb("00000000").await.unwrap();
+/// }
+/// ```
+pub async fn find_by_ispb(ispb: &str) -> Result<Option<Bank>, Error> {
+    let banks = get_all_banks().await?;
+
+    let bank = banks.into_iter().find(|bank| bank.ispb == ispb);
 
     Ok(bank)
 }
 
+/// #### `index()`
+/// Busca todos os bancos uma única vez e os indexa pelo ISPB e pelo código COMPE.
+///
+/// ### Retorno
+/// * `Result<BankIndex, Error>`
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::bank;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let index = bank::index().await.unwrap();
+///
+///   let by_ispb = index.get_by_ispb("00000000");
+///   let by_code = index.get_by_code(1);
+/// }
+/// ```
+pub async fn index() -> Result<BankIndex, Error> {
+    let banks = get_all_banks().await?;
+
+    Ok(BankIndex::new(banks))
+}
+
 #[cfg(test)]
 mod bank_tests {
     use super::*;
 
+    #[test]
+    fn bank_index_test() {
+        let banks: Vec<Bank> = serde_json::from_str(
+            r#"[
+                {"ispb": "00000000", "name": "BCO DO BRASIL S.A.", "code": 1, "fullName": "Banco do Brasil S.A."},
+                {"ispb": "00038166", "name": "BCO CENTRAL DO BRASIL", "code": null, "fullName": null}
+            ]"#,
+        )
+        .unwrap();
+
+        let index = BankIndex::new(banks);
+
+        assert_eq!(index.get_by_code(1).unwrap().ispb, "00000000");
+        assert_eq!(
+            index.get_by_ispb("00038166").unwrap().name.as_deref(),
+            Some("BCO CENTRAL DO BRASIL")
+        );
+        assert!(index.get_by_code(2).is_none());
+        assert_eq!(index.get_banks().len(), 2);
+    }
+
     #[tokio::test]
     async fn get_bank_test() {
         let banks = get_all_banks().await.unwrap();
@@ -110,6 +234,13 @@ mod bank_tests {
         assert!(banks.contains(&bank));
     }
 
+    #[tokio::test]
+    async fn find_by_ispb_test() {
+        let bank = find_by_ispb("00000000").await.unwrap().unwrap();
+
+        assert_eq!(bank.code, Some(1));
+    }
+
     #[tokio::test]
     async fn get_bank_error() {
         let bank = get_bank(2).await;
@@ -0,0 +1,282 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use futures::{stream, Stream, StreamExt};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Configuração das operações em lote, que disparam várias requisições à BrasilAPI.
+///
+/// Limita quantas requisições ficam em andamento ao mesmo tempo e, opcionalmente, espalha o
+/// início de cada uma dentro de uma janela de tempo aleatória, evitando picos de carga na API.
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::batch::Batch;
+///
+/// let batch = Batch::new()
+///     .concurrency(8)
+///     .spread(Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    concurrency: usize,
+    spread: Duration,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            spread: Duration::ZERO,
+        }
+    }
+}
+
+impl Batch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define o número máximo de requisições simultâneas (mínimo de 1).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Atrasa o início de cada requisição por um tempo aleatório entre zero e `window`.
+    pub fn spread(mut self, window: Duration) -> Self {
+        self.spread = window;
+        self
+    }
+
+    pub fn get_concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn get_spread(&self) -> Duration {
+        self.spread
+    }
+
+    fn jitter(&self, position: usize) -> Duration {
+        if self.spread.is_zero() {
+            return Duration::ZERO;
+        }
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(position);
+
+        self.spread
+            .mul_f64(hasher.finish() as f64 / u64::MAX as f64)
+    }
+
+    /// Executa `task` para cada item respeitando a configuração do lote. Os resultados são
+    /// retornados na mesma ordem dos itens.
+    pub(crate) async fn run<I, F, Fut>(&self, items: I, task: F) -> Vec<Fut::Output>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Fut,
+        Fut: Future,
+    {
+        self.stream(items, task).collect().await
+    }
+
+    /// Igual a `run`, mas entrega cada resultado assim que ele e os anteriores ficam prontos.
+    pub(crate) fn stream<'a, I, F, Fut>(
+        &'a self,
+        items: I,
+        mut task: F,
+    ) -> impl Stream<Item = Fut::Output> + 'a
+    where
+        I: IntoIterator,
+        I::IntoIter: 'a,
+        F: FnMut(I::Item) -> Fut + 'a,
+        Fut: Future + 'a,
+    {
+        let tasks = items.into_iter().enumerate().map(move |(position, item)| {
+            let delay = self.jitter(position);
+            let future = task(item);
+
+            async move {
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+
+                future.await
+            }
+        });
+
+        stream::iter(tasks).buffered(self.concurrency)
+    }
+}
+
+/// Progresso de uma operação em lote, que pode ser lido por outra task enquanto a operação
+/// alimenta os contadores.
+///
+/// Cada contador é atualizado atomicamente, então basta compartilhar o agregador, por exemplo
+/// com um `Arc`, entre a operação e a task que exibe o progresso.
+///
+/// # Exemplo
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+/// use brasilapi::{batch::ProgressAggregator, ibge};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let progress = Arc::new(ProgressAggregator::new());
+///    let reader = progress.clone();
+///
+///    tokio::spawn(async move {
+///        while !reader.is_finished() {
+///            println!("{}/{} estados", reader.completed(), reader.total());
+///            tokio::time::sleep(Duration::from_millis(200)).await;
+///        }
+///    });
+///
+///    let mut output: Vec<u8> = Vec::new();
+///    ibge::export_all_municipalities_ndjson_with_progress(&mut output, None, &progress)
+///        .await
+///        .unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ProgressAggregator {
+    total: AtomicUsize,
+    completed: AtomicUsize,
+    failures: AtomicUsize,
+}
+
+impl ProgressAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Quantidade de itens da operação, conhecida assim que ela começa.
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Acquire)
+    }
+
+    /// Quantidade de itens concluídos com sucesso.
+    pub fn completed(&self) -> usize {
+        self.completed.load(Ordering::Acquire)
+    }
+
+    /// Quantidade de itens que falharam.
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::Acquire)
+    }
+
+    /// Verifica se todos os itens já foram concluídos ou falharam. Também retorna `true` quando a
+    /// operação é interrompida por uma falha.
+    pub fn is_finished(&self) -> bool {
+        let failures = self.failures();
+
+        failures > 0 || (self.total() > 0 && self.completed() + failures >= self.total())
+    }
+
+    pub(crate) fn start(&self, total: usize) {
+        self.completed.store(0, Ordering::Release);
+        self.failures.store(0, Ordering::Release);
+        self.total.store(total, Ordering::Release);
+    }
+
+    pub(crate) fn record_success(&self) {
+        self.completed.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn record_failure(&self) {
+        self.failures.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn run_preserves_order_test() {
+        let batch = Batch::new()
+            .concurrency(3)
+            .spread(Duration::from_millis(20));
+
+        let results = batch.run(0..10, |item| async move { item * 2 }).await;
+
+        assert_eq!(results, (0..10).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn run_respects_concurrency_test() {
+        let batch = Batch::new().concurrency(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        batch
+            .run(0..8, |_| {
+                let running = running.clone();
+                let peak = peak.clone();
+
+                async move {
+                    let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+            .await;
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn jitter_within_window_test() {
+        let batch = Batch::new().spread(Duration::from_millis(100));
+
+        assert!((0..50).all(|position| batch.jitter(position) <= Duration::from_millis(100)));
+        assert_eq!(Batch::new().jitter(0), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn progress_aggregator_test() {
+        let progress = Arc::new(ProgressAggregator::new());
+        progress.start(100);
+
+        let tasks: Vec<_> = (0..100)
+            .map(|item| {
+                let progress = progress.clone();
+
+                tokio::spawn(async move {
+                    if item % 10 == 0 {
+                        progress.record_failure();
+                    } else {
+                        progress.record_success();
+                    }
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(progress.total(), 100);
+        assert_eq!(progress.completed(), 90);
+        assert_eq!(progress.failures(), 10);
+        assert!(progress.is_finished());
+        assert!(!ProgressAggregator::new().is_finished());
+    }
+
+    #[test]
+    fn concurrency_minimum_test() {
+        assert_eq!(Batch::new().concurrency(0).get_concurrency(), 1);
+    }
+}
@@ -0,0 +1,542 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, LAST_MODIFIED},
+    Response, StatusCode,
+};
+
+use crate::{client, error::Error};
+
+/// Resposta armazenada, com o instante em que foi recebida.
+struct CachedResponse {
+    stored: Instant,
+    status: u16,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+/// Limites de tamanho do cache. Ao exceder algum deles, as respostas usadas há mais tempo são
+/// descartadas primeiro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheLimits {
+    /// Número máximo de respostas armazenadas. Sem limite por padrão.
+    pub max_entries: Option<usize>,
+    /// Soma máxima do tamanho dos corpos armazenados, em bytes. Uma resposta maior que o limite
+    /// não é armazenada. Sem limite por padrão.
+    pub max_bytes: Option<usize>,
+}
+
+/// Contadores de uso do cache, retornados por `stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Consultas respondidas pelo cache, sem acessar a rede.
+    pub hits: u64,
+    /// Consultas que acessaram a rede, inclusive as revalidações de respostas expiradas.
+    pub misses: u64,
+    /// Respostas armazenadas no momento.
+    pub entries: usize,
+    /// Soma do tamanho dos corpos armazenados, em bytes.
+    pub bytes: usize,
+}
+
+impl CacheStats {
+    /// Proporção das consultas respondidas pelo cache, entre `0.0` e `1.0`. Sem consultas,
+    /// retorna `0.0`.
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl CachedResponse {
+    fn to_response(&self) -> Response {
+        let mut response = http::Response::new(self.body.clone());
+
+        *response.status_mut() =
+            http::StatusCode::from_u16(self.status).unwrap_or(http::StatusCode::OK);
+        *response.headers_mut() = self.headers.clone();
+
+        Response::from(response)
+    }
+}
+
+/// Respostas armazenadas, indexadas pela URL completa da requisição.
+///
+/// `recency` ordena as chaves pelo último uso, permitindo descartar a resposta usada há mais
+/// tempo sem percorrer todas as entradas.
+struct ResponseCache {
+    ttl: Duration,
+    limits: CacheLimits,
+    entries: HashMap<String, (u64, CachedResponse)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl ResponseCache {
+    fn new(ttl: Duration, limits: CacheLimits) -> Self {
+        Self {
+            ttl,
+            limits,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            bytes: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+            bytes: self.bytes,
+        }
+    }
+
+    /// Contabiliza o resultado de uma consulta ao cache.
+    fn record_lookup(&mut self, hit: bool) {
+        match hit {
+            true => self.hits += 1,
+            false => self.misses += 1,
+        }
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "brasilapi_cache_lookups_total",
+            "result" => if hit { "hit" } else { "miss" },
+        )
+        .increment(1);
+    }
+
+    /// Marca a entrada de `key` como a usada mais recentemente.
+    fn touch(&mut self, key: &str) -> Option<&mut CachedResponse> {
+        let (used, entry) = self.entries.get_mut(key)?;
+
+        self.clock += 1;
+        self.recency.remove(used);
+        self.recency.insert(self.clock, key.to_string());
+        *used = self.clock;
+
+        Some(entry)
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((used, entry)) = self.entries.remove(key) {
+            self.recency.remove(&used);
+            self.bytes -= entry.body.len();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.bytes = 0;
+    }
+
+    fn exceeds_limits(&self) -> bool {
+        self.limits
+            .max_entries
+            .is_some_and(|max_entries| self.entries.len() > max_entries)
+            || self
+                .limits
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes > max_bytes)
+    }
+
+    /// Resposta armazenada para `key`, caso ainda esteja dentro do `ttl`.
+    ///
+    /// Respostas expiradas que informaram `Last-Modified` são mantidas para que possam ser
+    /// revalidadas com `If-Modified-Since`; as demais são descartadas.
+    fn get(&mut self, key: &str, now: Instant) -> Option<Response> {
+        let ttl = self.ttl;
+
+        let response = match self.touch(key) {
+            Some(entry) if now.duration_since(entry.stored) < ttl => Some(entry.to_response()),
+            Some(entry) if entry.headers.contains_key(LAST_MODIFIED) => None,
+            Some(_) => {
+                self.remove(key);
+                None
+            }
+            None => None,
+        };
+
+        self.record_lookup(response.is_some());
+
+        response
+    }
+
+    /// Valor do `Last-Modified` da resposta armazenada para `key`.
+    fn last_modified(&self, key: &str) -> Option<HeaderValue> {
+        self.entries
+            .get(key)
+            .and_then(|(_, entry)| entry.headers.get(LAST_MODIFIED))
+            .cloned()
+    }
+
+    /// Renova a resposta armazenada para `key` após um `304 Not Modified`, retornando-a.
+    fn revalidate(&mut self, key: &str, now: Instant) -> Option<Response> {
+        let entry = self.touch(key)?;
+
+        entry.stored = now;
+
+        Some(entry.to_response())
+    }
+
+    /// Armazena `entry`, descartando as respostas usadas há mais tempo até que os limites sejam
+    /// respeitados.
+    fn insert(&mut self, key: String, entry: CachedResponse) {
+        self.remove(&key);
+
+        if self
+            .limits
+            .max_bytes
+            .is_some_and(|max_bytes| entry.body.len() > max_bytes)
+        {
+            return;
+        }
+
+        self.clock += 1;
+        self.bytes += entry.body.len();
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, (self.clock, entry));
+
+        while self.exceeds_limits() {
+            match self.recency.pop_first() {
+                Some((_, oldest)) => self.remove(&oldest),
+                None => break,
+            }
+        }
+    }
+}
+
+static CACHE: Mutex<Option<ResponseCache>> = Mutex::new(None);
+
+fn cache() -> MutexGuard<'static, Option<ResponseCache>> {
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Chave de uma requisição no cache: a URL completa, em sua forma canônica.
+///
+/// A URL inclui o endereço base, o caminho e os parâmetros, então a mesma consulta feita a
+/// instâncias diferentes da BrasilAPI (produção e um espelho, por exemplo) ou com provedores
+/// diferentes não compartilha entradas. A forma canônica trata como iguais URLs que diferem
+/// apenas na capitalização do host ou na porta padrão explícita.
+fn cache_key(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => url.into(),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// #### `enable(ttl: Duration)`
+/// Passa a armazenar as respostas bem-sucedidas de todas as consultas por `ttl`.
+///
+/// Uma consulta repetida dentro desse período é respondida sem acessar a rede, inclusive sem
+/// executar os hooks do módulo `client`. Chamar `enable` novamente descarta as respostas
+/// armazenadas.
+///
+/// Após o `ttl`, respostas que informaram `Last-Modified`, como as de recursos que não mudam
+/// (os feriados de anos anteriores, por exemplo), são revalidadas com uma requisição
+/// condicional `If-Modified-Since`: se o servidor responder `304 Not Modified`, a resposta
+/// armazenada é reutilizada por mais um `ttl` sem que o corpo seja baixado novamente.
+///
+/// Funções que leem a resposta de forma incremental, como `pix::stream_participants`, não
+/// consultam nem alimentam o cache.
+///
+/// ### Argumento
+/// * `ttl:Duration` => Tempo que cada resposta permanece válida.
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::cache;
+///
+/// cache::enable(Duration::from_secs(300));
+/// ```
+pub fn enable(ttl: Duration) {
+    enable_with_limits(ttl, CacheLimits::default());
+}
+
+/// #### `enable_with_limits(ttl: Duration, limits: CacheLimits)`
+/// Igual a `enable`, limitando o número de respostas armazenadas ou o seu tamanho total.
+///
+/// Serviços de longa duração que consultam muitos CEPs ou CNPJs distintos podem usar os limites
+/// para manter a memória constante: ao exceder um limite, as respostas usadas há mais tempo são
+/// descartadas.
+///
+/// ### Argumentos
+/// * `ttl:Duration` => Tempo que cada resposta permanece válida.
+/// * `limits:CacheLimits` => Número máximo de respostas e de bytes armazenados.
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::cache::{self, CacheLimits};
+///
+/// cache::enable_with_limits(
+///     Duration::from_secs(300),
+///     CacheLimits {
+///         max_entries: Some(10_000),
+///         max_bytes: Some(64 * 1024 * 1024),
+///     },
+/// );
+/// ```
+pub fn enable_with_limits(ttl: Duration, limits: CacheLimits) {
+    *cache() = Some(ResponseCache::new(ttl, limits));
+}
+
+/// #### `disable()`
+/// Deixa de armazenar respostas e descarta as já armazenadas.
+pub fn disable() {
+    *cache() = None;
+}
+
+/// #### `clear()`
+/// Descarta as respostas armazenadas, mantendo o cache ativo.
+pub fn clear() {
+    if let Some(cache) = cache().as_mut() {
+        cache.clear();
+    }
+}
+
+/// #### `stats()`
+/// Retorna o número de consultas respondidas pelo cache e das que acessaram a rede desde a
+/// última chamada de `enable`, além do tamanho atual do cache, para ajustar o `ttl` e os
+/// `CacheLimits`. Com o cache desativado, todos os valores são zero.
+///
+/// `clear` descarta as respostas, mas mantém os contadores. Com a feature `metrics`, cada
+/// consulta também é registrada no contador `brasilapi_cache_lookups_total`, com o rótulo
+/// `result` igual a `hit` ou `miss`.
+///
+/// ### Retorno
+/// * `CacheStats`
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::cache;
+///
+/// cache::enable(Duration::from_secs(300));
+///
+/// let stats = cache::stats();
+/// println!("{} acertos, {:.0}%", stats.hits, stats.hit_ratio() * 100.0);
+/// ```
+pub fn stats() -> CacheStats {
+    cache()
+        .as_ref()
+        .map(ResponseCache::stats)
+        .unwrap_or_default()
+}
+
+/// Executa `fetch` apenas se a resposta de `url` não estiver no cache, armazenando-a em seguida.
+///
+/// `fetch` recebe o valor do `If-Modified-Since` quando a resposta armazenada expirou e pode ser
+/// revalidada.
+pub(crate) async fn get<F, Fut>(url: &str, fetch: F) -> Result<Response, Error>
+where
+    F: Fn(Option<HeaderValue>) -> Fut,
+    Fut: std::future::Future<Output = Result<Response, Error>>,
+{
+    let key = cache_key(url);
+
+    let cached = cache()
+        .as_mut()
+        .map(|cache| match cache.get(&key, Instant::now()) {
+            Some(response) => Ok(response),
+            None => Err(cache.last_modified(&key)),
+        });
+
+    let if_modified_since = match cached {
+        Some(Ok(response)) => return Ok(response),
+        Some(Err(last_modified)) => last_modified,
+        None => return fetch(None).await,
+    };
+
+    let mut response = fetch(if_modified_since).await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        let revalidated = cache()
+            .as_mut()
+            .and_then(|cache| cache.revalidate(&key, Instant::now()));
+
+        match revalidated {
+            Some(response) => return Ok(response),
+            // O cache foi limpo durante a requisição.
+            None => response = fetch(None).await?,
+        }
+    }
+
+    let status = response.status().as_u16();
+    let headers = response.headers().clone();
+    let body = client::read_bytes(response).await?;
+
+    let entry = CachedResponse {
+        stored: Instant::now(),
+        status,
+        headers,
+        body,
+    };
+    let response = entry.to_response();
+
+    if let Some(cache) = cache().as_mut() {
+        cache.insert(key, entry);
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+
+    fn entry(body: &str, stored: Instant) -> CachedResponse {
+        CachedResponse {
+            stored,
+            status: 200,
+            headers: HeaderMap::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn revalidate_test() {
+        let now = Instant::now();
+        let key = cache_key("https://brasilapi.com.br/api/feriados/v1/2020");
+        let mut cache = ResponseCache::new(Duration::from_secs(60), CacheLimits::default());
+        let mut stable = entry("feriados", now);
+
+        stable.headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 01 Jan 2020 00:00:00 GMT"),
+        );
+        cache.insert(key.clone(), stable);
+
+        let expired = now + Duration::from_secs(61);
+
+        assert!(cache.get(&key, expired).is_none());
+        assert_eq!(
+            cache.last_modified(&key).unwrap(),
+            "Wed, 01 Jan 2020 00:00:00 GMT"
+        );
+
+        let response = cache.revalidate(&key, expired).unwrap();
+
+        assert_eq!(response.text().await.unwrap(), "feriados");
+        assert!(cache.get(&key, expired + Duration::from_secs(30)).is_some());
+    }
+
+    #[test]
+    fn cache_key_test() {
+        let prod = cache_key("https://brasilapi.com.br/api/ibge/municipios/v1/SP");
+        let mirror = cache_key("https://mirror.example.com/api/ibge/municipios/v1/SP");
+        let with_providers =
+            cache_key("https://brasilapi.com.br/api/ibge/municipios/v1/SP?providers=gov");
+
+        assert_ne!(prod, mirror);
+        assert_ne!(prod, with_providers);
+        assert_eq!(
+            prod,
+            cache_key("https://BRASILAPI.com.br:443/api/ibge/municipios/v1/SP")
+        );
+    }
+
+    #[tokio::test]
+    async fn response_cache_test() {
+        let now = Instant::now();
+        let mut cache = ResponseCache::new(Duration::from_secs(60), CacheLimits::default());
+
+        cache.insert(cache_key("https://prod.example.com/a"), entry("prod", now));
+        cache.insert(
+            cache_key("https://mirror.example.com/a"),
+            entry("mirror", now),
+        );
+
+        let prod = cache
+            .get(&cache_key("https://prod.example.com/a"), now)
+            .unwrap();
+        let mirror = cache
+            .get(&cache_key("https://mirror.example.com/a"), now)
+            .unwrap();
+
+        assert_eq!(prod.text().await.unwrap(), "prod");
+        assert_eq!(mirror.text().await.unwrap(), "mirror");
+
+        let expired = now + Duration::from_secs(61);
+
+        assert!(cache
+            .get(&cache_key("https://prod.example.com/a"), expired)
+            .is_none());
+        assert!(!cache
+            .entries
+            .contains_key(&cache_key("https://prod.example.com/a")));
+    }
+
+    #[test]
+    fn lru_eviction_test() {
+        let now = Instant::now();
+        let mut cache = ResponseCache::new(
+            Duration::from_secs(60),
+            CacheLimits {
+                max_entries: Some(2),
+                max_bytes: Some(10),
+            },
+        );
+
+        cache.insert("a".to_string(), entry("aaa", now));
+        cache.insert("b".to_string(), entry("bbb", now));
+        assert!(cache.get("a", now).is_some());
+
+        // "b" é a entrada usada há mais tempo.
+        cache.insert("c".to_string(), entry("ccc", now));
+
+        assert!(cache.entries.contains_key("a"));
+        assert!(!cache.entries.contains_key("b"));
+        assert_eq!(cache.bytes, 6);
+
+        // O limite de bytes descarta "a" e "c" para armazenar "d".
+        cache.insert("d".to_string(), entry("dddddddd", now));
+
+        assert_eq!(cache.entries.keys().collect::<Vec<_>>(), vec!["d"]);
+        assert_eq!(cache.bytes, 8);
+
+        cache.insert("e".to_string(), entry("too large body", now));
+
+        assert!(!cache.entries.contains_key("e"));
+        assert_eq!(cache.recency.len(), cache.entries.len());
+    }
+
+    #[test]
+    fn stats_test() {
+        let now = Instant::now();
+        let mut cache = ResponseCache::new(Duration::from_secs(60), CacheLimits::default());
+
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(cache.stats().hit_ratio(), 0.0);
+
+        assert!(cache.get("a", now).is_none());
+        cache.insert("a".to_string(), entry("aaa", now));
+        assert!(cache.get("a", now).is_some());
+        assert!(cache.get("a", now).is_some());
+        assert!(cache.get("a", now + Duration::from_secs(61)).is_none());
+
+        let stats = cache.stats();
+
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.hit_ratio(), 0.5);
+    }
+}
@@ -1,16 +1,82 @@
-use crate::{error::*, spec::BRASIL_API_URL};
-use serde::{Deserialize, Serialize};
+use crate::{
+    batch::Batch,
+    client,
+    error::*,
+    ibge::{self, Region, State, StateRegion, Uf},
+    validate::normalize_cep,
+};
+use serde::{Deserialize, Deserializer, Serialize};
 
+/// CEPs gerais de municípios, que não identificam um logradouro, são retornados sem rua e sem
+/// bairro. Nesses casos `street` e `neighborhood` ficam vazios.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Cep {
     pub cep: String,
     pub state: String,
     pub city: String,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
     pub neighborhood: String,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
     pub street: String,
     pub service: String,
 }
 
+/// Desserializa um texto que pode vir como `null`, tratado como vazio.
+fn deserialize_nullable<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Endereço normalizado de um CEP, com a unidade federativa validada e a região do estado.
+///
+/// `street` e `neighborhood` são `None` quando o CEP não os informa, como nos CEPs gerais de
+/// municípios, em vez de textos vazios.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Address {
+    /// CEP com 8 dígitos, sem pontuação.
+    pub cep: String,
+    pub street: Option<String>,
+    pub neighborhood: Option<String>,
+    pub city: String,
+    pub uf: Uf,
+    pub region: Region,
+}
+
+impl Address {
+    fn new(cep: Cep, uf: Uf, region: Region) -> Self {
+        let non_empty = |text: String| {
+            let text = text.trim();
+
+            (!text.is_empty()).then(|| text.to_string())
+        };
+
+        Self {
+            cep: cep.cep.chars().filter(char::is_ascii_digit).collect(),
+            street: non_empty(cep.street),
+            neighborhood: non_empty(cep.neighborhood),
+            city: cep.city.trim().to_string(),
+            uf,
+            region,
+        }
+    }
+}
+
+/// Um CEP acompanhado das informações do seu estado, provenientes do IBGE.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedLocation {
+    pub cep: Cep,
+    pub state: State,
+}
+
+impl ResolvedLocation {
+    /// Região do estado ao qual o CEP pertence.
+    pub fn region(&self) -> &StateRegion {
+        self.state.get_region()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Location {
     #[serde(rename = "type")]
@@ -34,35 +100,114 @@ pub struct CepError {
     pub errors: Vec<Error>,
 }
 
+#[derive(Debug, Clone)]
 pub struct CepService {
     base_url: String,
 }
 
+/// Versões do endpoint de CEP consultadas em ordem: a preferida, por padrão a `v2`, seguida das
+/// anteriores. Uma versão inexistente retorna um erro `Errored::InvalidInput`.
+fn cep_versions(preferred: Option<u8>) -> Result<Vec<u8>, Error> {
+    match preferred.unwrap_or(2) {
+        version @ 1..=2 => Ok((1..=version).rev().collect()),
+        version => Err(Error::invalid_input(&format!(
+            "{UNSUPPORTED_VERSION_MESSAGE}: cep v{version}"
+        ))),
+    }
+}
+
+/// Indica se a versão anterior deve ser consultada após `error`.
+///
+/// A BrasilAPI responde `404` com o objeto de erro do serviço de CEP para um CEP inexistente,
+/// que não deve ser consultado novamente. Sem uma versão escolhida com `Config::prefer_version`,
+/// a versão anterior só é consultada quando o `404` não traz esse objeto, ou seja, quando a
+/// versão não existe no servidor, por exemplo em um espelho desatualizado.
+fn falls_back(error: &Error, explicit: bool) -> bool {
+    error.error == Errored::NotFound && (explicit || error.api_error.is_none())
+}
+
 impl CepService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_cep_request(&self, cep_code: &str) -> Result<reqwest::Response, Error> {
-        let url = format!("{}/api/cep/v2/{}", self.base_url, cep_code);
+        self.get_cep_request_versions(cep_code, client::preferred_version("cep"))
+            .await
+    }
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+    async fn get_cep_request_versions(
+        &self,
+        cep_code: &str,
+        preferred: Option<u8>,
+    ) -> Result<reqwest::Response, Error> {
+        let cep_code = normalize_cep(cep_code)?;
+        let urls: Vec<String> = cep_versions(preferred)?
+            .into_iter()
+            .map(|version| format!("{}/api/cep/v{}/{}", self.base_url, version, cep_code))
+            .collect();
+
+        client::get_with_fallback(&urls, |error| falls_back(error, preferred.is_some())).await
+    }
+
+    async fn fetch_cep(&self, cep_code: &str) -> Result<Cep, Error> {
+        let response = self.get_cep_request(cep_code).await?;
+
+        client::read_json(response).await
+    }
+
+    async fn lookup_many_ceps(
+        &self,
+        ceps: &[&str],
+        batch: &Batch,
+    ) -> Vec<(String, Result<Cep, Error>)> {
+        // Os CEPs são copiados antes do lote: um iterador sobre `&[&str]` mantido entre os
+        // `.await` impede que o future seja `Send`.
+        let ceps: Vec<String> = ceps.iter().map(|cep| cep.to_string()).collect();
+
+        batch
+            .run(ceps, |cep_code| async move {
+                let result = self
+                    .fetch_cep(&cep_code)
+                    .await
+                    .map_err(|error| error.with_context(format!("cep {cep_code}")));
+
+                (cep_code, result)
+            })
+            .await
+    }
+
+    async fn cep_belongs_to_uf(&self, cep_code: &str, uf: &str) -> Result<bool, Error> {
+        let uf = ibge::resolve_uf(uf, client::resolves_state_names())?;
+        let cep = self.fetch_cep(cep_code).await?;
+
+        Ok(cep.state.trim().eq_ignore_ascii_case(uf.as_str()))
+    }
+
+    async fn resolve_cep_address(&self, cep_code: &str) -> Result<Address, Error> {
+        let cep = self.fetch_cep(cep_code).await?;
+        let uf = Uf::try_from(cep.state.trim())?;
+        let region = uf.region();
+
+        Ok(Address::new(cep, uf, region))
     }
 
     async fn validate_cep(&self, cep_code: &str) -> Result<bool, Error> {
-        let response = self.get_cep_request(cep_code).await;
+        self.validate_cep_versions(cep_code, client::preferred_version("cep"))
+            .await
+    }
 
-        match response {
+    async fn validate_cep_versions(
+        &self,
+        cep_code: &str,
+        preferred: Option<u8>,
+    ) -> Result<bool, Error> {
+        match self.get_cep_request_versions(cep_code, preferred).await {
             Ok(_) => Ok(true),
-            Err(e) => match e.code {
-                Some(404) => Ok(false),
-                _ => Err(e),
-            },
+            Err(e) if e.code == Some(404) || e.message == INVALID_CEP_MESSAGE => Ok(false),
+            Err(e) => Err(e),
         }
     }
 }
@@ -72,12 +217,19 @@ impl CepService {
 ///
 /// A busca utiliza como fonte principal o OpenCep, caso não encontre o CEP é buscado em diversos outros providers de CEP.
 ///
+/// A `v2` do endpoint é consultada primeiro, e a `v1` apenas quando a `v2` não existe no
+/// servidor; um CEP inexistente resulta em uma única requisição. A versão pode ser escolhida
+/// com `Config::prefer_version("cep", 1)`.
+///
 /// ### Argumento
 /// * `cep_code:&str` => CEP para ser consultado.
 ///
 /// ### Retorno
 /// * `Result<Cep, Error>`
 ///
+/// Um CEP que não possua 8 dígitos (a pontuação é ignorada) retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_CEP_MESSAGE`, sem que a API seja consultada.
+///
 /// # Exemplo
 /// ```
 /// use brasilapi::cep;
@@ -88,14 +240,44 @@ impl CepService {
 /// }
 /// ```
 pub async fn get_cep(cep_code: &str) -> Result<Cep, Error> {
-    let cep_service = CepService::new(BRASIL_API_URL);
+    let cep_service = CepService::new(client::base_url());
 
-    let response = cep_service.get_cep_request(cep_code).await?;
+    cep_service.fetch_cep(cep_code).await
+}
 
-    let body = response.text().await.unwrap();
-    let cep: Cep = serde_json::from_str(&body).unwrap();
+/// #### `lookup_many(ceps: &[&str], concurrency: usize)`
+/// Busca vários CEPs, com no máximo `concurrency` consultas simultâneas.
+///
+/// Os CEPs mal formatados retornam o erro `Errored::InvalidInput` de `get_cep` sem que a API
+/// seja consultada. A falha de um CEP não interrompe a busca dos demais.
+///
+/// ### Argumentos
+/// * `ceps:&[&str]` => CEPs para serem consultados.
+/// * `concurrency:usize` => Número máximo de consultas simultâneas (mínimo de 1).
+///
+/// ### Retorno
+/// * `Vec<(String, Result<Cep, Error>)>` => Cada CEP, como informado, e o resultado da sua
+///   busca, na mesma ordem de `ceps`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cep;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let results = cep::lookup_many(&["01001000", "20040-020"], 4).await;
+///
+///    for (cep, result) in results {
+///        println!("{}: {}", cep, result.is_ok());
+///    }
+/// }
+/// ```
+pub async fn lookup_many(ceps: &[&str], concurrency: usize) -> Vec<(String, Result<Cep, Error>)> {
+    let cep_service = CepService::new(client::base_url());
 
-    Ok(cep)
+    cep_service
+        .lookup_many_ceps(ceps, &Batch::new().concurrency(concurrency))
+        .await
 }
 
 /// #### `validate(cep_code: &str)`
@@ -107,6 +289,9 @@ pub async fn get_cep(cep_code: &str) -> Result<Cep, Error> {
 /// Retorno
 /// * `Result<bool, Error>`
 ///
+/// Um CEP mal formatado retorna `Ok(false)` sem que a API seja consultada. Uma versão
+/// inexistente escolhida com `Config::prefer_version` retorna o erro da configuração.
+///
 /// # Exemplo
 /// ```
 /// use brasilapi::cep;
@@ -116,10 +301,120 @@ pub async fn get_cep(cep_code: &str) -> Result<Cep, Error> {
 ///   let is_valid = cep::validate("01001000").await.unwrap();  
 /// }
 pub async fn validate(cep_code: &str) -> Result<bool, Error> {
-    let cep_service = CepService::new(BRASIL_API_URL);
+    let cep_service = CepService::new(client::base_url());
     cep_service.validate_cep(cep_code).await
 }
 
+/// #### `eq(a: &str, b: &str)`
+/// Compara dois CEPs ignorando a pontuação, de forma que `"01001-000"` é igual a `"01001000"`.
+///
+/// ### Argumentos
+/// * `a:&str` => Primeiro CEP.
+/// * `b:&str` => Segundo CEP.
+///
+/// ### Retorno
+/// * `bool` => `false` também quando algum dos CEPs não possui 8 dígitos.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cep;
+///
+/// assert!(cep::eq("01001-000", "01001000"));
+/// ```
+pub fn eq(a: &str, b: &str) -> bool {
+    match (normalize_cep(a), normalize_cep(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// #### `resolve_location(cep_code: &str)`
+/// Busca um CEP e as informações do seu estado e região no IBGE.
+///
+/// ### Argumento
+/// * `cep_code:&str` => CEP para ser consultado.
+///
+/// ### Retorno
+/// * `Result<ResolvedLocation, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cep;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let location = cep::resolve_location("01001000").await.unwrap();
+///
+///    println!("Estado: {}", location.state.get_name());
+///    println!("Região: {}", location.region().get_name());
+/// }
+/// ```
+pub async fn resolve_location(cep_code: &str) -> Result<ResolvedLocation, Error> {
+    let cep = get_cep(cep_code).await?;
+    let state = ibge::get_state(&cep.state).await?;
+
+    Ok(ResolvedLocation { cep, state })
+}
+
+/// #### `belongs_to_uf(cep_code: &str, uf: &str)`
+/// Busca um CEP e verifica se ele pertence à unidade federativa informada, por exemplo a
+/// selecionada pelo usuário em um formulário de endereço.
+///
+/// ### Argumentos
+/// * `cep_code:&str` => CEP para ser consultado.
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+///
+/// ### Retorno
+/// * `Result<bool, Error>`
+///
+/// Uma sigla inválida retorna um erro `Errored::InvalidInput`, sem que a API seja consultada.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cep;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let belongs = cep::belongs_to_uf("01001000", "sp").await.unwrap();
+/// }
+/// ```
+pub async fn belongs_to_uf(cep_code: &str, uf: &str) -> Result<bool, Error> {
+    let cep_service = CepService::new(client::base_url());
+
+    cep_service.cep_belongs_to_uf(cep_code, uf).await
+}
+
+/// #### `resolve_address(cep_code: &str)`
+/// Busca um CEP e retorna o endereço normalizado, com a região do estado obtida a partir da UF
+/// (veja `Uf::region`), por exemplo para preencher um formulário de endereço.
+///
+/// Os CEPs gerais de municípios, sem rua ou bairro, retornam `street` e `neighborhood` como
+/// `None`. Um CEP mal formatado retorna um erro `Errored::InvalidInput`, sem que a API seja
+/// consultada.
+///
+/// ### Argumento
+/// * `cep_code:&str` => CEP para ser consultado.
+///
+/// ### Retorno
+/// * `Result<Address, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cep;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let address = cep::resolve_address("01001000").await.unwrap();
+///
+///    println!("{}, {} - {}", address.street.unwrap_or_default(), address.city, address.uf);
+/// }
+/// ```
+pub async fn resolve_address(cep_code: &str) -> Result<Address, Error> {
+    let cep_service = CepService::new(client::base_url());
+
+    cep_service.resolve_cep_address(cep_code).await
+}
+
 #[cfg(test)]
 mod cep_tests {
     use super::*;
@@ -127,6 +422,120 @@ mod cep_tests {
     use reqwest::StatusCode;
     use serde_json::json;
 
+    #[tokio::test]
+    async fn test_lookup_many() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v2/01001000");
+                then.status(200).json_body(json!({
+                    "cep": "01001000",
+                    "state": "SP",
+                    "city": "São Paulo",
+                    "neighborhood": "Sé",
+                    "street": "Praça da Sé",
+                    "service": "open-cep"
+                }));
+            })
+            .await;
+
+        let cep_service = CepService::new(&server.base_url());
+        let results = cep_service
+            .lookup_many_ceps(&["01001-000", "123"], &Batch::new().concurrency(2))
+            .await;
+
+        assert_eq!(results[0].0, "01001-000");
+        assert_eq!(results[0].1.as_ref().unwrap().city, "São Paulo");
+        assert_eq!(results[1].0, "123");
+        assert_eq!(
+            results[1].1.as_ref().unwrap_err().error,
+            Errored::InvalidInput
+        );
+        assert_eq!(
+            results[1].1.as_ref().unwrap_err().context.as_deref(),
+            Some("cep 123")
+        );
+        mock.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn test_belongs_to_uf() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v2/01001000");
+                then.status(200).json_body(json!({
+                    "cep": "01001000",
+                    "state": "SP",
+                    "city": "São Paulo",
+                    "neighborhood": "Sé",
+                    "street": "Praça da Sé",
+                    "service": "open-cep"
+                }));
+            })
+            .await;
+
+        let cep_service = CepService::new(&server.base_url());
+
+        assert!(cep_service
+            .cep_belongs_to_uf("01001-000", " sp ")
+            .await
+            .unwrap());
+        assert!(!cep_service
+            .cep_belongs_to_uf("01001000", "RJ")
+            .await
+            .unwrap());
+
+        let error = cep_service
+            .cep_belongs_to_uf("01001000", "XX")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        mock.assert_hits_async(2).await;
+    }
+
+    #[tokio::test]
+    async fn test_resolve_address() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v2/69880000");
+                then.status(200).json_body(json!({
+                    "cep": "69880000",
+                    "state": "AM",
+                    "city": "Eirunepé",
+                    "neighborhood": null,
+                    "street": "",
+                    "service": "correios"
+                }));
+            })
+            .await;
+
+        let cep_service = CepService::new(&server.base_url());
+        let address = cep_service.resolve_cep_address("69880-000").await.unwrap();
+
+        assert_eq!(
+            address,
+            Address {
+                cep: "69880000".to_string(),
+                street: None,
+                neighborhood: None,
+                city: "Eirunepé".to_string(),
+                uf: Uf::Am,
+                region: Region::Norte,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eq() {
+        assert!(eq("01001-000", "01001000"));
+        assert!(eq("01.001-000", " 01001000 "));
+        assert!(!eq("01001000", "01001001"));
+        assert!(!eq("1001000", "01001000"));
+    }
+
     #[tokio::test]
     async fn get_cep_test() {
         let cep = get_cep("01001000").await.unwrap();
@@ -168,6 +577,143 @@ mod cep_tests {
         );
     }
 
+    #[tokio::test]
+    async fn get_cep_invalid_length_test() {
+        let error = get_cep("0100100").await.unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(error.message, "invalid cep: expected 8 digits");
+        assert_eq!(error.code, None);
+    }
+
+    #[test]
+    fn cep_versions_test() {
+        assert_eq!(cep_versions(None).unwrap(), vec![2, 1]);
+        assert_eq!(cep_versions(Some(1)).unwrap(), vec![1]);
+
+        let error = cep_versions(Some(9)).unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(
+            error.message,
+            format!("{UNSUPPORTED_VERSION_MESSAGE}: cep v9")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_cep_not_found_does_not_fall_back_test() {
+        let server = MockServer::start_async().await;
+        let v2 = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v2/99999999");
+                then.status(404).json_body(json!({
+                    "name": "CepPromiseError",
+                    "message": "Todos os serviços de CEP retornaram erro.",
+                    "type": "service_error",
+                    "errors": []
+                }));
+            })
+            .await;
+        let v1 = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v1/99999999");
+                then.status(404);
+            })
+            .await;
+
+        let cep_service = CepService::new(&server.base_url());
+        let error = cep_service
+            .get_cep_request_versions("99999999", None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::NotFound);
+        v2.assert_async().await;
+        v1.assert_hits_async(0).await;
+
+        cep_service
+            .get_cep_request_versions("99999999", Some(2))
+            .await
+            .unwrap_err();
+
+        v1.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn get_cep_falls_back_to_v1_test() {
+        let server = MockServer::start_async().await;
+        let v2 = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v2/01001000");
+                then.status(404);
+            })
+            .await;
+        let v1 = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/cep/v1/01001000");
+                then.status(200).json_body(json!({
+                    "cep": "01001000",
+                    "state": "SP",
+                    "city": "São Paulo",
+                    "neighborhood": "Sé",
+                    "street": "Praça da Sé",
+                    "service": "viacep"
+                }));
+            })
+            .await;
+
+        let cep_service = CepService::new(&server.base_url());
+        let response = cep_service
+            .get_cep_request_versions("01001000", None)
+            .await
+            .unwrap();
+        let cep: Cep = client::read_json(response).await.unwrap();
+
+        v2.assert_async().await;
+        v1.assert_async().await;
+        assert_eq!(cep.service, "viacep");
+    }
+
+    #[test]
+    fn normalize_cep_test() {
+        assert_eq!(normalize_cep("01001-000").unwrap(), "01001000");
+        assert!(normalize_cep("0100100a").is_err());
+    }
+
+    #[tokio::test]
+    async fn validate_invalid_length_test() {
+        let is_valid = validate("123").await.unwrap();
+
+        assert!(!is_valid);
+    }
+
+    #[tokio::test]
+    async fn validate_unsupported_version_test() {
+        let cep_service = CepService::new("http://127.0.0.1:1");
+        let error = cep_service
+            .validate_cep_versions("01001000", Some(9))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(
+            error.message,
+            format!("{UNSUPPORTED_VERSION_MESSAGE}: cep v9")
+        );
+        assert!(!cep_service
+            .validate_cep_versions("123", Some(9))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn resolve_location_test() {
+        let location = resolve_location("01001000").await.unwrap();
+
+        assert_eq!(location.state.get_sigla(), "SP");
+        assert_eq!(location.region().get_sigla(), "SE");
+    }
+
     #[tokio::test]
     async fn validate_test() {
         let cep = validate("01001000").await.unwrap();
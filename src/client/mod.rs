@@ -0,0 +1,1607 @@
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, VecDeque},
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+    time::Duration,
+};
+
+use futures::Stream;
+pub use reqwest::header::HeaderMap;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Request, Response};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    cache,
+    error::{Error, Errored, ALREADY_CONFIGURED_MESSAGE, INVALID_UTF8_MESSAGE},
+    parse::{self, DuplicateKeys, ParseMode},
+    retry::RetryBudget,
+    spec::BRASIL_API_URL,
+    transport,
+};
+
+const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+/// Configuração do cliente compartilhado pelas funções públicas da biblioteca.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Endereço base da API, por padrão `https://brasilapi.com.br`.
+    pub base_url: String,
+    /// Prefixo inserido entre `base_url` e `/api`, para APIs servidas sob um caminho de um
+    /// gateway, por exemplo `/brasilapi`. Vazio por padrão.
+    pub base_path: String,
+    /// Tempo limite de cada requisição, da conexão até a leitura completa do corpo. Sem limite
+    /// por padrão.
+    pub timeout: Option<Duration>,
+    /// Tempo limite apenas para estabelecer a conexão, incluindo a negociação TLS. Permite
+    /// falhar rapidamente em conexões lentas mantendo um `timeout` maior para a leitura de
+    /// respostas grandes. Sem limite por padrão.
+    pub connect_timeout: Option<Duration>,
+    /// Valor do cabeçalho `User-Agent`. Nenhum cabeçalho é enviado por padrão.
+    pub user_agent: Option<String>,
+    /// Intervalo do TCP keep-alive. Desativado por padrão.
+    pub tcp_keepalive: Option<Duration>,
+    /// Tempo que conexões ociosas permanecem no pool. O padrão do reqwest é de 90 segundos.
+    pub pool_idle_timeout: Option<Duration>,
+    /// Intervalo entre os pings de keep-alive do HTTP/2. Desativado por padrão.
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// Tempo de espera pela resposta de um ping de keep-alive do HTTP/2 antes de encerrar a
+    /// conexão.
+    pub http2_keep_alive_timeout: Option<Duration>,
+    /// Usa HTTP/2 sem negociação via ALPN. Por padrão o HTTP/2 é usado quando o servidor o
+    /// oferece durante a negociação TLS.
+    pub http2_prior_knowledge: bool,
+    /// Número máximo de redirecionamentos seguidos em uma requisição, por padrão 5. Com `0`
+    /// nenhum redirecionamento é seguido. Exceder o limite retorna um erro
+    /// `Errored::TooManyRedirects`.
+    pub max_redirects: usize,
+    /// Registra o corpo de cada resposta em um evento de nível `trace`, com a feature `tracing`.
+    /// Desativado por padrão.
+    pub log_bodies: bool,
+    /// Campos JSON cujos valores são substituídos por `[REDACTED]` antes do registro do corpo,
+    /// em qualquer nível do documento, por exemplo `cnpj_cpf_do_socio`. Com algum campo
+    /// configurado, corpos que não são JSON não são registrados.
+    pub redacted_fields: Vec<String>,
+    /// Versão preferida de cada endpoint que possui mais de uma versão, indexada pelo nome do
+    /// endpoint, por exemplo `cep`. Vazio por padrão, usando a versão mais recente. Veja
+    /// `Config::prefer_version`.
+    pub preferred_versions: BTreeMap<String, u8>,
+    /// Cabeçalho que recebe o identificador de correlação de cada requisição, por exemplo
+    /// `X-Correlation-ID`. Nenhum cabeçalho é enviado por padrão. Veja `with_correlation_id`.
+    pub correlation_header: Option<String>,
+    /// Como as listas retornadas por todos os módulos são desserializadas. Com
+    /// `ParseMode::Lenient`, elementos inválidos são ignorados em vez de invalidar a lista
+    /// inteira. Com `ParseMode::CaptureExtras`, os campos recebidos que o modelo não possui são
+    /// registrados em eventos de nível `warn`, com a feature `tracing`. `ParseMode::Strict` por
+    /// padrão. Funções que recebem um `ParseMode`, como `ibge::get_municipalities_with_mode`,
+    /// usam o modo informado e retornam os campos em `Parsed::get_extras`. Respostas com um
+    /// único objeto, como a de `cep::get_cep`, não são afetadas.
+    pub parse_mode: ParseMode,
+    /// Como chaves repetidas em um mesmo objeto das respostas, como em `{"id": 1, "id": 2}`,
+    /// são tratadas. Por padrão `DuplicateKeys::Allow`, mantendo o último valor como o
+    /// serde_json. Com `DuplicateKeys::Reject`, a consulta retorna um erro
+    /// `Errored::Deserialization`.
+    pub duplicate_keys: DuplicateKeys,
+    /// Limite de novas tentativas compartilhado por todas as chamadas de `retry::with_retry`.
+    /// Sem limite por padrão, cada chamada repete de acordo apenas com a sua `RetryPolicy`.
+    pub retry_budget: Option<RetryBudget>,
+    /// Tempo total de uma chamada de `retry::with_retry`, somando todas as tentativas e os
+    /// atrasos entre elas. Uma tentativa em andamento quando o prazo termina é interrompida, e
+    /// uma nova tentativa cujo atraso ultrapassaria o prazo não é feita; nos dois casos o erro
+    /// retornado é `Errored::DeadlineExceeded`. Sem limite por padrão. O `timeout` continua
+    /// limitando cada requisição individualmente.
+    pub retry_deadline: Option<Duration>,
+    /// Aceita o nome completo do estado, sem diferenciar acentos e maiúsculas, nas funções do
+    /// módulo `ibge` que recebem uma sigla, como `get_municipalities` e `get_state`, por exemplo
+    /// `"são paulo"` no lugar de `"SP"`. Desativado por padrão, aceitando apenas siglas.
+    pub resolve_state_names: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: BRASIL_API_URL.to_string(),
+            base_path: String::new(),
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            tcp_keepalive: None,
+            pool_idle_timeout: None,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            http2_prior_knowledge: false,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            log_bodies: false,
+            redacted_fields: Vec::new(),
+            preferred_versions: BTreeMap::new(),
+            correlation_header: None,
+            parse_mode: ParseMode::Strict,
+            duplicate_keys: DuplicateKeys::Allow,
+            retry_budget: None,
+            retry_deadline: None,
+            resolve_state_names: false,
+        }
+    }
+}
+
+impl Config {
+    /// Define `base_path`, por exemplo `Config::default().with_base_path("/brasilapi")`.
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.base_path = base_path.to_string();
+        self
+    }
+
+    /// Define a versão consultada de um endpoint, por exemplo
+    /// `Config::default().prefer_version("cep", 1)`.
+    ///
+    /// Quando a versão escolhida responde `404`, as versões anteriores são consultadas em
+    /// seguida, inclusive para um CEP inexistente. Sem uma versão escolhida, a `v1` só é
+    /// consultada quando a `v2` não existe no servidor. Atualmente apenas o endpoint `cep` possui
+    /// mais de uma versão: a `v2`, padrão, inclui as coordenadas do endereço quando disponíveis.
+    /// Uma versão inexistente, como `prefer_version("cep", 9)`, faz as consultas retornarem um
+    /// erro `Errored::InvalidInput` com a mensagem `UNSUPPORTED_VERSION_MESSAGE`.
+    pub fn prefer_version(mut self, endpoint: &str, version: u8) -> Self {
+        self.preferred_versions
+            .insert(endpoint.to_string(), version);
+        self
+    }
+
+    /// Endereço usado antes de `/api`, combinando `base_url` e `base_path`.
+    fn api_root(&self) -> String {
+        let base_url = trim_base_url(&self.base_url);
+        let base_path = self.base_path.trim_matches('/');
+
+        match base_path.is_empty() {
+            true => base_url.to_string(),
+            false => format!("{}/{}", base_url, base_path),
+        }
+    }
+
+    fn build_client(&self) -> Result<reqwest::Client, Error> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        builder = builder.tcp_keepalive(self.tcp_keepalive);
+
+        builder = builder.redirect(match self.max_redirects {
+            0 => reqwest::redirect::Policy::none(),
+            limit => reqwest::redirect::Policy::limited(limit),
+        });
+
+        builder.build().map_err(Error::from_error)
+    }
+}
+
+type RequestHook = Box<dyn FnMut(&mut Request) + Send>;
+type ResponseHook = Box<dyn FnMut(&Response) + Send>;
+
+struct Hooks {
+    request: Vec<RequestHook>,
+    response: Vec<ResponseHook>,
+}
+
+static HOOKS: Mutex<Hooks> = Mutex::new(Hooks {
+    request: Vec::new(),
+    response: Vec::new(),
+});
+
+/// Configuração em uso e o cliente criado a partir dela, definidos juntos para que uma
+/// requisição concorrente a `configure` não crie o cliente com outra configuração.
+static CLIENT: OnceLock<(Config, Result<reqwest::Client, Error>)> = OnceLock::new();
+
+static API_ROOT: OnceLock<String> = OnceLock::new();
+
+fn hooks() -> MutexGuard<'static, Hooks> {
+    HOOKS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn shared() -> &'static (Config, Result<reqwest::Client, Error>) {
+    CLIENT.get_or_init(|| {
+        let config = Config::default();
+        let client = config.build_client();
+
+        (config, client)
+    })
+}
+
+fn config() -> &'static Config {
+    &shared().0
+}
+
+/// Cliente HTTP compartilhado, criado com a configuração em uso. Uma falha ao criá-lo é
+/// retornada em todas as requisições.
+pub(crate) fn http_client() -> Result<&'static reqwest::Client, Error> {
+    shared().1.as_ref().map_err(Clone::clone)
+}
+
+/// Configuração definida com `configure`, sem criar a configuração padrão, para leituras que
+/// podem acontecer antes de qualquer requisição e não devem impedir uma chamada posterior a
+/// `configure`.
+fn configured() -> Option<&'static Config> {
+    CLIENT.get().map(|(config, _)| config)
+}
+
+/// Versão de `endpoint` definida com `Config::prefer_version`.
+pub(crate) fn preferred_version(endpoint: &str) -> Option<u8> {
+    configured().and_then(|config| config.preferred_versions.get(endpoint).copied())
+}
+
+/// Modo de desserialização de listas definido em `Config::parse_mode`.
+pub(crate) fn parse_mode() -> ParseMode {
+    config().parse_mode
+}
+
+/// Indica se `Config::resolve_state_names` está ativo.
+pub(crate) fn resolves_state_names() -> bool {
+    configured().is_some_and(|config| config.resolve_state_names)
+}
+
+/// Limite de novas tentativas definido em `Config::retry_budget`.
+pub(crate) fn retry_budget() -> Option<&'static RetryBudget> {
+    config().retry_budget.as_ref()
+}
+
+/// Tempo total das novas tentativas definido em `Config::retry_deadline`.
+pub(crate) fn retry_deadline() -> Option<Duration> {
+    config().retry_deadline
+}
+
+/// Endereço base usado pelas funções públicas dos módulos.
+pub(crate) fn base_url() -> &'static str {
+    API_ROOT.get_or_init(|| config().api_root())
+}
+
+/// Endereço base sem a barra final, para que as URLs montadas como `{base_url}/api/...` não
+/// contenham `//api` quando o endereço é copiado com a barra.
+pub(crate) fn trim_base_url(base_url: &str) -> &str {
+    base_url.trim_end_matches('/')
+}
+
+/// #### `configure(config: Config)`
+/// Define a configuração do cliente compartilhado pelas funções públicas da biblioteca.
+///
+/// Deve ser chamada uma única vez, antes da primeira requisição. Chamadas posteriores retornam
+/// um erro `Errored::InvalidInput` com a mensagem `ALREADY_CONFIGURED_MESSAGE`, mantendo a
+/// configuração existente. Os serviços criados com `new(base_url)` continuam usando o endereço
+/// informado, mas compartilham o mesmo cliente.
+///
+/// ### Argumentos
+/// * `config:Config` => Configuração do cliente.
+///
+/// ### Retorno
+/// * `Result<(), Error>`
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::client::{self, Config};
+///
+/// client::configure(Config {
+///     timeout: Some(Duration::from_secs(10)),
+///     user_agent: Some("minha-aplicacao/1.0".to_string()),
+///     ..Config::default()
+/// })
+/// .unwrap();
+/// ```
+pub fn configure(config: Config) -> Result<(), Error> {
+    let client = config.build_client()?;
+
+    CLIENT
+        .set((config, Ok(client)))
+        .map_err(|_| Error::invalid_input(ALREADY_CONFIGURED_MESSAGE))
+}
+
+/// Informações da biblioteca e da configuração em uso, retornadas por `info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// Versão do crate brasilapi.
+    pub crate_version: &'static str,
+    /// Endereço usado antes de `/api`, combinando `Config::base_url` e `Config::base_path`.
+    pub base_url: String,
+    /// Valor do cabeçalho `User-Agent`, quando configurado.
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION"),
+            base_url: config.api_root(),
+            user_agent: config.user_agent.clone(),
+        }
+    }
+}
+
+/// #### `info()`
+/// Retorna a versão da biblioteca e a configuração efetiva do cliente compartilhado, útil em
+/// diagnósticos e relatos de problemas.
+///
+/// A consulta não cria o cliente: chamar `info` antes de `configure` retorna a configuração
+/// padrão, sem impedir que `configure` seja chamada depois.
+///
+/// ### Retorno
+/// * `ClientInfo`
+///
+/// # Exemplo
+/// ```
+/// let info = brasilapi::info();
+///
+/// println!("{:?}", info);
+/// ```
+pub fn info() -> ClientInfo {
+    match configured() {
+        Some(config) => ClientInfo::from_config(config),
+        None => ClientInfo::from_config(&Config::default()),
+    }
+}
+
+/// Caminho consultado por `BrasilApi::connect` para verificar a conexão: um único estado, uma das
+/// respostas mais leves da API.
+const HEALTH_PROBE_PATH: &str = "/api/ibge/uf/v1/SP";
+
+/// Cliente compartilhado já configurado e com a conexão à BrasilAPI verificada.
+///
+/// Diferente do cliente criado sob demanda na primeira consulta, `BrasilApi::connect` garante que
+/// a API está acessível antes que a aplicação continue, para serviços que não devem iniciar
+/// sem ela. As funções públicas dos módulos passam a usar a configuração informada.
+#[derive(Debug, Clone)]
+pub struct BrasilApi {
+    base_url: String,
+}
+
+impl BrasilApi {
+    /// #### `connect(config: Config)`
+    /// Realiza uma consulta leve à API, sem passar pelo cache, e configura o cliente
+    /// compartilhado, como `configure`.
+    ///
+    /// A configuração só é aplicada se a consulta tiver sucesso, então `connect` pode ser chamada
+    /// novamente após uma falha, por exemplo quando a rede ainda não está disponível na
+    /// inicialização.
+    ///
+    /// ### Argumentos
+    /// * `config:Config` => Configuração do cliente.
+    ///
+    /// ### Retorno
+    /// * `Result<BrasilApi, Error>` => Erro de `configure` ou da consulta, caso a API esteja
+    ///   inacessível ou responda com erro.
+    ///
+    /// # Exemplo
+    /// ```
+    /// use brasilapi::{BrasilApi, Config};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let api = BrasilApi::connect(Config::default()).await.unwrap();
+    /// }
+    /// ```
+    pub async fn connect(config: Config) -> Result<Self, Error> {
+        let client = config.build_client()?;
+
+        probe(&client, &config.api_root()).await?;
+
+        CLIENT
+            .set((config, Ok(client)))
+            .map_err(|_| Error::invalid_input(ALREADY_CONFIGURED_MESSAGE))?;
+
+        Ok(Self {
+            base_url: base_url().to_string(),
+        })
+    }
+
+    /// Endereço base usado pelas funções públicas dos módulos.
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// Verifica se a API em `base_url` responde com sucesso usando `client`.
+async fn probe(client: &reqwest::Client, base_url: &str) -> Result<(), Error> {
+    let response = client
+        .get(format!("{base_url}{HEALTH_PROBE_PATH}"))
+        .send()
+        .await
+        .map_err(Error::from_error)?;
+
+    Error::from_response(response).await.map(|_| ())
+}
+
+/// #### `with_timeout(timeout: Duration, operation: F)`
+/// Limita o tempo de uma única consulta, sem alterar o tempo limite do cliente compartilhado.
+///
+/// Se `operation` não terminar dentro de `timeout`, ela é cancelada e um erro
+/// `Errored::Timeout` é retornado.
+///
+/// ### Argumentos
+/// * `timeout:Duration` => Tempo limite da consulta.
+/// * `operation:F` => Consulta, por exemplo `ibge::get_state("SP")`.
+///
+/// ### Retorno
+/// * `Result<T, Error>`
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{client, ibge};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let state = client::with_timeout(Duration::from_millis(500), ibge::get_state("SP")).await;
+/// }
+/// ```
+pub async fn with_timeout<T, F>(timeout: Duration, operation: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    match tokio::time::timeout(timeout, operation).await {
+        Ok(result) => result,
+        Err(_) => Err(Error::new(
+            format!("operation timed out after {:?}", timeout),
+            Errored::Timeout,
+            None,
+        )),
+    }
+}
+
+/// #### `on_request(hook: F)`
+/// Registra uma função executada antes de toda requisição feita pela biblioteca.
+///
+/// A requisição pode ser alterada, por exemplo para incluir cabeçalhos de autenticação
+/// exigidos por um gateway.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::client;
+/// use reqwest::header::{HeaderValue, AUTHORIZATION};
+///
+/// client::on_request(|request| {
+///     request
+///         .headers_mut()
+///         .insert(AUTHORIZATION, HeaderValue::from_static("Bearer token"));
+/// });
+/// ```
+pub fn on_request<F>(hook: F)
+where
+    F: FnMut(&mut Request) + Send + 'static,
+{
+    hooks().request.push(Box::new(hook));
+}
+
+/// #### `on_response(hook: F)`
+/// Registra uma função executada após toda resposta recebida pela biblioteca, inclusive as
+/// respostas de erro, antes que o corpo seja lido.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::client;
+///
+/// client::on_response(|response| {
+///     println!("{} {}", response.status(), response.url());
+/// });
+/// ```
+pub fn on_response<F>(hook: F)
+where
+    F: FnMut(&Response) + Send + 'static,
+{
+    hooks().response.push(Box::new(hook));
+}
+
+/// #### `clear_hooks()`
+/// Remove todas as funções registradas com `on_request` e `on_response`.
+pub fn clear_hooks() {
+    let mut hooks = hooks();
+
+    hooks.request.clear();
+    hooks.response.clear();
+}
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+static CORRELATION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// #### `with_correlation_id(id: &str, operation: F)`
+/// Executa `operation` enviando `id` como identificador de correlação em todas as requisições
+/// feitas por ela, inclusive em futures aguardados dentro de `operation`.
+///
+/// O identificador é guardado em um `tokio::task_local`, então tasks iniciadas com
+/// `tokio::spawn` dentro de `operation` não o recebem; envolva o future da task em
+/// `with_correlation_id` para propagá-lo.
+///
+/// O identificador só é enviado com `Config::correlation_header` definido. Sem um identificador
+/// informado por esta função, cada requisição usa o identificador do span atual, com a feature
+/// `tracing`, ou um identificador gerado aleatoriamente. Com a feature `tracing`, o
+/// identificador também é registrado no evento de cada requisição. Transportes definidos com
+/// `transport::set_transport` não recebem o cabeçalho.
+///
+/// ### Argumentos
+/// * `id:&str` => Identificador de correlação, por exemplo o recebido pela aplicação.
+/// * `operation:F` => Consulta, por exemplo `ibge::get_state("SP")`.
+///
+/// ### Retorno
+/// * `F::Output`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{client, ibge};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let state = client::with_correlation_id("pedido-1234", ibge::get_state("SP")).await;
+/// }
+/// ```
+pub async fn with_correlation_id<F: Future>(id: &str, operation: F) -> F::Output {
+    CORRELATION_ID.scope(id.to_string(), operation).await
+}
+
+/// Identificador de correlação da requisição atual.
+fn correlation_id() -> String {
+    if let Ok(id) = CORRELATION_ID.try_with(|id| id.clone()) {
+        return id;
+    }
+
+    #[cfg(feature = "tracing")]
+    if let Some(id) = tracing::Span::current().id() {
+        return format!("{:016x}", id.into_u64());
+    }
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(CORRELATION_COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// Insere o identificador de correlação no cabeçalho `header` da requisição e o retorna.
+fn insert_correlation_id(request: &mut Request, header: &str) -> String {
+    let id = correlation_id();
+
+    if let (Ok(name), Ok(value)) = (
+        HeaderName::from_bytes(header.as_bytes()),
+        HeaderValue::from_str(&id),
+    ) {
+        request.headers_mut().insert(name, value);
+    }
+
+    id
+}
+
+/// Realiza uma requisição `GET`, executando os hooks registrados, e retorna um erro caso o
+/// status da resposta seja diferente de 200.
+pub(crate) async fn get(url: &str) -> Result<Response, Error> {
+    cache::get(url, |if_modified_since| fetch(url, if_modified_since)).await
+}
+
+/// Igual a `get`, sem consultar nem alimentar o cache.
+///
+/// Usada pelas leituras incrementais do corpo, como `stream_list` e `read_bytes_with_progress`:
+/// o cache lê o corpo inteiro antes de retornar a resposta, o que as tornaria inúteis.
+pub(crate) async fn get_uncached(url: &str) -> Result<Response, Error> {
+    fetch(url, None).await
+}
+
+/// Igual a `get`, consultando cada uma das `urls` em ordem enquanto `falls_back` aceitar o erro
+/// da anterior.
+pub(crate) async fn get_with_fallback<F>(urls: &[String], falls_back: F) -> Result<Response, Error>
+where
+    F: Fn(&Error) -> bool,
+{
+    let mut result = Err(Error::invalid_input("no url to request"));
+
+    for url in urls {
+        result = get(url).await;
+
+        match &result {
+            Err(error) if falls_back(error) => continue,
+            _ => break,
+        }
+    }
+
+    result
+}
+
+/// Realiza a requisição sem consultar o cache.
+///
+/// Com `if_modified_since`, a requisição é condicional e o servidor pode responder
+/// `304 Not Modified`, que não é tratado como erro.
+async fn fetch(url: &str, if_modified_since: Option<HeaderValue>) -> Result<Response, Error> {
+    #[cfg(feature = "metrics")]
+    let started = std::time::Instant::now();
+
+    let result = execute(url, if_modified_since).await;
+
+    #[cfg(feature = "metrics")]
+    record_metrics(url, &result, started.elapsed());
+
+    result
+}
+
+async fn execute(url: &str, if_modified_since: Option<HeaderValue>) -> Result<Response, Error> {
+    if let Some(transport) = transport::current() {
+        return transport::get(transport.as_ref(), url).await;
+    }
+
+    let client = http_client()?;
+
+    let mut request = client.get(url).build().map_err(Error::from_error)?;
+
+    if let Some(if_modified_since) = if_modified_since {
+        request
+            .headers_mut()
+            .insert(reqwest::header::IF_MODIFIED_SINCE, if_modified_since);
+    }
+
+    if let Some(header) = &config().correlation_header {
+        let _correlation_id = insert_correlation_id(&mut request, header);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(url = %request.url(), correlation_id = %_correlation_id, "sending request");
+    }
+
+    for hook in hooks().request.iter_mut() {
+        hook(&mut request);
+    }
+
+    let response = client.execute(request).await.map_err(Error::from_error)?;
+
+    for hook in hooks().response.iter_mut() {
+        hook(&response);
+    }
+
+    Error::from_response(response).await
+}
+
+/// Nome do endpoint usado como rótulo das métricas: o primeiro segmento após `/api/`.
+#[cfg(feature = "metrics")]
+fn endpoint_label(url: &str) -> String {
+    let path = reqwest::Url::parse(url)
+        .map(|url| url.path().to_string())
+        .unwrap_or_default();
+
+    path.split('/')
+        .skip_while(|segment| *segment != "api")
+        .nth(1)
+        .filter(|segment| !segment.is_empty())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Classe do status da resposta (`2xx`, `4xx`, ...), ou `error` quando não houve resposta.
+#[cfg(feature = "metrics")]
+fn status_class(result: &Result<Response, Error>) -> &'static str {
+    let code = match result {
+        Ok(response) => Some(response.status().as_u16()),
+        Err(error) => error.code,
+    };
+
+    match code {
+        Some(100..=199) => "1xx",
+        Some(200..=299) => "2xx",
+        Some(300..=399) => "3xx",
+        Some(400..=499) => "4xx",
+        Some(500..=599) => "5xx",
+        _ => "error",
+    }
+}
+
+/// Registra as métricas de uma requisição:
+/// * `brasilapi_requests_total` - contador por `endpoint` e `status`.
+/// * `brasilapi_request_duration_seconds` - histograma da latência por `endpoint`.
+#[cfg(feature = "metrics")]
+fn record_metrics(url: &str, result: &Result<Response, Error>, elapsed: Duration) {
+    let endpoint = endpoint_label(url);
+
+    metrics::counter!(
+        "brasilapi_requests_total",
+        "endpoint" => endpoint.clone(),
+        "status" => status_class(result),
+    )
+    .increment(1);
+
+    metrics::histogram!("brasilapi_request_duration_seconds", "endpoint" => endpoint)
+        .record(elapsed.as_secs_f64());
+}
+
+/// Lê o corpo da resposta como texto, sem entrar em pânico caso a leitura falhe.
+///
+/// O corpo é decodificado como UTF-8, a codificação exigida para JSON. Bytes inválidos não são
+/// substituídos, o que corromperia nomes acentuados sem aviso: a leitura retorna um erro
+/// `Errored::Deserialization` com a mensagem `INVALID_UTF8_MESSAGE`. Para tratar respostas em
+/// outra codificação, use `get_bytes`.
+pub(crate) async fn read_body(response: Response) -> Result<String, Error> {
+    #[cfg(feature = "tracing")]
+    let url = response.url().clone();
+
+    let body = String::from_utf8(read_bytes(response).await?)
+        .map_err(|error| invalid_utf8_error(error.utf8_error()))?;
+
+    #[cfg(feature = "tracing")]
+    log_body(&url, &body);
+
+    Ok(body)
+}
+
+/// Igual a `read_body`, mas mantém o corpo como bytes, que são desserializados diretamente
+/// com `serde_json::from_slice`. O UTF-8 é verificado sem que uma `String` seja criada.
+pub(crate) async fn read_body_bytes(response: Response) -> Result<Vec<u8>, Error> {
+    #[cfg(feature = "tracing")]
+    let url = response.url().clone();
+
+    let body = read_bytes(response).await?;
+    let _text = std::str::from_utf8(&body).map_err(invalid_utf8_error)?;
+    parse::check_duplicate_keys(&body, config().duplicate_keys)?;
+
+    #[cfg(feature = "tracing")]
+    log_body(&url, _text);
+
+    Ok(body)
+}
+
+fn invalid_utf8_error(error: std::str::Utf8Error) -> Error {
+    Error::new(
+        format!(
+            "{INVALID_UTF8_MESSAGE}: invalid byte at position {}",
+            error.valid_up_to()
+        ),
+        Errored::Deserialization,
+        None,
+    )
+}
+
+#[cfg(feature = "tracing")]
+fn log_body(url: &reqwest::Url, body: &str) {
+    tracing::debug!(url = %url, bytes = body.len(), "response body read");
+
+    if config().log_bodies {
+        match redact(body, &config().redacted_fields) {
+            Some(redacted) => tracing::trace!(url = %url, body = %redacted, "response body"),
+            None => tracing::trace!(url = %url, "response body is not JSON, not logged"),
+        }
+    }
+}
+
+/// Corpo da resposta com os valores de `fields` substituídos, em qualquer nível do JSON. Sem
+/// campos, o corpo é retornado sem alterações; com campos, um corpo que não é JSON resulta em
+/// `None`, para que ele não seja registrado sem a remoção dos dados.
+#[cfg(feature = "tracing")]
+fn redact(body: &str, fields: &[String]) -> Option<String> {
+    fn redact_value(value: &mut serde_json::Value, fields: &[String]) {
+        match value {
+            serde_json::Value::Object(object) => {
+                for (key, value) in object.iter_mut() {
+                    match fields.contains(key) {
+                        true => *value = serde_json::Value::from("[REDACTED]"),
+                        false => redact_value(value, fields),
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    redact_value(value, fields);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if fields.is_empty() {
+        return Some(body.to_string());
+    }
+
+    let mut value: serde_json::Value = serde_json::from_str(body).ok()?;
+    redact_value(&mut value, fields);
+
+    Some(value.to_string())
+}
+
+/// Lê o corpo da resposta sem interpretá-lo como texto. O buffer lido pelo reqwest é
+/// reaproveitado, sem cópia, quando não é compartilhado.
+pub(crate) async fn read_bytes(response: Response) -> Result<Vec<u8>, Error> {
+    let body = response.bytes().await.map_err(Error::from_error)?;
+
+    Ok(Vec::from(body))
+}
+
+/// Lê o corpo da resposta parte por parte, chamando `on_progress` com o total de bytes recebidos
+/// após cada parte. A resposta deve ser obtida com `get_uncached`, pois uma resposta do cache já
+/// foi lida por inteiro e resultaria em uma única chamada.
+pub(crate) async fn read_bytes_with_progress<F: Fn(u64)>(
+    mut response: Response,
+    on_progress: F,
+) -> Result<Vec<u8>, Error> {
+    let capacity = response.content_length().unwrap_or_default();
+    let mut body = Vec::with_capacity(usize::try_from(capacity).unwrap_or_default());
+
+    while let Some(chunk) = response.chunk().await.map_err(Error::from_error)? {
+        body.extend_from_slice(&chunk);
+        on_progress(body.len() as u64);
+    }
+
+    Ok(body)
+}
+
+/// #### `get_bytes(path: &str)`
+/// Consulta um caminho da API e retorna o corpo da resposta sem convertê-lo para texto.
+///
+/// As funções dos módulos leem as respostas como UTF-8. Para respostas em outra codificação ou
+/// com conteúdo binário, por exemplo de um espelho da API, esta função permite que o corpo seja
+/// tratado pelo chamador. O endereço base, os hooks e o cache são os mesmos das demais consultas.
+///
+/// ### Argumento
+/// * `path:&str` => Caminho a partir do endereço base, por exemplo `/api/cep/v2/01001000`.
+///
+/// ### Retorno
+/// * `Result<Vec<u8>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let body = client::get_bytes("/api/banks/v1/1").await.unwrap();
+/// }
+/// ```
+pub async fn get_bytes(path: &str) -> Result<Vec<u8>, Error> {
+    get_bytes_from(base_url(), path).await
+}
+
+async fn get_bytes_from(base_url: &str, path: &str) -> Result<Vec<u8>, Error> {
+    let path = path.trim_start_matches('/');
+    let response = get(&format!("{base_url}/{path}")).await?;
+
+    read_bytes(response).await
+}
+
+/// #### `get_bytes_with_progress(path: &str, on_progress: F)`
+/// Igual a `get_bytes`, chamando `on_progress` com o total de bytes recebidos conforme o corpo é
+/// baixado, por exemplo para exibir uma barra de progresso.
+///
+/// O total esperado, quando informado pelo servidor, é o cabeçalho `Content-Length`; respostas
+/// comprimidas ou sem esse cabeçalho não permitem calcular a porcentagem. A requisição não passa
+/// pelo cache de `cache::enable`, que entregaria o corpo já baixado.
+///
+/// ### Argumentos
+/// * `path:&str` => Caminho a partir do endereço base, por exemplo `/api/pix/v1/participants`.
+/// * `on_progress:F` => Função chamada com o total de bytes recebidos até o momento.
+///
+/// ### Retorno
+/// * `Result<Vec<u8>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let body = client::get_bytes_with_progress("/api/pix/v1/participants", |received| {
+///        println!("{received} bytes");
+///    })
+///    .await
+///    .unwrap();
+/// }
+/// ```
+pub async fn get_bytes_with_progress<F>(path: &str, on_progress: F) -> Result<Vec<u8>, Error>
+where
+    F: Fn(u64) + Send,
+{
+    get_bytes_with_progress_from(base_url(), path, on_progress).await
+}
+
+async fn get_bytes_with_progress_from<F: Fn(u64)>(
+    base_url: &str,
+    path: &str,
+    on_progress: F,
+) -> Result<Vec<u8>, Error> {
+    let path = path.trim_start_matches('/');
+    let response = get_uncached(&format!("{base_url}/{path}")).await?;
+
+    read_bytes_with_progress(response, on_progress).await
+}
+
+/// Lê o corpo da resposta e o desserializa como JSON.
+pub(crate) async fn read_json<T: DeserializeOwned>(response: Response) -> Result<T, Error> {
+    let body = read_body_bytes(response).await?;
+
+    parse_json(&body)
+}
+
+/// Desserializa o corpo de uma resposta.
+///
+/// Com a feature `simd-json`, o corpo é interpretado pelo simd-json no lugar do serde_json. O
+/// simd-json altera o corpo durante a interpretação, então o corpo é copiado uma vez antes. A
+/// biblioteca não possui medições comparando os dois; a diferença de desempenho depende do
+/// processador e do tamanho da resposta. As mensagens de erro de desserialização são as do
+/// simd-json, diferentes das do serde_json.
+///
+/// Um corpo vazio, ou apenas com espaços, retorna um erro `Errored::EmptyResponse`.
+pub(crate) fn parse_json<T: DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    if body.trim_ascii().is_empty() {
+        return Err(Error::empty_response());
+    }
+
+    #[cfg(feature = "simd-json")]
+    return simd_json::serde::from_slice(&mut body.to_vec()).map_err(|error| {
+        Error::from_api_error_body(body)
+            .unwrap_or_else(|| Error::new(error.to_string(), Errored::Deserialization, None))
+    });
+
+    #[cfg(not(feature = "simd-json"))]
+    serde_json::from_slice(body).map_err(|error| Error::from_json_body_error(body, error))
+}
+
+/// Desserializa uma lista de acordo com `Config::parse_mode`. No modo `ParseMode::Lenient`, os
+/// elementos inválidos são descartados e, com a feature `tracing`, registrados em eventos de
+/// nível `warn`, assim como os campos não modelados no modo `ParseMode::CaptureExtras`.
+pub(crate) async fn read_list<T: DeserializeOwned + Serialize>(
+    response: Response,
+) -> Result<Vec<T>, Error> {
+    let body = read_body_bytes(response).await?;
+
+    parse_configured_list(&body)
+}
+
+/// Igual a `read_list`, chamando `on_progress` com o total de bytes recebidos a cada parte do
+/// corpo lida.
+pub(crate) async fn read_list_with_progress<T, F>(
+    response: Response,
+    on_progress: F,
+) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned + Serialize,
+    F: Fn(u64),
+{
+    let body = read_bytes_with_progress(response, on_progress).await?;
+    std::str::from_utf8(&body).map_err(invalid_utf8_error)?;
+    parse::check_duplicate_keys(&body, config().duplicate_keys)?;
+
+    parse_configured_list(&body)
+}
+
+/// Igual a `parse_json` para listas, de acordo com `Config::parse_mode`.
+pub(crate) fn parse_configured_list<T: DeserializeOwned + Serialize>(
+    body: &[u8],
+) -> Result<Vec<T>, Error> {
+    let parsed = parse::parse_list(body, config().parse_mode)?;
+
+    #[cfg(feature = "tracing")]
+    for failure in parsed.get_failures() {
+        tracing::warn!(
+            index = failure.get_index(),
+            error = %failure.get_message(),
+            "invalid list item skipped"
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    for field in parsed.get_extras() {
+        tracing::warn!(field = %field, "unmodeled response field");
+    }
+
+    Ok(parsed.into_items())
+}
+
+/// Estado de `stream_list` entre a leitura de dois elementos.
+struct ListStream {
+    response: Option<Response>,
+    splitter: parse::ArraySplitter,
+    pending: VecDeque<Vec<u8>>,
+    index: usize,
+    mode: ParseMode,
+    duplicate_keys: DuplicateKeys,
+}
+
+/// Lê uma lista parte por parte, desserializando cada elemento assim que ele termina de ser
+/// recebido, sem manter o corpo inteiro em memória. A resposta deve ser obtida com
+/// `get_uncached`, pois uma resposta do cache já foi lida por inteiro.
+///
+/// Assim como em `read_list`, o modo `ParseMode::Lenient` de `Config::parse_mode` descarta os
+/// elementos inválidos e o modo `ParseMode::CaptureExtras` registra os campos não modelados.
+/// Os demais erros encerram a stream após serem retornados.
+pub(crate) fn stream_list<T: DeserializeOwned + Serialize>(
+    response: Response,
+) -> impl Stream<Item = Result<T, Error>> + Send {
+    let state = ListStream {
+        response: Some(response),
+        splitter: parse::ArraySplitter::new(),
+        pending: VecDeque::new(),
+        index: 0,
+        mode: config().parse_mode,
+        duplicate_keys: config().duplicate_keys,
+    };
+
+    futures::stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                state.index += 1;
+
+                if let Err(error) = parse::check_duplicate_keys(&item, state.duplicate_keys) {
+                    return Some((Err(error), None));
+                }
+
+                match (serde_json::from_slice::<T>(&item), state.mode) {
+                    (Ok(parsed), ParseMode::CaptureExtras) => {
+                        #[cfg(feature = "tracing")]
+                        log_stream_extras(&item, &parsed, state.index - 1);
+
+                        return Some((Ok(parsed), Some(state)));
+                    }
+                    (Ok(item), _) => return Some((Ok(item), Some(state))),
+                    (Err(error), ParseMode::Strict | ParseMode::CaptureExtras) => {
+                        return Some((Err(Error::from_json_error(error)), None))
+                    }
+                    (Err(_error), ParseMode::Lenient) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            index = state.index - 1,
+                            error = %_error,
+                            "invalid list item skipped"
+                        );
+                    }
+                }
+
+                continue;
+            }
+
+            let response = state.response.as_mut()?;
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => match state.splitter.push(&chunk) {
+                    Ok(items) => state.pending.extend(items),
+                    Err(error) => return Some((Err(error), None)),
+                },
+                Ok(None) => {
+                    state.response = None;
+
+                    if let Err(error) = state.splitter.finish() {
+                        return Some((Err(error), None));
+                    }
+                }
+                Err(error) => return Some((Err(Error::from_error(error)), None)),
+            }
+        }
+    })
+}
+
+/// Registra, em eventos de nível `warn`, os campos de um elemento de `stream_list` que o modelo
+/// não possui.
+#[cfg(feature = "tracing")]
+fn log_stream_extras<T: Serialize>(raw: &[u8], item: &T, index: usize) {
+    let mut extras = Vec::new();
+
+    if let Ok(raw) = serde_json::from_slice(raw) {
+        let _ = parse::push_extras(&raw, item, &mut extras);
+    }
+
+    for field in extras {
+        tracing::warn!(index, field = %field, "unmodeled response field");
+    }
+}
+
+/// Igual a `read_json`, retornando também os cabeçalhos da resposta.
+pub(crate) async fn read_json_with_headers<T: DeserializeOwned>(
+    response: Response,
+) -> Result<(T, HeaderMap), Error> {
+    let headers = response.headers().clone();
+
+    Ok((read_json(response).await?, headers))
+}
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+    use httpmock::MockServer;
+    use reqwest::header::HeaderValue;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn parse_json_test() {
+        let states: Vec<String> = parse_json(r#"["SP", "São Paulo"]"#.as_bytes()).unwrap();
+
+        assert_eq!(states, vec!["SP", "São Paulo"]);
+
+        let error = parse_json::<Vec<String>>(
+            r#"{"message": "CEP não encontrado", "type": "service_error"}"#.as_bytes(),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.message, "CEP não encontrado");
+        assert_eq!(
+            parse_json::<Vec<String>>(b" \n").unwrap_err().error,
+            Errored::EmptyResponse
+        );
+    }
+
+    #[tokio::test]
+    async fn read_list_test() {
+        let response = |body: &str| Response::from(http::Response::new(body.to_string()));
+
+        let items: Vec<u8> = read_list(response("[1, 2]")).await.unwrap();
+        assert_eq!(items, vec![1, 2]);
+
+        // O modo padrão, `ParseMode::Strict`, rejeita a lista com um elemento inválido.
+        assert!(read_list::<u8>(response(r#"[1, "dois"]"#)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn read_body_utf8_test() {
+        let response = |body: Vec<u8>| Response::from(http::Response::new(body));
+
+        assert_eq!(
+            read_body(response("São Paulo".as_bytes().to_vec()))
+                .await
+                .unwrap(),
+            "São Paulo"
+        );
+
+        // "São" em ISO-8859-1.
+        let error = read_body(response(vec![b'S', 0xE3, b'o']))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+        assert!(error.message.starts_with(INVALID_UTF8_MESSAGE));
+        assert!(error.message.ends_with("position 1"));
+
+        let error = read_body_bytes(response(vec![b'S', 0xE3, b'o']))
+            .await
+            .unwrap_err();
+
+        assert!(error.message.ends_with("position 1"));
+        assert_eq!(
+            read_body_bytes(response("São Paulo".as_bytes().to_vec()))
+                .await
+                .unwrap(),
+            "São Paulo".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn request_hook_test() {
+        on_request(|request| {
+            if request.url().path() == "/hooks/request" {
+                request
+                    .headers_mut()
+                    .insert("x-hook", HeaderValue::from_static("applied"));
+            }
+        });
+
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/hooks/request")
+                    .header("x-hook", "applied");
+                then.status(200);
+            })
+            .await;
+
+        get(&server.url("/hooks/request")).await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn response_hook_test() {
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = seen.clone();
+
+        on_response(move |response| {
+            if response.url().path() == "/hooks/response" {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/hooks/response");
+                then.status(404);
+            })
+            .await;
+
+        let error = get(&server.url("/hooks/response")).await.unwrap_err();
+
+        assert_eq!(error.code, Some(404));
+        assert_eq!(seen.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn read_json_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/body/invalid");
+                then.status(200).body("<html>");
+            })
+            .await;
+
+        let response = get(&server.url("/body/invalid")).await.unwrap();
+        let error = read_json::<Vec<String>>(response).await.unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[tokio::test]
+    async fn redirect_limit_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/redirect/loop");
+                then.status(302).header("location", "/redirect/loop");
+            })
+            .await;
+
+        let client = Config {
+            max_redirects: 2,
+            ..Config::default()
+        }
+        .build_client()
+        .unwrap();
+
+        let error = client
+            .get(server.url("/redirect/loop"))
+            .send()
+            .await
+            .map_err(Error::from_error)
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::TooManyRedirects);
+    }
+
+    #[tokio::test]
+    async fn read_json_with_headers_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/body/headers");
+                then.status(200)
+                    .header("etag", "\"abc\"")
+                    .header("x-ratelimit-remaining", "59")
+                    .body("[1, 2]");
+            })
+            .await;
+
+        let response = get(&server.url("/body/headers")).await.unwrap();
+        let (items, headers) = read_json_with_headers::<Vec<u8>>(response).await.unwrap();
+
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(headers["etag"], "\"abc\"");
+        assert_eq!(headers["x-ratelimit-remaining"], "59");
+    }
+
+    #[tokio::test]
+    async fn with_timeout_test() {
+        let error = with_timeout(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.error, Errored::Timeout);
+
+        let value = with_timeout(Duration::from_secs(5), async { Ok(42) })
+            .await
+            .unwrap();
+
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn base_path_test() {
+        let config = Config {
+            base_url: "https://gw.company.com/".to_string(),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.clone().with_base_path("/brasilapi").api_root(),
+            "https://gw.company.com/brasilapi"
+        );
+        assert_eq!(
+            config.with_base_path("brasilapi/").api_root(),
+            "https://gw.company.com/brasilapi"
+        );
+    }
+
+    #[test]
+    fn client_info_test() {
+        let info = ClientInfo::from_config(&Config {
+            base_url: "https://mirror.example.com/".to_string(),
+            user_agent: Some("brasilapi-test".to_string()),
+            ..Config::default().with_base_path("/brasilapi")
+        });
+
+        assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.base_url, "https://mirror.example.com/brasilapi");
+        assert_eq!(info.user_agent.as_deref(), Some("brasilapi-test"));
+    }
+
+    #[test]
+    fn default_config_test() {
+        let config = Config::default();
+
+        assert_eq!(config.base_url, BRASIL_API_URL);
+        assert_eq!(config.api_root(), BRASIL_API_URL);
+        assert_eq!(config.timeout, None);
+        assert_eq!(config.connect_timeout, None);
+        assert_eq!(config.user_agent, None);
+        assert!(!config.http2_prior_knowledge);
+        assert_eq!(config.max_redirects, 5);
+        assert!(!config.log_bodies);
+        assert!(config.redacted_fields.is_empty());
+        assert!(config.preferred_versions.is_empty());
+        assert_eq!(config.parse_mode, ParseMode::Strict);
+        assert_eq!(
+            config
+                .prefer_version("cep", 1)
+                .preferred_versions
+                .get("cep"),
+            Some(&1)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_with_fallback_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/fallback/v2");
+                then.status(404);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/fallback/v1");
+                then.status(200).body("v1");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/fallback/error");
+                then.status(500);
+            })
+            .await;
+
+        let not_found = |error: &Error| error.error == Errored::NotFound;
+        let response = get_with_fallback(
+            &[server.url("/fallback/v2"), server.url("/fallback/v1")],
+            not_found,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.text().await.unwrap(), "v1");
+
+        let error = get_with_fallback(
+            &[server.url("/fallback/error"), server.url("/fallback/v1")],
+            not_found,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code, Some(500));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn redact_test() {
+        let fields = vec!["cnpj_cpf_do_socio".to_string(), "nome_socio".to_string()];
+        let body = r#"{"cnpj": "00000000000191", "qsa": [{"nome_socio": "FULANO", "cnpj_cpf_do_socio": "***123456**", "codigo_qualificacao_socio": 10}]}"#;
+
+        let redacted: serde_json::Value =
+            serde_json::from_str(&redact(body, &fields).unwrap()).unwrap();
+
+        assert_eq!(redacted["cnpj"], "00000000000191");
+        assert_eq!(redacted["qsa"][0]["nome_socio"], "[REDACTED]");
+        assert_eq!(redacted["qsa"][0]["cnpj_cpf_do_socio"], "[REDACTED]");
+        assert_eq!(redacted["qsa"][0]["codigo_qualificacao_socio"], 10);
+
+        assert_eq!(redact("not json", &fields), None);
+        assert_eq!(redact("not json", &[]), Some("not json".to_string()));
+    }
+
+    #[tokio::test]
+    async fn config_client_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/config/user-agent")
+                    .header("user-agent", "brasilapi-test");
+                then.status(200);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/config/timeout");
+                then.status(200).delay(Duration::from_millis(500));
+            })
+            .await;
+
+        let client = Config {
+            base_url: server.base_url(),
+            timeout: Some(Duration::from_millis(50)),
+            connect_timeout: Some(Duration::from_millis(25)),
+            user_agent: Some("brasilapi-test".to_string()),
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            pool_idle_timeout: Some(Duration::from_secs(5)),
+            http2_keep_alive_interval: Some(Duration::from_secs(10)),
+            http2_keep_alive_timeout: Some(Duration::from_secs(5)),
+            ..Config::default()
+        }
+        .build_client()
+        .unwrap();
+
+        let response = client
+            .get(server.url("/config/user-agent"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let error = client
+            .get(server.url("/config/timeout"))
+            .send()
+            .await
+            .map_err(Error::from_error)
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::Timeout);
+    }
+
+    #[tokio::test]
+    async fn probe_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path(HEALTH_PROBE_PATH);
+                then.status(200).body("{}");
+            })
+            .await;
+
+        let client = reqwest::Client::new();
+
+        assert!(probe(&client, &server.base_url()).await.is_ok());
+
+        let error = probe(&client, &format!("{}/missing", server.base_url()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::NotFound);
+    }
+
+    #[tokio::test]
+    async fn correlation_id_test() {
+        let request = || {
+            reqwest::Client::new()
+                .get("http://localhost/")
+                .build()
+                .unwrap()
+        };
+
+        let mut scoped = request();
+        let id = with_correlation_id("pedido-1234", async {
+            insert_correlation_id(&mut scoped, "X-Correlation-ID")
+        })
+        .await;
+
+        assert_eq!(id, "pedido-1234");
+        assert_eq!(scoped.headers()["x-correlation-id"], "pedido-1234");
+
+        let mut first = request();
+        let mut second = request();
+        insert_correlation_id(&mut first, "X-Correlation-ID");
+        insert_correlation_id(&mut second, "X-Correlation-ID");
+
+        assert_ne!(
+            first.headers()["x-correlation-id"],
+            second.headers()["x-correlation-id"]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_bytes_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/latin1");
+                then.status(200).body(vec![0x53, 0xe3, 0x6f]);
+            })
+            .await;
+
+        let body = get_bytes_from(&server.base_url(), "/api/latin1")
+            .await
+            .unwrap();
+
+        assert_eq!(body, vec![0x53, 0xe3, 0x6f]);
+    }
+
+    #[tokio::test]
+    async fn get_bytes_with_progress_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/pix/v1/participants");
+                then.status(200).body(vec![b'x'; 64 * 1024]);
+            })
+            .await;
+
+        let progress = std::sync::Mutex::new(Vec::new());
+        let body = get_bytes_with_progress_from(
+            &server.base_url(),
+            "/api/pix/v1/participants",
+            |received| progress.lock().unwrap().push(received),
+        )
+        .await
+        .unwrap();
+        let progress = progress.into_inner().unwrap();
+
+        assert_eq!(body.len(), 64 * 1024);
+        assert_eq!(progress.last(), Some(&(64 * 1024)));
+        assert!(progress.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn endpoint_label_test() {
+        assert_eq!(
+            endpoint_label("https://brasilapi.com.br/api/ibge/municipios/v1/SP"),
+            "ibge"
+        );
+        assert_eq!(
+            endpoint_label("https://example.com/brasilapi/api/cep/v1/01001000"),
+            "cep"
+        );
+        assert_eq!(endpoint_label("https://example.com/other"), "unknown");
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn status_class_test() {
+        let not_found = Err(Error::new(String::new(), Errored::NotFound, Some(404)));
+        let timeout = Err(Error::new(String::new(), Errored::Timeout, None));
+
+        assert_eq!(status_class(&not_found), "4xx");
+        assert_eq!(status_class(&timeout), "error");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{error::*, spec::BRASIL_API_URL};
+use crate::{client, error::*, normalize, validate::normalize_cnpj};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,7 +40,14 @@ pub struct Cnpj {
     situacao_especial: Option<String>,
     data_situacao_especial: Option<String>,
     cnaes_secundarias: Option<Vec<Cnaes>>,
-    qsa: Option<Vec<Qsa>>,
+    qsa: Option<Vec<Partner>>,
+}
+
+impl Cnpj {
+    /// Sócios do quadro societário (QSA), vazio quando a API não os informa.
+    pub fn get_partners(&self) -> &[Partner] {
+        self.qsa.as_deref().unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -49,12 +56,96 @@ pub struct Cnaes {
     descricao: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Qsa {
+/// Qualificação de um sócio no quadro societário (QSA) da empresa.
+///
+/// As qualificações mais comuns possuem uma variante própria; as demais são mantidas em
+/// `PartnerRole::Other` com o texto retornado pela API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum PartnerRole {
+    /// Administrador
+    Administrator,
+    /// Conselheiro de Administração
+    BoardMember,
+    /// Diretor
+    Director,
+    /// Presidente
+    President,
+    /// Procurador
+    Attorney,
+    /// Sócio
+    Partner,
+    /// Sócio-Administrador
+    ManagingPartner,
+    /// Sócio Pessoa Jurídica Domiciliado no Exterior
+    ForeignCorporatePartner,
+    /// Titular Pessoa Física Residente ou Domiciliado no Brasil
+    Owner,
+    /// Qualquer outra qualificação, com o texto retornado pela API.
+    Other(String),
+}
+
+impl PartnerRole {
+    /// Descrição da qualificação, como retornada pela API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            PartnerRole::Administrator => "Administrador",
+            PartnerRole::BoardMember => "Conselheiro de Administração",
+            PartnerRole::Director => "Diretor",
+            PartnerRole::President => "Presidente",
+            PartnerRole::Attorney => "Procurador",
+            PartnerRole::Partner => "Sócio",
+            PartnerRole::ManagingPartner => "Sócio-Administrador",
+            PartnerRole::ForeignCorporatePartner => "Sócio Pessoa Jurídica Domiciliado no Exterior",
+            PartnerRole::Owner => "Titular Pessoa Física Residente ou Domiciliado no Brasil",
+            PartnerRole::Other(role) => role,
+        }
+    }
+}
+
+impl From<String> for PartnerRole {
+    /// A comparação ignora acentos e a caixa das letras.
+    fn from(role: String) -> Self {
+        let folded = normalize::fold_accents(role.trim()).to_lowercase();
+
+        match folded.as_str() {
+            "administrador" => PartnerRole::Administrator,
+            "conselheiro de administracao" => PartnerRole::BoardMember,
+            "diretor" => PartnerRole::Director,
+            "presidente" => PartnerRole::President,
+            "procurador" => PartnerRole::Attorney,
+            "socio" => PartnerRole::Partner,
+            "socio-administrador" => PartnerRole::ManagingPartner,
+            "socio pessoa juridica domiciliado no exterior" => PartnerRole::ForeignCorporatePartner,
+            "titular pessoa fisica residente ou domiciliado no brasil" => PartnerRole::Owner,
+            _ => PartnerRole::Other(role),
+        }
+    }
+}
+
+impl From<PartnerRole> for String {
+    fn from(role: PartnerRole) -> Self {
+        match role {
+            PartnerRole::Other(role) => role,
+            role => role.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for PartnerRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sócio do quadro societário (QSA) da empresa.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Partner {
     identificador_de_socio: Option<i32>,
     nome_socio: Option<String>,
     cnpj_cpf_do_socio: Option<String>,
     codigo_qualificacao_socio: Option<i32>,
+    qualificacao_socio: Option<PartnerRole>,
     percentual_capital_social: Option<i32>,
     data_entrada_sociedade: Option<String>,
     cpf_representante_legal: Option<String>,
@@ -62,6 +153,45 @@ pub struct Qsa {
     codigo_qualificacao_representante_legal: Option<i32>,
 }
 
+/// Nome anterior de [`Partner`].
+pub type Qsa = Partner;
+
+impl Partner {
+    pub fn get_name(&self) -> Option<&str> {
+        self.nome_socio.as_deref()
+    }
+
+    /// CNPJ ou CPF do sócio. A API mascara os dígitos iniciais e finais dos CPFs.
+    pub fn get_document(&self) -> Option<&str> {
+        self.cnpj_cpf_do_socio.as_deref()
+    }
+
+    pub fn get_role(&self) -> Option<&PartnerRole> {
+        self.qualificacao_socio.as_ref()
+    }
+
+    pub fn get_role_code(&self) -> Option<i32> {
+        self.codigo_qualificacao_socio
+    }
+
+    pub fn get_capital_share(&self) -> Option<i32> {
+        self.percentual_capital_social
+    }
+
+    pub fn get_joined_at(&self) -> Option<&str> {
+        self.data_entrada_sociedade.as_deref()
+    }
+
+    pub fn get_legal_representative_name(&self) -> Option<&str> {
+        self.nome_representante_legal.as_deref()
+    }
+
+    pub fn get_legal_representative_cpf(&self) -> Option<&str> {
+        self.cpf_representante_legal.as_deref()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct CnpjService {
     base_url: String,
 }
@@ -69,17 +199,15 @@ pub struct CnpjService {
 impl CnpjService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_cnpj_request(&self, cnpj_code: &str) -> Result<reqwest::Response, Error> {
+        let cnpj_code = normalize_cnpj(cnpj_code)?;
         let url = format!("{}/api/cnpj/v1/{}", self.base_url, cnpj_code);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 }
 
@@ -92,6 +220,9 @@ impl CnpjService {
 /// ### Retorno
 /// * `Result<Cnpj, Error>`
 ///
+/// Um CNPJ que não possua 14 dígitos (a pontuação é ignorada) retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_CNPJ_MESSAGE`, sem que a API seja consultada.
+///
 /// # Exemplo
 /// ```rust
 /// use brasilapi::cnpj;
@@ -102,12 +233,11 @@ impl CnpjService {
 /// }
 /// ```
 pub async fn get_cnpj(cnpj: &str) -> Result<Cnpj, Error> {
-    let cnpj_service = CnpjService::new(BRASIL_API_URL);
+    let cnpj_service = CnpjService::new(client::base_url());
 
     let response = cnpj_service.get_cnpj_request(cnpj).await?;
 
-    let body = response.text().await.unwrap();
-    let cnpj: Cnpj = serde_json::from_str(&body).unwrap();
+    let cnpj: Cnpj = client::read_json(response).await?;
 
     Ok(cnpj)
 }
@@ -122,4 +252,39 @@ mod cnpj_tests {
 
         assert_eq!(cnpj.cnpj, Some("00000000000191".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_get_cnpj_malformed() {
+        let error = get_cnpj("00.000.000/0001").await.unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(error.message, "invalid cnpj: expected 14 digits");
+    }
+
+    #[test]
+    fn test_partner_role() {
+        let partner: Partner = serde_json::from_str(
+            r#"{"nome_socio": "FULANO", "qualificacao_socio": "Socio-Administrador"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(partner.get_name(), Some("FULANO"));
+        assert_eq!(partner.get_role(), Some(&PartnerRole::ManagingPartner));
+        assert_eq!(
+            PartnerRole::from("Liquidante".to_string()),
+            PartnerRole::Other("Liquidante".to_string())
+        );
+        assert_eq!(
+            serde_json::to_string(&PartnerRole::ManagingPartner).unwrap(),
+            r#""Sócio-Administrador""#
+        );
+    }
+
+    #[test]
+    fn test_normalize_cnpj() {
+        assert_eq!(
+            normalize_cnpj("00.000.000/0001-91").unwrap(),
+            "00000000000191"
+        );
+    }
 }
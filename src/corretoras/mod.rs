@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Error, spec::BRASIL_API_URL};
+use crate::{client, endpoint::ListEndpoint, error::Error};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Corretora {
@@ -23,6 +23,7 @@ pub struct Corretora {
     valor_patrimonio_liquido: String,
 }
 
+#[derive(Debug, Clone)]
 pub struct CorretorasService {
     url: String,
 }
@@ -30,26 +31,30 @@ pub struct CorretorasService {
 impl CorretorasService {
     pub fn new(url: &str) -> Self {
         Self {
-            url: url.to_string(),
+            url: client::trim_base_url(url).to_string(),
         }
     }
 
     async fn get_corretoras_request(&self) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/cvm/corretoras/v1", self.url);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 
     async fn get_corretora_by_cnpj(&self, cnpj: &str) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/cvm/corretoras/v1/{}", self.url, cnpj);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
+    }
+}
+
+impl ListEndpoint<Corretora> for CorretorasService {
+    async fn fetch_all(&self) -> Result<Vec<Corretora>, Error> {
+        let response = self.get_corretoras_request().await?;
+
+        let corretoras: Vec<Corretora> = client::read_list(response).await?;
+
+        Ok(corretoras)
     }
 }
 
@@ -70,14 +75,9 @@ impl CorretorasService {
 ///
 /// ```
 pub async fn get_corretoras() -> Result<Vec<Corretora>, Error> {
-    let corretoras_service = CorretorasService::new(BRASIL_API_URL);
-
-    let response = corretoras_service.get_corretoras_request().await?;
-
-    let body = response.text().await.unwrap();
-    let corretoras: Vec<Corretora> = serde_json::from_str(&body).unwrap();
+    let corretoras_service = CorretorasService::new(client::base_url());
 
-    Ok(corretoras)
+    corretoras_service.fetch_all().await
 }
 
 /// #### `get_corretora(cnpj: &str)`
@@ -100,12 +100,11 @@ pub async fn get_corretoras() -> Result<Vec<Corretora>, Error> {
 ///
 /// ```
 pub async fn get_corretora(cnpj: &str) -> Result<Corretora, Error> {
-    let corretoras_service = CorretorasService::new(BRASIL_API_URL);
+    let corretoras_service = CorretorasService::new(client::base_url());
 
     let response = corretoras_service.get_corretora_by_cnpj(cnpj).await?;
 
-    let body = response.text().await.unwrap();
-    let corretora: Corretora = serde_json::from_str(&body).unwrap();
+    let corretora: Corretora = client::read_json(response).await?;
 
     Ok(corretora)
 }
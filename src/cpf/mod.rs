@@ -0,0 +1,121 @@
+use crate::error::*;
+
+/// Remove a pontuação do CPF e verifica se restam exatamente 11 dígitos.
+fn normalize_cpf(cpf: &str) -> Result<Vec<u32>, Error> {
+    let digits: String = cpf
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | ' '))
+        .collect();
+
+    if digits.len() != 11 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::invalid_input(INVALID_CPF_MESSAGE));
+    }
+
+    Ok(digits.chars().filter_map(|c| c.to_digit(10)).collect())
+}
+
+/// Calcula o dígito verificador (módulo 11) a partir dos dígitos anteriores.
+fn check_digit(digits: &[u32]) -> u32 {
+    let weight = digits.len() as u32 + 1;
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(position, digit)| digit * (weight - position as u32))
+        .sum();
+
+    match sum % 11 {
+        0 | 1 => 0,
+        rest => 11 - rest,
+    }
+}
+
+/// #### `validate(cpf: &str)`
+/// Verifica offline se o CPF é válido, conferindo os dois dígitos verificadores.
+///
+/// A pontuação é ignorada. CPFs com todos os dígitos iguais, como `111.111.111-11`, passam no
+/// cálculo dos dígitos verificadores mas são considerados inválidos.
+///
+/// ### Argumento
+/// * `cpf:&str` => CPF, com ou sem pontuação.
+///
+/// ### Retorno
+/// * `bool`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cpf;
+///
+/// assert!(cpf::validate("529.982.247-25"));
+/// assert!(!cpf::validate("111.111.111-11"));
+/// ```
+pub fn validate(cpf: &str) -> bool {
+    let digits = match normalize_cpf(cpf) {
+        Ok(digits) => digits,
+        Err(_) => return false,
+    };
+
+    if digits.iter().all(|digit| *digit == digits[0]) {
+        return false;
+    }
+
+    check_digit(&digits[..9]) == digits[9] && check_digit(&digits[..10]) == digits[10]
+}
+
+/// #### `format(cpf: &str)`
+/// Formata o CPF no padrão `000.000.000-00`.
+///
+/// Os dígitos verificadores não são conferidos; use `validate` para isso.
+///
+/// ### Argumento
+/// * `cpf:&str` => CPF, com ou sem pontuação.
+///
+/// ### Retorno
+/// * `Result<String, Error>`
+///
+/// Um CPF que não possua 11 dígitos (a pontuação é ignorada) retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_CPF_MESSAGE`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cpf;
+///
+/// assert_eq!(cpf::format("52998224725").unwrap(), "529.982.247-25");
+/// ```
+pub fn format(cpf: &str) -> Result<String, Error> {
+    let digits: String = normalize_cpf(cpf)?
+        .into_iter()
+        .filter_map(|digit| char::from_digit(digit, 10))
+        .collect();
+
+    Ok(format!(
+        "{}.{}.{}-{}",
+        &digits[..3],
+        &digits[3..6],
+        &digits[6..9],
+        &digits[9..]
+    ))
+}
+
+#[cfg(test)]
+mod cpf_tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("52998224725"));
+        assert!(validate("529.982.247-25"));
+        assert!(!validate("529.982.247-24"));
+        assert!(!validate("5299822472"));
+        assert!(!validate("5299822472a"));
+
+        for digit in 0..=9 {
+            assert!(!validate(&digit.to_string().repeat(11)));
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format("529 982 247 25").unwrap(), "529.982.247-25");
+        assert_eq!(format("123").unwrap_err().message, INVALID_CPF_MESSAGE);
+    }
+}
@@ -0,0 +1,234 @@
+//! Tipos das previsões do tempo do CPTEC/INPE.
+//!
+//! As consultas às previsões ainda não estão disponíveis; este módulo define os tipos
+//! compartilhados por elas, começando pela condição do tempo.
+use serde::{Deserialize, Serialize};
+
+/// Condição do tempo de uma previsão, informada pelo CPTEC como um código curto (campo
+/// `condicao`), por exemplo `pn` para parcialmente nublado.
+///
+/// Os códigos desconhecidos são mantidos em `WeatherCondition::Other`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::cptec::WeatherCondition;
+///
+/// let condition = WeatherCondition::from("pn");
+///
+/// assert_eq!(condition, WeatherCondition::PartlyCloudy);
+/// assert_eq!(condition.description(), "Parcialmente Nublado");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum WeatherCondition {
+    /// `cl` - Céu Claro
+    ClearSky,
+    /// `ps` - Predomínio de Sol
+    MostlySunny,
+    /// `pn` - Parcialmente Nublado
+    PartlyCloudy,
+    /// `n` - Nublado
+    Cloudy,
+    /// `e` - Encoberto
+    Overcast,
+    /// `vn` - Variação de Nebulosidade
+    VariableCloudiness,
+    /// `cv` - Chuvisco
+    Drizzle,
+    /// `c` - Chuva
+    Rain,
+    /// `ch` - Chuvoso
+    Rainy,
+    /// `ci` - Chuvas Isoladas
+    IsolatedRain,
+    /// `ec` - Encoberto com Chuvas Isoladas
+    OvercastWithIsolatedRain,
+    /// `cm` - Chuva pela Manhã
+    MorningRain,
+    /// `ct` - Chuva à Tarde
+    AfternoonRain,
+    /// `cn` - Chuva à Noite
+    NightRain,
+    /// `psc` - Possibilidade de Chuva
+    ChanceOfRain,
+    /// `pc` - Pancadas de Chuva
+    Showers,
+    /// `pp` - Possibilidade de Pancadas de Chuva
+    ChanceOfShowers,
+    /// `pm` - Pancadas de Chuva pela Manhã
+    MorningShowers,
+    /// `pt` - Pancadas de Chuva à Tarde
+    AfternoonShowers,
+    /// `pnt` - Pancadas de Chuva à Noite
+    NightShowers,
+    /// `np` - Nublado e Pancadas de Chuva
+    CloudyWithShowers,
+    /// `in` - Instável
+    Unstable,
+    /// `t` - Tempestade
+    Storm,
+    /// `nv` - Nevoeiro
+    Fog,
+    /// `g` - Geada
+    Frost,
+    /// `ne` - Neve
+    Snow,
+    /// `nd` - Não Definido
+    Undefined,
+    /// Qualquer outro código, como retornado pela API.
+    Other(String),
+}
+
+impl WeatherCondition {
+    /// Código da condição, como retornado pela API.
+    pub fn code(&self) -> &str {
+        match self {
+            WeatherCondition::ClearSky => "cl",
+            WeatherCondition::MostlySunny => "ps",
+            WeatherCondition::PartlyCloudy => "pn",
+            WeatherCondition::Cloudy => "n",
+            WeatherCondition::Overcast => "e",
+            WeatherCondition::VariableCloudiness => "vn",
+            WeatherCondition::Drizzle => "cv",
+            WeatherCondition::Rain => "c",
+            WeatherCondition::Rainy => "ch",
+            WeatherCondition::IsolatedRain => "ci",
+            WeatherCondition::OvercastWithIsolatedRain => "ec",
+            WeatherCondition::MorningRain => "cm",
+            WeatherCondition::AfternoonRain => "ct",
+            WeatherCondition::NightRain => "cn",
+            WeatherCondition::ChanceOfRain => "psc",
+            WeatherCondition::Showers => "pc",
+            WeatherCondition::ChanceOfShowers => "pp",
+            WeatherCondition::MorningShowers => "pm",
+            WeatherCondition::AfternoonShowers => "pt",
+            WeatherCondition::NightShowers => "pnt",
+            WeatherCondition::CloudyWithShowers => "np",
+            WeatherCondition::Unstable => "in",
+            WeatherCondition::Storm => "t",
+            WeatherCondition::Fog => "nv",
+            WeatherCondition::Frost => "g",
+            WeatherCondition::Snow => "ne",
+            WeatherCondition::Undefined => "nd",
+            WeatherCondition::Other(code) => code,
+        }
+    }
+
+    /// Descrição da condição em português. Para códigos desconhecidos, retorna o próprio código.
+    pub fn description(&self) -> &str {
+        match self {
+            WeatherCondition::ClearSky => "Céu Claro",
+            WeatherCondition::MostlySunny => "Predomínio de Sol",
+            WeatherCondition::PartlyCloudy => "Parcialmente Nublado",
+            WeatherCondition::Cloudy => "Nublado",
+            WeatherCondition::Overcast => "Encoberto",
+            WeatherCondition::VariableCloudiness => "Variação de Nebulosidade",
+            WeatherCondition::Drizzle => "Chuvisco",
+            WeatherCondition::Rain => "Chuva",
+            WeatherCondition::Rainy => "Chuvoso",
+            WeatherCondition::IsolatedRain => "Chuvas Isoladas",
+            WeatherCondition::OvercastWithIsolatedRain => "Encoberto com Chuvas Isoladas",
+            WeatherCondition::MorningRain => "Chuva pela Manhã",
+            WeatherCondition::AfternoonRain => "Chuva à Tarde",
+            WeatherCondition::NightRain => "Chuva à Noite",
+            WeatherCondition::ChanceOfRain => "Possibilidade de Chuva",
+            WeatherCondition::Showers => "Pancadas de Chuva",
+            WeatherCondition::ChanceOfShowers => "Possibilidade de Pancadas de Chuva",
+            WeatherCondition::MorningShowers => "Pancadas de Chuva pela Manhã",
+            WeatherCondition::AfternoonShowers => "Pancadas de Chuva à Tarde",
+            WeatherCondition::NightShowers => "Pancadas de Chuva à Noite",
+            WeatherCondition::CloudyWithShowers => "Nublado e Pancadas de Chuva",
+            WeatherCondition::Unstable => "Instável",
+            WeatherCondition::Storm => "Tempestade",
+            WeatherCondition::Fog => "Nevoeiro",
+            WeatherCondition::Frost => "Geada",
+            WeatherCondition::Snow => "Neve",
+            WeatherCondition::Undefined => "Não Definido",
+            WeatherCondition::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for WeatherCondition {
+    /// A comparação ignora a caixa das letras e espaços nas extremidades.
+    fn from(code: &str) -> Self {
+        match code.trim().to_lowercase().as_str() {
+            "cl" => WeatherCondition::ClearSky,
+            "ps" => WeatherCondition::MostlySunny,
+            "pn" => WeatherCondition::PartlyCloudy,
+            "n" => WeatherCondition::Cloudy,
+            "e" => WeatherCondition::Overcast,
+            "vn" => WeatherCondition::VariableCloudiness,
+            "cv" => WeatherCondition::Drizzle,
+            "c" => WeatherCondition::Rain,
+            "ch" => WeatherCondition::Rainy,
+            "ci" => WeatherCondition::IsolatedRain,
+            "ec" => WeatherCondition::OvercastWithIsolatedRain,
+            "cm" => WeatherCondition::MorningRain,
+            "ct" => WeatherCondition::AfternoonRain,
+            "cn" => WeatherCondition::NightRain,
+            "psc" => WeatherCondition::ChanceOfRain,
+            "pc" => WeatherCondition::Showers,
+            "pp" => WeatherCondition::ChanceOfShowers,
+            "pm" => WeatherCondition::MorningShowers,
+            "pt" => WeatherCondition::AfternoonShowers,
+            "pnt" => WeatherCondition::NightShowers,
+            "np" => WeatherCondition::CloudyWithShowers,
+            "in" => WeatherCondition::Unstable,
+            "t" => WeatherCondition::Storm,
+            "nv" => WeatherCondition::Fog,
+            "g" => WeatherCondition::Frost,
+            "ne" => WeatherCondition::Snow,
+            "nd" => WeatherCondition::Undefined,
+            _ => WeatherCondition::Other(code.to_string()),
+        }
+    }
+}
+
+impl From<String> for WeatherCondition {
+    fn from(code: String) -> Self {
+        WeatherCondition::from(code.as_str())
+    }
+}
+
+impl From<WeatherCondition> for String {
+    fn from(condition: WeatherCondition) -> Self {
+        match condition {
+            WeatherCondition::Other(code) => code,
+            condition => condition.code().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for WeatherCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+#[cfg(test)]
+mod cptec_tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_condition() {
+        assert_eq!(WeatherCondition::from("NV"), WeatherCondition::Fog);
+        assert_eq!(WeatherCondition::from("c").description(), "Chuva");
+        assert_eq!(
+            WeatherCondition::from("xyz"),
+            WeatherCondition::Other("xyz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_weather_condition_serde() {
+        let condition: WeatherCondition = serde_json::from_str(r#""ps""#).unwrap();
+
+        assert_eq!(condition, WeatherCondition::MostlySunny);
+        assert_eq!(serde_json::to_string(&condition).unwrap(), r#""ps""#);
+        assert_eq!(
+            serde_json::to_string(&WeatherCondition::Other("xyz".to_string())).unwrap(),
+            r#""xyz""#
+        );
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{error::*, spec::BRASIL_API_URL};
+use std::collections::HashMap;
+
+use crate::{
+    client,
+    error::*,
+    ibge::{self, IbgeService, MunicipalitiesProvider, Municipality, State, Uf},
+    normalize,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +23,84 @@ pub struct Regiao {
     nome: String,
 }
 
+/// DDDs de cada unidade federativa, segundo o plano de numeração da Anatel. O DDD 61 atende o
+/// Distrito Federal e também os municípios de Goiás do entorno.
+fn area_codes(uf: Uf) -> &'static [&'static str] {
+    match uf {
+        Uf::Ro => &["69"],
+        Uf::Ac => &["68"],
+        Uf::Am => &["92", "97"],
+        Uf::Rr => &["95"],
+        Uf::Pa => &["91", "93", "94"],
+        Uf::Ap => &["96"],
+        Uf::To => &["63"],
+        Uf::Ma => &["98", "99"],
+        Uf::Pi => &["86", "89"],
+        Uf::Ce => &["85", "88"],
+        Uf::Rn => &["84"],
+        Uf::Pb => &["83"],
+        Uf::Pe => &["81", "87"],
+        Uf::Al => &["82"],
+        Uf::Se => &["79"],
+        Uf::Ba => &["71", "73", "74", "75", "77"],
+        Uf::Mg => &["31", "32", "33", "34", "35", "37", "38"],
+        Uf::Es => &["27", "28"],
+        Uf::Rj => &["21", "22", "24"],
+        Uf::Sp => &["11", "12", "13", "14", "15", "16", "17", "18", "19"],
+        Uf::Pr => &["41", "42", "43", "44", "45", "46"],
+        Uf::Sc => &["47", "48", "49"],
+        Uf::Rs => &["51", "53", "54", "55"],
+        Uf::Ms => &["67"],
+        Uf::Mt => &["65", "66"],
+        Uf::Go => &["61", "62", "64"],
+        Uf::Df => &["61"],
+    }
+}
+
+/// Chave usada para cruzar os nomes das cidades do DDD com os municípios do IBGE: sem acentos,
+/// em minúsculo e com hífens e apóstrofos tratados como espaços, já que `"EMBU-GUAÇU"` e
+/// `"Embu-Guaçu"`, ou `"PAU D'ARCO"` e `"Pau d'Arco"`, variam entre as duas bases.
+fn city_key(name: &str) -> String {
+    normalize::fold_accents(name)
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || matches!(c, '-' | '\'' | '’'))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Associa a cada município os DDDs cujas listas de cidades contêm o seu nome, na ordem de
+/// `ddds`. Um município não encontrado em nenhuma lista recebe uma lista vazia.
+fn join_area_codes(
+    municipalities: Vec<Municipality>,
+    ddds: &[(&str, Ddd)],
+) -> Vec<(Municipality, Vec<String>)> {
+    let mut codes_by_city: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (code, ddd) in ddds {
+        for city in &ddd.cities {
+            let codes = codes_by_city.entry(city_key(city)).or_default();
+
+            if !codes.iter().any(|existing| existing == code) {
+                codes.push(code.to_string());
+            }
+        }
+    }
+
+    municipalities
+        .into_iter()
+        .map(|municipality| {
+            let codes = codes_by_city
+                .get(&city_key(municipality.get_name()))
+                .cloned()
+                .unwrap_or_default();
+
+            (municipality, codes)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
 pub struct DDDService {
     base_url: String,
 }
@@ -23,17 +108,42 @@ pub struct DDDService {
 impl DDDService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_ddd_request(&self, ddd: &str) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/ddd/v1/{}", self.base_url, ddd);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
+    }
+
+    async fn fetch_ddd(&self, ddd: &str) -> Result<Ddd, Error> {
+        let response = self.get_ddd_request(ddd).await?;
+
+        client::read_json(response).await
+    }
+
+    async fn fetch_municipalities_with_ddd(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<Vec<(Municipality, Vec<String>)>, Error> {
+        let uf = ibge::resolve_uf(uf, client::resolves_state_names())?;
+        let ibge_service = IbgeService::new(&self.base_url);
+        let codes = area_codes(uf);
+
+        let (municipalities, ddds) = futures::join!(
+            ibge_service.fetch_municipalities(uf.as_str(), providers),
+            futures::future::try_join_all(codes.iter().map(|code| async move {
+                self.fetch_ddd(code)
+                    .await
+                    .map_err(|error| error.with_context(format!("ddd {code}")))
+            }))
+        );
+        let ddds: Vec<(&str, Ddd)> = codes.iter().copied().zip(ddds?).collect();
+
+        Ok(join_area_codes(municipalities?, &ddds))
     }
 
     async fn validate_ddd(&self, ddd: &str) -> Result<bool, Error> {
@@ -68,12 +178,11 @@ impl DDDService {
 /// }
 /// ```
 pub async fn get_ddd(ddd: &str) -> Result<Ddd, Error> {
-    let ddd_service = DDDService::new(BRASIL_API_URL);
+    let ddd_service = DDDService::new(client::base_url());
 
     let response = ddd_service.get_ddd_request(ddd).await?;
 
-    let body = response.text().await.unwrap();
-    let ddd: Ddd = serde_json::from_str(&body).unwrap();
+    let ddd: Ddd = client::read_json(response).await?;
 
     Ok(ddd)
 }
@@ -97,16 +206,130 @@ pub async fn get_ddd(ddd: &str) -> Result<Ddd, Error> {
 /// }
 /// ```
 pub async fn ddd_exists(ddd: &str) -> Result<bool, Error> {
-    let ddd_service = DDDService::new(BRASIL_API_URL);
+    let ddd_service = DDDService::new(client::base_url());
 
     let response = ddd_service.validate_ddd(ddd).await?;
 
     Ok(response)
 }
 
+/// #### `resolve_state(ddd: &str)`
+/// Busca o estado de um DDD e as suas informações completas no IBGE, incluindo a região.
+///
+/// ### Argumento
+/// * `ddd:&str` => DDD para consulta.
+///
+/// ### Retorno
+/// * `Result<State, Error>`
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::ddd;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let state = ddd::resolve_state("61").await.unwrap();
+///
+///     println!("Região: {}", state.get_region().get_name());
+/// }
+/// ```
+pub async fn resolve_state(ddd: &str) -> Result<State, Error> {
+    let ddd = get_ddd(ddd).await?;
+
+    ibge::get_state(&ddd.state).await
+}
+
+/// #### `get_municipalities_with_ddd(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna os municípios de um estado, cada um com os seus DDDs.
+///
+/// Os DDDs da unidade federativa são consultados junto com os municípios, e cada município
+/// recebe os DDDs em cuja lista de cidades aparece. Os nomes são comparados sem acentos, caixa,
+/// hífens ou apóstrofos, já que as duas bases os escrevem de formas diferentes. Um município
+/// que não apareça em nenhuma lista recebe uma lista vazia.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta dos
+///   municípios.
+///
+/// ### Retorno
+/// * `Result<Vec<(Municipality, Vec<String>)>, Error>`
+///
+/// Uma UF inválida retorna um erro `Errored::InvalidInput` com a mensagem
+/// `INVALID_UF_MESSAGE`, sem que a API seja consultada.
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::ddd;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let municipalities = ddd::get_municipalities_with_ddd("GO", None).await.unwrap();
+///
+///     for (municipality, codes) in municipalities {
+///         println!("{}: {}", municipality.get_name(), codes.join(", "));
+///     }
+/// }
+/// ```
+pub async fn get_municipalities_with_ddd(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<(Municipality, Vec<String>)>, Error> {
+    let ddd_service = DDDService::new(client::base_url());
+
+    ddd_service
+        .fetch_municipalities_with_ddd(uf, providers)
+        .await
+}
+
 #[cfg(test)]
 mod ddd_tests {
     use super::*;
+    use httpmock::MockServer;
+
+    #[test]
+    fn test_city_key() {
+        assert_eq!(city_key("EMBU-GUAÇU"), city_key("Embu-Guaçu"));
+        assert_eq!(city_key("PAU D'ARCO"), city_key("Pau d’Arco"));
+        assert_eq!(city_key(" São  Paulo "), "sao paulo");
+        assert_ne!(city_key("Bonfim"), city_key("Bom Fim do Piauí"));
+    }
+
+    #[tokio::test]
+    async fn test_municipalities_with_ddd() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/DF");
+                then.status(200)
+                    .body(r#"[{"nome": "BRASÍLIA", "codigo_ibge": "5300108"}]"#);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ddd/v1/61");
+                then.status(200)
+                    .body(r#"{"state": "DF", "cities": ["Brasilia", "Águas Lindas de Goiás"]}"#);
+            })
+            .await;
+
+        let ddd_service = DDDService::new(&server.base_url());
+        let municipalities = ddd_service
+            .fetch_municipalities_with_ddd("DF", None)
+            .await
+            .unwrap();
+
+        assert_eq!(municipalities.len(), 1);
+        assert_eq!(municipalities[0].0.get_name(), "BRASÍLIA");
+        assert_eq!(municipalities[0].1, vec!["61".to_string()]);
+
+        let error = ddd_service
+            .fetch_municipalities_with_ddd("XX", None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+    }
 
     #[tokio::test]
     async fn get_ddd_test() {
@@ -0,0 +1,52 @@
+use std::future::Future;
+
+use crate::error::Error;
+
+/// Um endpoint da BrasilAPI que retorna a lista completa de um recurso.
+///
+/// Implementado pelos serviços dos módulos, permitindo escrever código genérico (cache,
+/// exportação, etc.) sobre qualquer endpoint de listagem.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{bank::BankService, endpoint::ListEndpoint, spec::BRASIL_API_URL};
+///
+/// async fn count<T>(endpoint: &impl ListEndpoint<T>) -> usize {
+///     endpoint.fetch_all().await.map(|items| items.len()).unwrap_or(0)
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let banks = count(&BankService::new(BRASIL_API_URL)).await;
+/// }
+/// ```
+pub trait ListEndpoint<T> {
+    /// Busca todos os itens do endpoint.
+    fn fetch_all(&self) -> impl Future<Output = Result<Vec<T>, Error>> + Send;
+}
+
+#[cfg(test)]
+mod endpoint_tests {
+    use crate::{
+        bank::BankService, cep::CepService, cnpj::CnpjService, corretoras::CorretorasService,
+        ddd::DDDService, fipe::FipeService, holidays::HolidayService, ibge::IbgeService,
+        ncm::NcmService, pix::PIXService, registrobr::RegistroBrService,
+    };
+
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+
+    #[test]
+    fn services_are_shareable_test() {
+        assert_shareable::<BankService>();
+        assert_shareable::<CepService>();
+        assert_shareable::<CnpjService>();
+        assert_shareable::<CorretorasService>();
+        assert_shareable::<DDDService>();
+        assert_shareable::<FipeService>();
+        assert_shareable::<HolidayService>();
+        assert_shareable::<IbgeService>();
+        assert_shareable::<NcmService>();
+        assert_shareable::<PIXService>();
+        assert_shareable::<RegistroBrService>();
+    }
+}
@@ -1,6 +1,76 @@
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
+/// Mensagem retornada quando a UF informada não é uma sigla de estado válida.
+pub const INVALID_UF_MESSAGE: &str = "invalid uf: expected a state abbreviation (e.g. SP)";
+
+/// Mensagem retornada por `ibge::get_state` e `ibge::get_states`, que também aceitam o código
+/// IBGE do estado, quando o valor informado não é uma sigla nem um código válido.
+pub const INVALID_STATE_CODE_MESSAGE: &str =
+    "invalid state: expected a state abbreviation (e.g. SP) or IBGE state code (e.g. 35)";
+
+/// Prefixo da mensagem retornada ao restaurar um estado armazenado cujo código IBGE não
+/// corresponde à sigla, seguido pelos dois valores, por exemplo `id 33, uf SP`.
+pub const STATE_CODE_MISMATCH_MESSAGE: &str = "state code does not match the uf";
+
+/// Mensagem retornada quando o CEP informado não possui 8 dígitos.
+pub const INVALID_CEP_MESSAGE: &str = "invalid cep: expected 8 digits";
+
+/// Mensagem retornada quando o CNPJ informado não possui 14 dígitos.
+pub const INVALID_CNPJ_MESSAGE: &str = "invalid cnpj: expected 14 digits";
+
+/// Mensagem retornada quando o código IBGE de município informado não possui 7 dígitos ou não
+/// começa com o código de uma unidade federativa.
+pub const INVALID_MUNICIPALITY_CODE_MESSAGE: &str =
+    "invalid municipality code: expected 7 digits starting with an IBGE state code";
+
+/// Mensagem retornada quando o código NCM informado não possui 8 dígitos.
+pub const INVALID_NCM_MESSAGE: &str = "invalid ncm: expected 8 digits";
+
+/// Mensagem retornada quando o ISBN informado não possui 10 ou 13 dígitos ou o dígito
+/// verificador não confere.
+pub const INVALID_ISBN_MESSAGE: &str =
+    "invalid isbn: expected 10 or 13 digits with a valid check digit";
+
+/// Mensagem retornada quando o CPF informado não possui 11 dígitos.
+pub const INVALID_CPF_MESSAGE: &str = "invalid cpf: expected 11 digits";
+
+/// Prefixo da mensagem retornada quando um provedor de municípios não é aceito pela BrasilAPI,
+/// seguido pelo nome do provedor.
+pub const UNSUPPORTED_PROVIDER_MESSAGE: &str = "unsupported provider";
+
+/// Prefixo da mensagem retornada quando a versão escolhida com `Config::prefer_version` não existe,
+/// seguido pelo endpoint e pela versão, por exemplo `cep v9`.
+pub const UNSUPPORTED_VERSION_MESSAGE: &str = "unsupported endpoint version";
+
+/// Prefixo da mensagem retornada quando um `transport::Cassette` em modo de reprodução não
+/// possui uma interação gravada para a URL, seguido pela URL.
+pub const CASSETTE_MISS_MESSAGE: &str = "no recorded interaction for url";
+
+/// Prefixo da mensagem retornada quando o corpo de uma resposta não é UTF-8 válido, seguido
+/// pela posição do primeiro byte inválido.
+pub const INVALID_UTF8_MESSAGE: &str = "response body is not valid utf-8";
+
+/// Mensagem retornada quando uma resposta bem-sucedida não possui corpo, por exemplo de um
+/// espelho da API mal configurado.
+pub const EMPTY_RESPONSE_MESSAGE: &str = "empty response body: expected json";
+
+/// Prefixo da mensagem retornada quando uma lista lida de forma incremental não é um array JSON
+/// válido, seguido pelo motivo.
+pub const INVALID_JSON_ARRAY_MESSAGE: &str = "response body is not a valid json array";
+
+/// Prefixo da mensagem retornada quando uma resposta possui chaves repetidas em um mesmo objeto
+/// e `Config::duplicate_keys` é `DuplicateKeys::Reject`, seguido pelo caminho da chave.
+pub const DUPLICATE_KEY_MESSAGE: &str = "duplicate json key";
+
+/// Mensagem retornada quando `Config::retry_deadline` termina antes que a consulta seja
+/// concluída. Quando o prazo impede uma nova tentativa, é seguida pelo erro da última tentativa.
+pub const DEADLINE_EXCEEDED_MESSAGE: &str = "retry deadline exceeded";
+
+/// Mensagem retornada quando `client::configure` é chamada após o cliente já ter sido configurado.
+pub const ALREADY_CONFIGURED_MESSAGE: &str =
+    "client already configured: configure must be called once, before the first request";
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, PartialOrd)]
 pub struct BrasilAPIError {
     pub message: String,
@@ -15,6 +85,15 @@ pub enum Errored {
     NotFound,
     InternalServerError,
     BadRequest,
+    InvalidInput,
+    UnsupportedProvider,
+    Deserialization,
+    EmptyResponse,
+    Timeout,
+    DeadlineExceeded,
+    Network,
+    TooManyRedirects,
+    InvariantViolation,
     Unexpected,
 }
 
@@ -35,6 +114,10 @@ pub struct Error {
     pub api_error: Option<BrasilAPIError>,
     pub message: String,
     pub error: Errored,
+    /// Item da consulta que causou o erro em operações com várias requisições, por exemplo
+    /// `uf SP` ou `cep 01001000`. Adicionado por `with_context`.
+    #[serde(default)]
+    pub context: Option<Box<str>>,
 }
 
 impl Error {
@@ -44,43 +127,230 @@ impl Error {
             code,
             message,
             error,
+            context: None,
+        }
+    }
+
+    /// Anota o erro com a consulta que o causou, mantendo o tipo e a mensagem originais.
+    ///
+    /// Quando o erro já possui um contexto, o novo contexto é adicionado antes dele, separado
+    /// por `: `, por exemplo `uf SP: provider gov`.
+    ///
+    /// # Exemplo
+    /// ```
+    /// use brasilapi::error::{Error, INVALID_CEP_MESSAGE};
+    ///
+    /// let error = Error::invalid_input(INVALID_CEP_MESSAGE).with_context("cep 0100");
+    ///
+    /// assert_eq!(error.context.as_deref(), Some("cep 0100"));
+    /// ```
+    pub fn with_context(self, context: impl Into<String>) -> Self {
+        let context = match self.context {
+            Some(inner) => format!("{}: {inner}", context.into()),
+            None => context.into(),
+        };
+
+        Self {
+            context: Some(context.into_boxed_str()),
+            ..self
         }
     }
 
+    /// Cria um erro de validação local, sem que nenhuma requisição seja feita.
+    pub fn invalid_input(message: &str) -> Self {
+        Self::new(message.to_string(), Errored::InvalidInput, None)
+    }
+
     pub fn from_error(error: reqwest::Error) -> Self {
         let status = error.status();
         let message = error.to_string();
 
         let api_error: Option<BrasilAPIError> = serde_json::from_str(&error.to_string()).ok();
-        let error = Errored::status_code(status);
+        let error = match (error.is_timeout(), error.is_connect(), error.is_redirect()) {
+            (true, _, _) => Errored::Timeout,
+            (_, true, _) => Errored::Network,
+            (_, _, true) => Errored::TooManyRedirects,
+            _ => Errored::status_code(status),
+        };
 
         Self {
             code: status.map(|s| s.as_u16()),
             message,
             api_error,
             error,
+            context: None,
+        }
+    }
+
+    /// Indica se o erro é temporário e a consulta pode ser repetida.
+    pub fn is_retryable(&self) -> bool {
+        match self.error {
+            Errored::Timeout | Errored::Network | Errored::InternalServerError => true,
+            _ => matches!(self.code, Some(429 | 502 | 503 | 504)),
         }
     }
 
-    /// Retorna um erro caso o status code seja diferente de 200
+    /// Status HTTP sugerido para repassar o erro em um servidor web que consulta a BrasilAPI.
+    ///
+    /// Erros de validação resultam em `400`, recursos inexistentes em `404`, `429` é repassado e
+    /// o tempo limite excedido resulta em `504`. Os demais erros da BrasilAPI ou da conexão com
+    /// ela resultam em `502`.
+    pub fn suggested_http_status(&self) -> u16 {
+        match self.error {
+            Errored::NotFound => 404,
+            Errored::BadRequest | Errored::InvalidInput | Errored::UnsupportedProvider => 400,
+            Errored::Timeout | Errored::DeadlineExceeded => 504,
+            _ if self.code == Some(429) => 429,
+            _ => 502,
+        }
+    }
+
+    /// Erro `Errored::DeadlineExceeded`, com o erro da última tentativa quando houver.
+    pub(crate) fn deadline_exceeded(last_error: Option<&Error>) -> Self {
+        let message = match last_error {
+            Some(error) => format!("{DEADLINE_EXCEEDED_MESSAGE}: {}", error.message),
+            None => DEADLINE_EXCEEDED_MESSAGE.to_string(),
+        };
+
+        Self::new(message, Errored::DeadlineExceeded, None)
+    }
+
+    /// Erro `Errored::EmptyResponse`, para respostas sem corpo onde um JSON era esperado.
+    pub(crate) fn empty_response() -> Self {
+        Self::new(
+            EMPTY_RESPONSE_MESSAGE.to_string(),
+            Errored::EmptyResponse,
+            None,
+        )
+    }
+
+    /// Cria um erro a partir de uma falha na desserialização do corpo da resposta.
+    pub fn from_json_error(error: serde_json::Error) -> Self {
+        Self::new(error.to_string(), Errored::Deserialization, None)
+    }
+
+    /// Igual a `from_json_error`, mas quando `body` é o objeto de erro da BrasilAPI
+    /// (`{"message": ..., "type": ..., "name": ...}`) no lugar do valor esperado, por exemplo de
+    /// uma lista, retorna a mensagem do servidor com um erro `Errored::Unexpected`.
+    pub(crate) fn from_json_body_error(body: &[u8], error: serde_json::Error) -> Self {
+        Self::from_api_error_body(body).unwrap_or_else(|| Self::from_json_error(error))
+    }
+
+    /// Erro `Errored::Unexpected` com a mensagem do servidor, quando `body` é o objeto de erro da
+    /// BrasilAPI.
+    pub(crate) fn from_api_error_body(body: &[u8]) -> Option<Self> {
+        let api_error = serde_json::from_slice::<BrasilAPIError>(body).ok()?;
+
+        Some(Self {
+            code: None,
+            message: api_error.message.clone(),
+            api_error: Some(api_error),
+            error: Errored::Unexpected,
+            context: None,
+        })
+    }
+
+    /// Retorna a resposta quando o status é `200`, ou `304 Not Modified` em resposta a uma
+    /// requisição condicional, e um erro com a mensagem da API nos demais casos.
     pub async fn from_response(response: reqwest::Response) -> Result<reqwest::Response, Self> {
         let status = response.status();
 
         let error = Errored::status_code(Some(status));
 
         match status {
-            reqwest::StatusCode::OK => Ok(response),
+            reqwest::StatusCode::OK | reqwest::StatusCode::NOT_MODIFIED => Ok(response),
             _ => {
-                let body = response.text().await.unwrap();
+                let body = response.text().await.unwrap_or_default();
                 let api_error: Option<BrasilAPIError> = serde_json::from_str(&body).ok();
 
                 Err(Self {
                     code: Some(status.as_u16()),
-                    message: body,
+                    message: match &api_error {
+                        Some(api_error) => api_error.message.clone(),
+                        None => body,
+                    },
                     api_error,
                     error,
+                    context: None,
                 })
             }
         }
     }
 }
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn from_response_not_modified_test() {
+        let response = http::Response::builder().status(304).body("").unwrap();
+        let response = Error::from_response(reqwest::Response::from(response))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_MODIFIED);
+
+        let response = http::Response::builder().status(404).body("").unwrap();
+        let error = Error::from_response(reqwest::Response::from(response))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::NotFound);
+    }
+
+    #[test]
+    fn from_json_body_error_test() {
+        let body = r#"{"message": "Todos os serviços de CEP retornaram erro.", "type": "service_error", "name": "CepPromiseError"}"#;
+        let json_error = serde_json::from_str::<Vec<String>>(body).unwrap_err();
+
+        let error = Error::from_json_body_error(body.as_bytes(), json_error);
+
+        assert_eq!(error.error, Errored::Unexpected);
+        assert_eq!(error.message, "Todos os serviços de CEP retornaram erro.");
+        assert_eq!(error.api_error.unwrap().kind, "service_error");
+
+        let json_error = serde_json::from_str::<Vec<String>>("[1]").unwrap_err();
+
+        assert_eq!(
+            Error::from_json_body_error(b"[1]", json_error).error,
+            Errored::Deserialization
+        );
+    }
+
+    #[test]
+    fn suggested_http_status_test() {
+        let status = |error: Errored, code: Option<u16>| {
+            Error::new(String::new(), error, code).suggested_http_status()
+        };
+
+        assert_eq!(status(Errored::NotFound, Some(404)), 404);
+        assert_eq!(status(Errored::InvalidInput, None), 400);
+        assert_eq!(status(Errored::Timeout, None), 504);
+        assert_eq!(status(Errored::DeadlineExceeded, None), 504);
+        assert_eq!(status(Errored::Unexpected, Some(429)), 429);
+        assert_eq!(status(Errored::InternalServerError, Some(500)), 502);
+        assert_eq!(status(Errored::Network, None), 502);
+    }
+
+    #[test]
+    fn is_retryable_test() {
+        assert!(Error::new(String::new(), Errored::Timeout, None).is_retryable());
+        assert!(Error::new(String::new(), Errored::Unexpected, Some(503)).is_retryable());
+        assert!(!Error::new(String::new(), Errored::NotFound, Some(404)).is_retryable());
+        assert!(!Error::invalid_input("invalid").is_retryable());
+        assert!(!Error::deadline_exceeded(None).is_retryable());
+    }
+
+    #[test]
+    fn with_context_test() {
+        let error = Error::new("not found".to_string(), Errored::NotFound, Some(404))
+            .with_context("provider gov")
+            .with_context("uf SP");
+
+        assert_eq!(error.context.as_deref(), Some("uf SP: provider gov"));
+        assert_eq!(error.message, "not found");
+        assert_eq!(error.error, Errored::NotFound);
+        assert_eq!(error.code, Some(404));
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Error, spec::BRASIL_API_URL};
+use crate::{
+    client,
+    error::{Error, Errored},
+    number,
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Brand {
@@ -8,6 +12,29 @@ pub struct Brand {
     valor: String,
 }
 
+impl Brand {
+    pub fn get_name(&self) -> &str {
+        &self.nome
+    }
+
+    /// Código da marca, usado em `get_models`.
+    pub fn get_code(&self) -> &str {
+        &self.valor
+    }
+}
+
+/// Modelo de veículo de uma marca.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Model {
+    modelo: String,
+}
+
+impl Model {
+    pub fn get_name(&self) -> &str {
+        &self.modelo
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Vehicle {
     valor: String,
@@ -28,12 +55,69 @@ pub struct Vehicle {
     data_consulta: String,
 }
 
+impl Vehicle {
+    /// Preço do veículo como retornado pela API, por exemplo `"R$ 289.116,00"`.
+    pub fn get_value(&self) -> &str {
+        &self.valor
+    }
+
+    /// Preço do veículo convertido para `f64`.
+    pub fn get_price(&self) -> Option<f64> {
+        number::parse_f64(&self.valor)
+    }
+
+    /// Preço do veículo convertido para `Decimal`, sem perda de precisão.
+    #[cfg(feature = "decimal")]
+    pub fn get_price_decimal(&self) -> Option<rust_decimal::Decimal> {
+        number::parse_decimal(&self.valor)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ReferenceTable {
     codigo: i32,
     mes: String,
 }
 
+/// Nomes dos meses como retornados no campo `mes` das tabelas de referência.
+const MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+
+impl ReferenceTable {
+    pub fn get_code(&self) -> i32 {
+        self.codigo
+    }
+
+    pub fn get_month(&self) -> &str {
+        &self.mes
+    }
+
+    /// Retorna o ano e o mês (1 a 12) da tabela, interpretados a partir do campo `mes`
+    /// (por exemplo `"outubro/2024"`).
+    pub fn get_year_month(&self) -> Option<(u16, u8)> {
+        let (month, year) = self.mes.trim().split_once('/')?;
+
+        let month = MONTHS
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(month.trim()))?;
+        let year = year.trim().parse().ok()?;
+
+        Some((year, month as u8 + 1))
+    }
+}
+
 pub enum VehicleType {
     Car,
     Motorcycle,
@@ -50,6 +134,7 @@ impl VehicleType {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct FipeService {
     base_url: String,
 }
@@ -57,7 +142,7 @@ pub struct FipeService {
 impl FipeService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
@@ -78,10 +163,26 @@ impl FipeService {
             self.base_url, vehicle_type, reference_table
         );
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
+        client::get(&url).await
+    }
+
+    async fn get_models_request(
+        &self,
+        vehicle_type: VehicleType,
+        brand_code: &str,
+    ) -> Result<reqwest::Response, Error> {
+        if brand_code.is_empty() || !brand_code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::invalid_input("invalid brand code: expected digits"));
         }
+
+        let url = format!(
+            "{}/api/fipe/veiculos/v1/{}/{}",
+            self.base_url,
+            vehicle_type.to_string(),
+            brand_code
+        );
+
+        client::get(&url).await
     }
 
     async fn get_vehicle_request(
@@ -99,19 +200,13 @@ impl FipeService {
             self.base_url, fipe_code, reference_table
         );
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 
     async fn get_reference_tables_request(&self) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/fipe/tabelas/v1/", self.base_url);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 }
 
@@ -139,18 +234,49 @@ pub async fn get_brands(
     vehicle_type: VehicleType,
     reference_table: Option<i64>,
 ) -> Result<Vec<Brand>, Error> {
-    let fipe_service = FipeService::new(BRASIL_API_URL);
+    let fipe_service = FipeService::new(client::base_url());
 
     let response = fipe_service
         .get_brands_request(vehicle_type, reference_table)
         .await?;
 
-    let body = response.text().await.unwrap();
-    let brands: Vec<Brand> = serde_json::from_str(&body).unwrap();
+    let brands: Vec<Brand> = client::read_list(response).await?;
 
     Ok(brands)
 }
 
+/// #### `get_models(vehicle_type: VehicleType, brand_code: &str)`
+/// Lista os modelos de veículos de uma marca.
+///
+/// ## Argumentos
+/// * `vehicle_type: VehicleType` => Tipo de veículo para consulta.
+/// * `brand_code: &str` => Código da marca, retornado por `Brand::get_code`.
+///
+/// ## Retorno
+/// * `Result<Vec<Model>, Error>` => Um código de marca que não seja numérico retorna um erro
+///   `Errored::InvalidInput` sem que a API seja consultada.
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::fipe;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let models = fipe::get_models(fipe::VehicleType::Car, "21").await.unwrap();
+/// }
+/// ```
+pub async fn get_models(vehicle_type: VehicleType, brand_code: &str) -> Result<Vec<Model>, Error> {
+    let fipe_service = FipeService::new(client::base_url());
+
+    let response = fipe_service
+        .get_models_request(vehicle_type, brand_code)
+        .await?;
+
+    let models: Vec<Model> = client::read_list(response).await?;
+
+    Ok(models)
+}
+
 /// #### `get_vehicles(fipe_code: &str, reference_table: Option<i64>)`
 /// Consulta o preço do veículo segundo a tabela fipe.
 ///
@@ -174,14 +300,13 @@ pub async fn get_vehicles(
     fipe_code: &str,
     reference_table: Option<i64>,
 ) -> Result<Vec<Vehicle>, Error> {
-    let fipe_service = FipeService::new(BRASIL_API_URL);
+    let fipe_service = FipeService::new(client::base_url());
 
     let response = fipe_service
         .get_vehicle_request(fipe_code, reference_table)
         .await?;
 
-    let body = response.text().await.unwrap();
-    let vehicle: Vec<Vehicle> = serde_json::from_str(&body).unwrap();
+    let vehicle: Vec<Vehicle> = client::read_list(response).await?;
 
     Ok(vehicle)
 }
@@ -202,19 +327,69 @@ pub async fn get_vehicles(
 /// }
 /// ```
 pub async fn get_reference_tables() -> Result<Vec<ReferenceTable>, Error> {
-    let fipe_service = FipeService::new(BRASIL_API_URL);
+    let fipe_service = FipeService::new(client::base_url());
 
     let response = fipe_service.get_reference_tables_request().await?;
 
-    let body = response.text().await.unwrap();
-    let reference_tables: Vec<ReferenceTable> = serde_json::from_str(&body).unwrap();
+    let reference_tables: Vec<ReferenceTable> = client::read_list(response).await?;
 
     Ok(reference_tables)
 }
 
+/// Seleciona a tabela de referência de um mês em uma lista de tabelas.
+fn find_reference_table(
+    reference_tables: Vec<ReferenceTable>,
+    year: u16,
+    month: u8,
+) -> Result<ReferenceTable, Error> {
+    if !(1..=12).contains(&month) {
+        return Err(Error::invalid_input(
+            "invalid month: expected a value between 1 and 12",
+        ));
+    }
+
+    reference_tables
+        .into_iter()
+        .find(|table| table.get_year_month() == Some((year, month)))
+        .ok_or_else(|| {
+            Error::new(
+                String::from("reference table not found"),
+                Errored::NotFound,
+                Some(404),
+            )
+        })
+}
+
+/// #### `reference_table_for(year: u16, month: u8)`
+/// Busca a tabela de referência de um mês específico.
+///
+/// ## Argumentos
+/// * `year: u16` => Ano da tabela, por exemplo `2024`.
+/// * `month: u8` => Mês da tabela, de 1 a 12.
+///
+/// ## Retorno
+/// * `Result<ReferenceTable, Error>` => Retorna um erro `Errored::NotFound` caso o mês não esteja disponível.
+///
+/// # Exemplo
+/// ```rust
+/// use brasilapi::fipe;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let reference_table = fipe::reference_table_for(2024, 1).await.unwrap();
+///    let vehicles = fipe::get_vehicles("008274-0", Some(reference_table.get_code() as i64)).await.unwrap();
+/// }
+/// ```
+pub async fn reference_table_for(year: u16, month: u8) -> Result<ReferenceTable, Error> {
+    let reference_tables = get_reference_tables().await?;
+
+    find_reference_table(reference_tables, year, month)
+}
+
 #[cfg(test)]
 mod fipe_tests {
     use super::*;
+    use httpmock::MockServer;
 
     #[tokio::test]
     async fn test_get_brands() {
@@ -222,6 +397,55 @@ mod fipe_tests {
         assert!(!brands.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_models() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/fipe/veiculos/v1/carros/21");
+                then.status(200)
+                    .body(r#"[{"modelo": "147 C/ CL"}, {"modelo": "UNO MILLE 1.0"}]"#);
+            })
+            .await;
+
+        let fipe_service = FipeService::new(&server.base_url());
+        let response = fipe_service
+            .get_models_request(VehicleType::Car, "21")
+            .await
+            .unwrap();
+        let models: Vec<Model> = client::read_json(response).await.unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[1].get_name(), "UNO MILLE 1.0");
+
+        let error = fipe_service
+            .get_models_request(VehicleType::Car, "../21")
+            .await
+            .unwrap_err();
+        assert_eq!(error.error, Errored::InvalidInput);
+    }
+
+    #[test]
+    fn test_find_reference_table() {
+        let reference_tables: Vec<ReferenceTable> = serde_json::from_str(
+            r#"[
+                {"codigo": 308, "mes": "agosto/2024 "},
+                {"codigo": 307, "mes": "julho/2024 "},
+                {"codigo": 296, "mes": "março/2023 "}
+            ]"#,
+        )
+        .unwrap();
+
+        let table = find_reference_table(reference_tables.clone(), 2023, 3).unwrap();
+        assert_eq!(table.get_code(), 296);
+
+        let error = find_reference_table(reference_tables.clone(), 2020, 1).unwrap_err();
+        assert_eq!(error.error, Errored::NotFound);
+
+        let error = find_reference_table(reference_tables, 2024, 13).unwrap_err();
+        assert_eq!(error.error, Errored::InvalidInput);
+    }
+
     #[tokio::test]
     async fn test_get_vehicles() {
         let vehicles = get_vehicles("008274-0", None).await.unwrap();
@@ -232,4 +456,26 @@ mod fipe_tests {
         assert_eq!(vehicle.marca, "Audi");
         assert_eq!(vehicle.modelo, "RS E-TRON GT Quattro Aut. (Elétrico)");
     }
+
+    #[test]
+    fn test_vehicle_price() {
+        let vehicle: Vehicle = serde_json::from_str(
+            r#"{
+                "valor": "R$ 289.116,00",
+                "marca": "Audi",
+                "modelo": "RS E-TRON GT Quattro Aut. (Elétrico)",
+                "anoModelo": 2022,
+                "combustivel": "Elétrico",
+                "codigoFipe": "008274-0",
+                "mesReferencia": "agosto de 2024 ",
+                "tipoVeiculo": 1,
+                "siglaCombustivel": "E",
+                "dataConsulta": "segunda-feira, 5 de agosto de 2024 15:03"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(vehicle.get_value(), "R$ 289.116,00");
+        assert_eq!(vehicle.get_price(), Some(289116.0));
+    }
 }
@@ -0,0 +1,40 @@
+//! Utilitário de testes para as fixtures em `tests/fixtures/`.
+//!
+//! Os testes com mock leem as respostas salvas em disco. Para atualizar as fixtures com os dados
+//! atuais da BrasilAPI, rode os testes com a variável de ambiente `BRASILAPI_FIXTURES` definida:
+//!
+//! ```sh
+//! BRASILAPI_FIXTURES=1 cargo test
+//! ```
+//!
+//! Nesse modo cada fixture é buscada uma única vez na API real e o arquivo correspondente é
+//! sobrescrito antes de ser servido ao teste.
+use std::path::PathBuf;
+
+use crate::spec::BRASIL_API_URL;
+
+const FIXTURES_ENV: &str = "BRASILAPI_FIXTURES";
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{}.json", name))
+}
+
+/// Retorna o conteúdo da fixture `name`, regerando-a a partir de `path` na API real quando
+/// `BRASILAPI_FIXTURES` estiver definida.
+pub(crate) async fn load(name: &str, path: &str) -> String {
+    let file = fixture_path(name);
+
+    if std::env::var_os(FIXTURES_ENV).is_some() {
+        let url = format!("{}{}", BRASIL_API_URL, path);
+        let body = reqwest::get(&url).await.unwrap().text().await.unwrap();
+
+        std::fs::write(&file, &body).unwrap();
+
+        return body;
+    }
+
+    std::fs::read_to_string(&file).unwrap()
+}
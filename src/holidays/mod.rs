@@ -1,5 +1,10 @@
-use crate::{error::*, spec::BRASIL_API_URL};
+use crate::{batch::Batch, client, error::*};
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "chrono")]
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Holiday {
@@ -10,6 +15,62 @@ pub struct Holiday {
     full_name: Option<String>,
 }
 
+impl Holiday {
+    /// Data do feriado no formato `AAAA-MM-DD`.
+    pub fn get_date(&self) -> &str {
+        &self.date
+    }
+
+    /// Tipo do feriado, por exemplo `national`.
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_full_name(&self) -> Option<&str> {
+        self.full_name.as_deref()
+    }
+
+    /// Data do feriado convertida para `chrono::NaiveDate`, ou `None` se não estiver no formato
+    /// `AAAA-MM-DD`.
+    #[cfg(feature = "chrono")]
+    pub fn get_naive_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.date, "%Y-%m-%d").ok()
+    }
+}
+
+/// Feriados de um intervalo de anos, em que a consulta de parte dos anos pode ter falhado.
+#[derive(Debug, Clone)]
+pub struct HolidayRange {
+    holidays: BTreeMap<u16, Vec<Holiday>>,
+    failures: Vec<(u16, Error)>,
+}
+
+impl HolidayRange {
+    /// Feriados de cada ano consultado com sucesso, indexados pelo ano.
+    pub fn get_holidays(&self) -> &BTreeMap<u16, Vec<Holiday>> {
+        &self.holidays
+    }
+
+    /// Anos cuja consulta falhou e o erro retornado para cada um, em ordem crescente.
+    pub fn get_failures(&self) -> &[(u16, Error)] {
+        &self.failures
+    }
+
+    /// Indica se todos os anos foram consultados com sucesso.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn into_holidays(self) -> BTreeMap<u16, Vec<Holiday>> {
+        self.holidays
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct HolidayService {
     base_url: String,
 }
@@ -17,20 +78,156 @@ pub struct HolidayService {
 impl HolidayService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_holiday_request(&self, year: &str) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/feriados/v1/{}", self.base_url, year);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
+        client::get(&url).await
+    }
+
+    async fn fetch_holidays(&self, year: &str) -> Result<Vec<Holiday>, Error> {
+        let response = self.get_holiday_request(year).await?;
+
+        client::read_list(response).await
+    }
+
+    async fn fetch_range(
+        &self,
+        start_year: u16,
+        end_year: u16,
+        batch: &Batch,
+    ) -> Result<HolidayRange, Error> {
+        if start_year > end_year {
+            return Err(Error::invalid_input(
+                "invalid year range: start_year is after end_year",
+            ));
+        }
+
+        let results = batch
+            .run(start_year..=end_year, |year| async move {
+                let result = self
+                    .fetch_holidays(&year.to_string())
+                    .await
+                    .map_err(|error| error.with_context(format!("year {year}")));
+
+                (year, result)
+            })
+            .await;
+
+        let mut holidays = BTreeMap::new();
+        let mut failures = Vec::new();
+
+        for (year, result) in results {
+            match result {
+                Ok(year_holidays) => {
+                    holidays.insert(year, year_holidays);
+                }
+                Err(error) => failures.push((year, error)),
+            }
+        }
+
+        if holidays.is_empty() {
+            return Err(failures.swap_remove(0).1);
+        }
+
+        Ok(HolidayRange { holidays, failures })
+    }
+
+    /// Feriados do ano, com suas datas, ordenados por data.
+    #[cfg(feature = "chrono")]
+    async fn fetch_dated_holidays(&self, year: i32) -> Result<Vec<(NaiveDate, Holiday)>, Error> {
+        let mut holidays: Vec<(NaiveDate, Holiday)> = self
+            .fetch_holidays(&year.to_string())
+            .await?
+            .into_iter()
+            .filter_map(|holiday| Some((holiday.get_naive_date()?, holiday)))
+            .collect();
+
+        holidays.sort_by_key(|(date, _)| *date);
+
+        Ok(holidays)
+    }
+
+    #[cfg(feature = "chrono")]
+    async fn find_next_holiday(&self, from: NaiveDate) -> Result<Holiday, Error> {
+        for year in [from.year(), from.year() + 1] {
+            let next = self
+                .fetch_dated_holidays(year)
+                .await?
+                .into_iter()
+                .find(|(date, _)| *date >= from);
+
+            if let Some((_, holiday)) = next {
+                return Ok(holiday);
+            }
         }
+
+        Err(holiday_not_found())
+    }
+
+    #[cfg(feature = "chrono")]
+    async fn find_holiday_on(&self, date: NaiveDate) -> Result<Option<Holiday>, Error> {
+        Ok(self
+            .fetch_dated_holidays(date.year())
+            .await?
+            .into_iter()
+            .find(|(holiday_date, _)| *holiday_date == date)
+            .map(|(_, holiday)| holiday))
+    }
+
+    /// Avança `n` dias úteis a partir de `start`, ou retrocede quando `n` é negativo. Os feriados
+    /// de cada ano alcançado são consultados uma única vez.
+    #[cfg(feature = "chrono")]
+    async fn shift_business_days(&self, start: NaiveDate, n: i32) -> Result<NaiveDate, Error> {
+        let step = if n < 0 {
+            Duration::days(-1)
+        } else {
+            Duration::days(1)
+        };
+        let mut holidays: HashMap<i32, HashSet<NaiveDate>> = HashMap::new();
+        let mut remaining = n.unsigned_abs();
+        let mut date = start;
+
+        while remaining > 0 {
+            date = date.checked_add_signed(step).ok_or_else(|| {
+                Error::invalid_input("business day calculation out of the supported date range")
+            })?;
+
+            if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                continue;
+            }
+
+            if let Entry::Vacant(entry) = holidays.entry(date.year()) {
+                let dates = self
+                    .fetch_dated_holidays(date.year())
+                    .await?
+                    .into_iter()
+                    .map(|(date, _)| date)
+                    .collect();
+
+                entry.insert(dates);
+            }
+
+            if !holidays[&date.year()].contains(&date) {
+                remaining -= 1;
+            }
+        }
+
+        Ok(date)
     }
 }
 
+fn holiday_not_found() -> Error {
+    Error::new(
+        String::from("holiday not found"),
+        Errored::NotFound,
+        Some(404),
+    )
+}
+
 /// #### `get_holidays(year: &str)`
 /// Lista os feriados nacionais de determinado ano.
 ///
@@ -51,14 +248,9 @@ impl HolidayService {
 /// }
 /// ```
 pub async fn get_holidays(year: &str) -> Result<Vec<Holiday>, Error> {
-    let holiday_service = HolidayService::new(BRASIL_API_URL);
-
-    let response = holiday_service.get_holiday_request(year).await?;
+    let holiday_service = HolidayService::new(client::base_url());
 
-    let body = response.text().await.unwrap();
-    let holidays: Vec<Holiday> = serde_json::from_str(&body).unwrap();
-
-    Ok(holidays)
+    holiday_service.fetch_holidays(year).await
 }
 
 /// #### `get_holiday(year: &str, month: &str, day: &str)`
@@ -90,19 +282,161 @@ pub async fn get_holiday(year: &str, month: &str, day: &str) -> Result<Holiday,
         .position(|holiday| holiday.date == format!("{year}-{month}-{day}"));
 
     match holiday_position {
-        Some(position) => {
-            return Ok(holidays.get(position).unwrap().clone());
-        }
-        None => Err(Error::new(
-            String::from("holiday not found"),
-            Errored::NotFound,
-            Some(404),
-        )),
+        Some(position) => Ok(holidays.get(position).unwrap().clone()),
+        None => Err(holiday_not_found()),
     }
 }
 
-#[cfg(test)]
+/// #### `get_range(start_year: u16, end_year: u16)`
+/// Lista os feriados nacionais de cada ano do intervalo, consultando os anos de forma
+/// concorrente com a configuração padrão de `Batch`.
+///
+/// Os anos cuja consulta falha são retornados em `HolidayRange::get_failures`, sem descartar os
+/// demais. Um erro é retornado apenas se todos os anos falharem ou se `start_year` for posterior a
+/// `end_year`, neste caso `Errored::InvalidInput`.
+///
+/// ### Argumentos
+/// * `start_year:u16` => Primeiro ano do intervalo.
+/// * `end_year:u16` => Último ano do intervalo, incluído na consulta.
+///
+/// ### Retorno
+/// * `Result<HolidayRange, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::holidays;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let range = holidays::get_range(2024, 2026).await.unwrap();
+///
+///     for (year, holidays) in range.get_holidays() {
+///         println!("{}: {} feriados", year, holidays.len());
+///     }
+/// }
+/// ```
+pub async fn get_range(start_year: u16, end_year: u16) -> Result<HolidayRange, Error> {
+    let holiday_service = HolidayService::new(client::base_url());
+
+    holiday_service
+        .fetch_range(start_year, end_year, &Batch::default())
+        .await
+}
+
+/// #### `next_holiday(from: NaiveDate)`
+/// Retorna o primeiro feriado nacional na data `from` ou depois dela.
+///
+/// Quando não há mais feriados no ano de `from`, por exemplo no fim de dezembro, o primeiro
+/// feriado do ano seguinte é retornado. Disponível com a feature `chrono`.
+///
+/// ### Argumento
+/// * `from:NaiveDate` => Data a partir da qual o feriado é buscado.
+///
+/// ### Retorno
+/// * `Result<Holiday, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::holidays;
+/// use chrono::NaiveDate;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let from = NaiveDate::from_ymd_opt(2022, 12, 26).unwrap();
+///     let holiday = holidays::next_holiday(from).await.unwrap();
+///
+///     assert_eq!(holiday.get_date(), "2023-01-01");
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub async fn next_holiday(from: NaiveDate) -> Result<Holiday, Error> {
+    let holiday_service = HolidayService::new(client::base_url());
+
+    holiday_service.find_next_holiday(from).await
+}
+
+/// #### `is_holiday(date: NaiveDate)`
+/// Verifica se `date` é um feriado nacional.
+///
+/// Os feriados do ano são consultados a cada chamada. Para verificações repetidas, por exemplo
+/// em um laço sobre vários dias, ative o [cache](crate::cache) para que a lista de cada ano seja
+/// baixada uma única vez. Disponível com a feature `chrono`.
+///
+/// ### Argumento
+/// * `date:NaiveDate` => Data para verificar.
+///
+/// ### Retorno
+/// * `Result<bool, Error>`
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{cache, holidays};
+/// use chrono::NaiveDate;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     cache::enable(Duration::from_secs(3600));
+///
+///     let date = NaiveDate::from_ymd_opt(2022, 9, 7).unwrap();
+///     assert!(holidays::is_holiday(date).await.unwrap());
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub async fn is_holiday(date: NaiveDate) -> Result<bool, Error> {
+    Ok(is_holiday_detail(date).await?.is_some())
+}
+
+/// #### `is_holiday_detail(date: NaiveDate)`
+/// Igual a `is_holiday`, retornando o feriado encontrado em `date`.
+///
+/// ### Argumento
+/// * `date:NaiveDate` => Data para verificar.
+///
+/// ### Retorno
+/// * `Result<Option<Holiday>, Error>` => `None` quando `date` não é um feriado.
+#[cfg(feature = "chrono")]
+pub async fn is_holiday_detail(date: NaiveDate) -> Result<Option<Holiday>, Error> {
+    let holiday_service = HolidayService::new(client::base_url());
+
+    holiday_service.find_holiday_on(date).await
+}
+
+/// #### `add_business_days(start: NaiveDate, n: i32)`
+/// Soma `n` dias úteis a `start`, ignorando sábados, domingos e feriados nacionais.
+///
+/// Com `n` negativo a contagem é feita para trás, e com `n` igual a zero `start` é retornada
+/// sem alterações, mesmo que não seja um dia útil. Os feriados de todos os anos alcançados são
+/// consultados, uma vez por ano. Disponível com a feature `chrono`.
+///
+/// ### Argumentos
+/// * `start:NaiveDate` => Data inicial, que não entra na contagem.
+/// * `n:i32` => Quantidade de dias úteis.
+///
+/// ### Retorno
+/// * `Result<NaiveDate, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::holidays;
+/// use chrono::NaiveDate;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let start = NaiveDate::from_ymd_opt(2022, 12, 23).unwrap();
+///     let date = holidays::add_business_days(start, 1).await.unwrap();
+///
+///     assert_eq!(date, NaiveDate::from_ymd_opt(2022, 12, 26).unwrap());
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub async fn add_business_days(start: NaiveDate, n: i32) -> Result<NaiveDate, Error> {
+    let holiday_service = HolidayService::new(client::base_url());
 
+    holiday_service.shift_business_days(start, n).await
+}
+
+#[cfg(test)]
 mod holidays_tests {
     use super::*;
 
@@ -135,4 +469,141 @@ mod holidays_tests {
 
         assert!(holiday.is_err());
     }
+
+    #[tokio::test]
+    async fn fetch_range_test() {
+        let server = httpmock::MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/feriados/v1/2022");
+                then.status(200).body(
+                    r#"[{"date": "2022-01-01", "name": "Confraternização mundial", "type": "national"}]"#,
+                );
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/feriados/v1/2023");
+                then.status(500).body(r#"{"message": "Erro interno"}"#);
+            })
+            .await;
+
+        let holiday_service = HolidayService::new(&server.base_url());
+        let range = holiday_service
+            .fetch_range(2022, 2023, &Batch::new().concurrency(2))
+            .await
+            .unwrap();
+
+        assert_eq!(range.get_holidays().keys().collect::<Vec<_>>(), vec![&2022]);
+        assert_eq!(range.get_failures()[0].0, 2023);
+        assert!(!range.is_complete());
+
+        let error = holiday_service
+            .fetch_range(2023, 2023, &Batch::new())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, Some(500));
+
+        let error = holiday_service
+            .fetch_range(2024, 2023, &Batch::new())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+    }
+
+    #[cfg(feature = "chrono")]
+    async fn mock_holidays(server: &httpmock::MockServer) {
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/feriados/v1/2022");
+                then.status(200).body(
+                    r#"[{"date": "2022-01-01", "name": "Confraternização mundial", "type": "national"}, {"date": "2022-12-25", "name": "Natal", "type": "national"}, {"date": "2022-11-15", "name": "Proclamação da República", "type": "national"}]"#,
+                );
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/feriados/v1/2023");
+                then.status(200).body(
+                    r#"[{"date": "2023-01-01", "name": "Confraternização mundial", "type": "national"}, {"date": "2023-12-25", "name": "Natal", "type": "national"}]"#,
+                );
+            })
+            .await;
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn next_holiday_test() {
+        let server = httpmock::MockServer::start_async().await;
+        mock_holidays(&server).await;
+
+        let holiday_service = HolidayService::new(&server.base_url());
+        let date = |month, day| NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+
+        let holiday = holiday_service
+            .find_next_holiday(date(11, 2))
+            .await
+            .unwrap();
+        assert_eq!(holiday.get_name(), "Proclamação da República");
+
+        let holiday = holiday_service
+            .find_next_holiday(date(12, 25))
+            .await
+            .unwrap();
+        assert_eq!(holiday.get_name(), "Natal");
+
+        let holiday = holiday_service
+            .find_next_holiday(date(12, 26))
+            .await
+            .unwrap();
+        assert_eq!(holiday.get_date(), "2023-01-01");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn find_holiday_on_test() {
+        let server = httpmock::MockServer::start_async().await;
+        mock_holidays(&server).await;
+
+        let holiday_service = HolidayService::new(&server.base_url());
+        let date = |month, day| NaiveDate::from_ymd_opt(2022, month, day).unwrap();
+
+        let holiday = holiday_service.find_holiday_on(date(12, 25)).await.unwrap();
+        assert_eq!(holiday.unwrap().get_name(), "Natal");
+
+        let holiday = holiday_service.find_holiday_on(date(12, 24)).await.unwrap();
+        assert!(holiday.is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn shift_business_days_test() {
+        let server = httpmock::MockServer::start_async().await;
+        mock_holidays(&server).await;
+
+        let holiday_service = HolidayService::new(&server.base_url());
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        // 2022-12-30 é sexta-feira e 2023-01-01 é um feriado em um domingo.
+        let forward = holiday_service
+            .shift_business_days(date(2022, 12, 29), 2)
+            .await
+            .unwrap();
+        assert_eq!(forward, date(2023, 1, 2));
+
+        // 2022-11-15 é um feriado em uma terça-feira.
+        let backward = holiday_service
+            .shift_business_days(date(2022, 11, 16), -1)
+            .await
+            .unwrap();
+        assert_eq!(backward, date(2022, 11, 14));
+
+        let unchanged = holiday_service
+            .shift_business_days(date(2022, 12, 25), 0)
+            .await
+            .unwrap();
+        assert_eq!(unchanged, date(2022, 12, 25));
+    }
 }
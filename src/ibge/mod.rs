@@ -1,13 +1,367 @@
-use crate::{error::Error, spec::BRASIL_API_URL};
+use crate::{
+    batch::{Batch, ProgressAggregator},
+    client::{self, HeaderMap},
+    endpoint::ListEndpoint,
+    error::{
+        Error, Errored, INVALID_MUNICIPALITY_CODE_MESSAGE, INVALID_STATE_CODE_MESSAGE,
+        INVALID_UF_MESSAGE, UNSUPPORTED_PROVIDER_MESSAGE,
+    },
+    normalize,
+    parse::{self, ParseMode, Parsed, Timings},
+};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::ascii::AsciiExt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::BitOr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub mod storage;
+
+/// Unidade federativa, identificada pela sigla.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Uf {
+    /// Rondônia
+    Ro,
+    /// Acre
+    Ac,
+    /// Amazonas
+    Am,
+    /// Roraima
+    Rr,
+    /// Pará
+    Pa,
+    /// Amapá
+    Ap,
+    /// Tocantins
+    To,
+    /// Maranhão
+    Ma,
+    /// Piauí
+    Pi,
+    /// Ceará
+    Ce,
+    /// Rio Grande do Norte
+    Rn,
+    /// Paraíba
+    Pb,
+    /// Pernambuco
+    Pe,
+    /// Alagoas
+    Al,
+    /// Sergipe
+    Se,
+    /// Bahia
+    Ba,
+    /// Minas Gerais
+    Mg,
+    /// Espírito Santo
+    Es,
+    /// Rio de Janeiro
+    Rj,
+    /// São Paulo
+    Sp,
+    /// Paraná
+    Pr,
+    /// Santa Catarina
+    Sc,
+    /// Rio Grande do Sul
+    Rs,
+    /// Mato Grosso do Sul
+    Ms,
+    /// Mato Grosso
+    Mt,
+    /// Goiás
+    Go,
+    /// Distrito Federal
+    Df,
+}
+
+impl Uf {
+    /// As 27 unidades federativas, na ordem do código IBGE.
+    pub const ALL: [Uf; 27] = [
+        Uf::Ro,
+        Uf::Ac,
+        Uf::Am,
+        Uf::Rr,
+        Uf::Pa,
+        Uf::Ap,
+        Uf::To,
+        Uf::Ma,
+        Uf::Pi,
+        Uf::Ce,
+        Uf::Rn,
+        Uf::Pb,
+        Uf::Pe,
+        Uf::Al,
+        Uf::Se,
+        Uf::Ba,
+        Uf::Mg,
+        Uf::Es,
+        Uf::Rj,
+        Uf::Sp,
+        Uf::Pr,
+        Uf::Sc,
+        Uf::Rs,
+        Uf::Ms,
+        Uf::Mt,
+        Uf::Go,
+        Uf::Df,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Uf::Ro => "RO",
+            Uf::Ac => "AC",
+            Uf::Am => "AM",
+            Uf::Rr => "RR",
+            Uf::Pa => "PA",
+            Uf::Ap => "AP",
+            Uf::To => "TO",
+            Uf::Ma => "MA",
+            Uf::Pi => "PI",
+            Uf::Ce => "CE",
+            Uf::Rn => "RN",
+            Uf::Pb => "PB",
+            Uf::Pe => "PE",
+            Uf::Al => "AL",
+            Uf::Se => "SE",
+            Uf::Ba => "BA",
+            Uf::Mg => "MG",
+            Uf::Es => "ES",
+            Uf::Rj => "RJ",
+            Uf::Sp => "SP",
+            Uf::Pr => "PR",
+            Uf::Sc => "SC",
+            Uf::Rs => "RS",
+            Uf::Ms => "MS",
+            Uf::Mt => "MT",
+            Uf::Go => "GO",
+            Uf::Df => "DF",
+        }
+    }
+
+    /// Nome da unidade federativa, por exemplo `São Paulo` para SP.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Uf::Ro => "Rondônia",
+            Uf::Ac => "Acre",
+            Uf::Am => "Amazonas",
+            Uf::Rr => "Roraima",
+            Uf::Pa => "Pará",
+            Uf::Ap => "Amapá",
+            Uf::To => "Tocantins",
+            Uf::Ma => "Maranhão",
+            Uf::Pi => "Piauí",
+            Uf::Ce => "Ceará",
+            Uf::Rn => "Rio Grande do Norte",
+            Uf::Pb => "Paraíba",
+            Uf::Pe => "Pernambuco",
+            Uf::Al => "Alagoas",
+            Uf::Se => "Sergipe",
+            Uf::Ba => "Bahia",
+            Uf::Mg => "Minas Gerais",
+            Uf::Es => "Espírito Santo",
+            Uf::Rj => "Rio de Janeiro",
+            Uf::Sp => "São Paulo",
+            Uf::Pr => "Paraná",
+            Uf::Sc => "Santa Catarina",
+            Uf::Rs => "Rio Grande do Sul",
+            Uf::Ms => "Mato Grosso do Sul",
+            Uf::Mt => "Mato Grosso",
+            Uf::Go => "Goiás",
+            Uf::Df => "Distrito Federal",
+        }
+    }
+
+    /// Busca a unidade federativa pelo nome, sem diferenciar acentos e maiúsculas, de forma que
+    /// `sao paulo` e `SÃO PAULO` resultam em SP.
+    pub fn from_name(name: &str) -> Option<Uf> {
+        let name = index_key(name.trim());
+
+        Uf::ALL
+            .into_iter()
+            .find(|uf| index_key(uf.get_name()) == name)
+    }
+
+    /// Código IBGE da unidade federativa, por exemplo `35` para SP.
+    pub fn get_ibge_code(&self) -> &'static str {
+        match self {
+            Uf::Ro => "11",
+            Uf::Ac => "12",
+            Uf::Am => "13",
+            Uf::Rr => "14",
+            Uf::Pa => "15",
+            Uf::Ap => "16",
+            Uf::To => "17",
+            Uf::Ma => "21",
+            Uf::Pi => "22",
+            Uf::Ce => "23",
+            Uf::Rn => "24",
+            Uf::Pb => "25",
+            Uf::Pe => "26",
+            Uf::Al => "27",
+            Uf::Se => "28",
+            Uf::Ba => "29",
+            Uf::Mg => "31",
+            Uf::Es => "32",
+            Uf::Rj => "33",
+            Uf::Sp => "35",
+            Uf::Pr => "41",
+            Uf::Sc => "42",
+            Uf::Rs => "43",
+            Uf::Ms => "50",
+            Uf::Mt => "51",
+            Uf::Go => "52",
+            Uf::Df => "53",
+        }
+    }
+
+    /// Busca a unidade federativa pelo código IBGE, por exemplo `35` para SP.
+    pub fn from_ibge_code(code: &str) -> Option<Uf> {
+        Uf::ALL.into_iter().find(|uf| uf.get_ibge_code() == code)
+    }
+
+    /// Região da unidade federativa, indicada pelo primeiro dígito do código IBGE.
+    pub fn region(&self) -> Region {
+        match &self.get_ibge_code()[..1] {
+            "1" => Region::Norte,
+            "2" => Region::Nordeste,
+            "3" => Region::Sudeste,
+            "4" => Region::Sul,
+            _ => Region::CentroOeste,
+        }
+    }
+}
+
+impl std::fmt::Display for Uf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<&str> for Uf {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// Converte a sigla, sem diferenciar maiúsculas de minúsculas.
+impl TryFrom<&str> for Uf {
+    type Error = Error;
+
+    fn try_from(uf: &str) -> Result<Self, Self::Error> {
+        Uf::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str().eq_ignore_ascii_case(uf))
+            .ok_or_else(|| Error::invalid_input(INVALID_UF_MESSAGE))
+    }
+}
+
+impl std::str::FromStr for Uf {
+    type Err = Error;
+
+    fn from_str(uf: &str) -> Result<Self, Self::Err> {
+        Uf::try_from(uf)
+    }
+}
+
+impl From<&State> for Uf {
+    fn from(state: &State) -> Self {
+        state.sigla
+    }
+}
+
+/// Unidade federativa informada pela sigla em `uf`. Com `resolve_names`, o nome completo do
+/// estado também é aceito, como em `Config::resolve_state_names`.
+pub(crate) fn resolve_uf(uf: &str, resolve_names: bool) -> Result<Uf, Error> {
+    match Uf::try_from(uf.trim()) {
+        Err(_) if resolve_names => {
+            Uf::from_name(uf).ok_or_else(|| Error::invalid_input(INVALID_UF_MESSAGE))
+        }
+        result => result,
+    }
+}
+
+/// Verifica se todos os `providers` são aceitos pela BrasilAPI, evitando que um provedor
+/// desconhecido pelo servidor resulte em um `400` sem explicação.
+fn validate_providers(providers: &[MunicipalitiesProvider]) -> Result<(), Error> {
+    match providers
+        .iter()
+        .find(|provider| !MunicipalitiesProvider::SUPPORTED.contains(provider))
+    {
+        Some(provider) => Err(Error::new(
+            format!("{UNSUPPORTED_PROVIDER_MESSAGE}: {}", provider.to_string()),
+            Errored::UnsupportedProvider,
+            None,
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Unidade federativa de um código IBGE de município, indicada pelos dois primeiros dígitos.
+fn municipality_uf(code: &str) -> Result<Uf, Error> {
+    let invalid = || Error::invalid_input(INVALID_MUNICIPALITY_CODE_MESSAGE);
+
+    if code.len() != 7 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    Uf::from_ibge_code(&code[..2]).ok_or_else(invalid)
+}
+
+/// Verifica se `code` é a sigla ou o código IBGE de uma unidade federativa, retornando o código
+/// a ser consultado: a sigla em maiúsculas ou o próprio código IBGE.
+fn normalize_state_code(code: &str, resolve_names: bool) -> Result<String, Error> {
+    match Uf::from_ibge_code(code.trim()) {
+        Some(_) => Ok(code.trim().to_string()),
+        None => resolve_uf(code, resolve_names)
+            .map(|uf| uf.as_str().to_string())
+            .map_err(|_| Error::invalid_input(INVALID_STATE_CODE_MESSAGE)),
+    }
+}
+
+/// Município retornado pelo endpoint `/api/ibge/municipios/v1/{uf}`.
+///
+/// A BrasilAPI retorna apenas o nome e o código IBGE de cada município: não há indicação de
+/// situação (ativo, extinto, incorporado) nem de qual provedor originou o registro. Cada
+/// provedor publica a lista vigente da sua fonte, então municípios desmembrados ou renomeados
+/// seguem o recorte daquela fonte. Para análises históricas, consulte cada provedor
+/// separadamente e compare os resultados.
+///
+/// `PartialEq` e `Eq` comparam o nome e o código IBGE exatamente, então o mesmo município
+/// retornado com grafias diferentes por dois provedores não é considerado igual, inclusive em um
+/// `HashSet`. Para identificar o mesmo município use `same_municipality`, que compara apenas o
+/// código IBGE, ou `dedup_municipalities`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Municipality {
     nome: String,
+    #[serde(deserialize_with = "deserialize_ibge_code")]
     codigo_ibge: String,
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IbgeCode {
+    Number(u64),
+    Text(String),
+}
+
+/// Desserializa o código IBGE, que o provedor `gov` pode retornar como número JSON em vez de
+/// texto.
+fn deserialize_ibge_code<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match IbgeCode::deserialize(deserializer)? {
+        IbgeCode::Number(code) => Ok(code.to_string()),
+        IbgeCode::Text(code) => Ok(code),
+    }
+}
+
 impl Municipality {
     pub fn get_name(&self) -> &str {
         &self.nome
@@ -16,19 +370,56 @@ impl Municipality {
     pub fn get_ibge_code(&self) -> &str {
         &self.codigo_ibge
     }
+
+    /// Indica se `other` é o mesmo município, comparando apenas o código IBGE.
+    pub fn same_municipality(&self, other: &Self) -> bool {
+        self.codigo_ibge == other.codigo_ibge
+    }
+
+    /// Converte para `EnglishMunicipality`, que serializa os campos com nomes em inglês.
+    pub fn to_english(&self) -> EnglishMunicipality {
+        EnglishMunicipality {
+            name: self.nome.clone(),
+            ibge_code: self.codigo_ibge.clone(),
+        }
+    }
+
+    /// Converte para `MunicipalityRecord`, uma cópia independente do município.
+    pub fn to_record(&self) -> MunicipalityRecord {
+        MunicipalityRecord {
+            code: self.codigo_ibge.clone(),
+            name: self.nome.clone(),
+        }
+    }
+}
+
+/// Projeção de um município contendo apenas o nome, usada quando o código IBGE não é necessário.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MunicipalityName {
+    nome: String,
+}
+
+impl MunicipalityName {
+    pub fn get_name(&self) -> &str {
+        &self.nome
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct State {
     id: i32,
-    sigla: String,
+    sigla: Uf,
     nome: String,
     regiao: StateRegion,
 }
 
 impl State {
     pub fn get_sigla(&self) -> &str {
-        &self.sigla
+        self.sigla.as_str()
+    }
+
+    pub fn get_uf(&self) -> Uf {
+        self.sigla
     }
 
     pub fn get_name(&self) -> &str {
@@ -38,25 +429,169 @@ impl State {
     pub fn get_region(&self) -> &StateRegion {
         &self.regiao
     }
+
+    /// Verifica se os dois estados pertencem à mesma região, comparando os ids das regiões.
+    pub fn same_region_as(&self, other: &State) -> bool {
+        self.regiao.id == other.regiao.id
+    }
+
+    /// Converte para `EnglishState`, que serializa os campos com nomes em inglês.
+    pub fn to_english(&self) -> EnglishState {
+        EnglishState {
+            id: self.id,
+            abbreviation: self.sigla.to_string(),
+            name: self.nome.clone(),
+            region: self.regiao.to_english(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct StateRegion {
     id: i32,
-    sigla: String,
+    sigla: Region,
     nome: String,
 }
 
 impl StateRegion {
     pub fn get_sigla(&self) -> &str {
+        self.sigla.as_str()
+    }
+
+    /// Região como `Region`, já convertida ao desserializar o estado.
+    pub fn as_enum(&self) -> &Region {
         &self.sigla
     }
 
     pub fn get_name(&self) -> &str {
         &self.nome
     }
+
+    /// Converte para `EnglishRegion`, que serializa os campos com nomes em inglês.
+    pub fn to_english(&self) -> EnglishRegion {
+        EnglishRegion {
+            id: self.id,
+            abbreviation: self.sigla.to_string(),
+            name: self.nome.clone(),
+        }
+    }
+}
+
+/// Região do Brasil, identificada pela sigla (campo `sigla` de `StateRegion`).
+///
+/// Siglas desconhecidas são mantidas em `Region::Other`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::Region;
+///
+/// assert_eq!(Region::from("NE"), Region::Nordeste);
+/// assert_eq!(Region::Nordeste.as_str(), "NE");
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum Region {
+    /// `N`
+    Norte,
+    /// `NE`
+    Nordeste,
+    /// `SE`
+    Sudeste,
+    /// `S`
+    Sul,
+    /// `CO`
+    CentroOeste,
+    /// Qualquer outra sigla, como retornada pela API.
+    Other(String),
+}
+
+impl Region {
+    /// Sigla da região, como retornada pela API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Region::Norte => "N",
+            Region::Nordeste => "NE",
+            Region::Sudeste => "SE",
+            Region::Sul => "S",
+            Region::CentroOeste => "CO",
+            Region::Other(sigla) => sigla,
+        }
+    }
+}
+
+impl From<&str> for Region {
+    fn from(sigla: &str) -> Self {
+        match sigla {
+            "N" => Region::Norte,
+            "NE" => Region::Nordeste,
+            "SE" => Region::Sudeste,
+            "S" => Region::Sul,
+            "CO" => Region::CentroOeste,
+            _ => Region::Other(sigla.to_string()),
+        }
+    }
+}
+
+impl From<String> for Region {
+    fn from(sigla: String) -> Self {
+        Region::from(sigla.as_str())
+    }
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> Self {
+        match region {
+            Region::Other(sigla) => sigla,
+            region => region.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Equivalente a `Municipality` com nomes de campos em inglês, para serialização.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EnglishMunicipality {
+    pub name: String,
+    pub ibge_code: String,
+}
+
+/// Código IBGE e nome de um município em uma estrutura simples, com campos públicos, para
+/// armazenamento ou envio entre tasks sem depender dos demais tipos do módulo.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct MunicipalityRecord {
+    pub code: String,
+    pub name: String,
+}
+
+impl From<&Municipality> for MunicipalityRecord {
+    fn from(municipality: &Municipality) -> Self {
+        municipality.to_record()
+    }
+}
+
+/// Equivalente a `State` com nomes de campos em inglês, para serialização.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EnglishState {
+    pub id: i32,
+    pub abbreviation: String,
+    pub name: String,
+    pub region: EnglishRegion,
+}
+
+/// Equivalente a `StateRegion` com nomes de campos em inglês, para serialização.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EnglishRegion {
+    pub id: i32,
+    pub abbreviation: String,
+    pub name: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MunicipalitiesProvider {
     DadosAbertos,
     Gov,
@@ -64,6 +599,27 @@ pub enum MunicipalitiesProvider {
 }
 
 impl MunicipalitiesProvider {
+    /// Provedores aceitos pela BrasilAPI, validados antes de cada consulta de municípios.
+    pub const SUPPORTED: [MunicipalitiesProvider; 3] = [
+        MunicipalitiesProvider::DadosAbertos,
+        MunicipalitiesProvider::Gov,
+        MunicipalitiesProvider::Wikipedia,
+    ];
+
+    /// Todos os provedores aceitos, por exemplo para listar as opções em uma interface.
+    pub fn all() -> &'static [MunicipalitiesProvider] {
+        &Self::SUPPORTED
+    }
+
+    /// Converte o nome retornado por `to_string`, por exemplo `dados-abertos-br`, sem
+    /// diferenciar maiúsculas de minúsculas. Retorna `None` para nomes desconhecidos.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .into_iter()
+            .find(|provider| provider.to_string().eq_ignore_ascii_case(name.trim()))
+    }
+
     pub fn to_string(&self) -> &str {
         match self {
             MunicipalitiesProvider::DadosAbertos => "dados-abertos-br",
@@ -73,6 +629,84 @@ impl MunicipalitiesProvider {
     }
 }
 
+bitflags::bitflags! {
+    /// Conjunto de provedores de municípios, sem repetições.
+    ///
+    /// Pode ser composto com `|`, tanto a partir das constantes quanto das variantes de
+    /// `MunicipalitiesProvider`, e convertido para o `Vec` aceito por `get_municipalities`.
+    ///
+    /// # Exemplo
+    /// ```
+    /// use brasilapi::ibge::{self, MunicipalitiesProvider, ProviderSet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let providers: ProviderSet =
+    ///        MunicipalitiesProvider::DadosAbertos | MunicipalitiesProvider::Wikipedia;
+    ///
+    ///    let municipalities = ibge::get_municipalities("SC", Some(providers.into())).await.unwrap();
+    /// }
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ProviderSet: u8 {
+        const DADOS_ABERTOS = 1;
+        const GOV = 1 << 1;
+        const WIKIPEDIA = 1 << 2;
+        const ALL = Self::DADOS_ABERTOS.bits() | Self::GOV.bits() | Self::WIKIPEDIA.bits();
+    }
+}
+
+impl ProviderSet {
+    /// Retorna os provedores do conjunto, na ordem de declaração de `MunicipalitiesProvider`.
+    pub fn providers(&self) -> Vec<MunicipalitiesProvider> {
+        MunicipalitiesProvider::SUPPORTED
+            .into_iter()
+            .filter(|provider| self.contains(ProviderSet::from(*provider)))
+            .collect()
+    }
+}
+
+impl From<MunicipalitiesProvider> for ProviderSet {
+    fn from(provider: MunicipalitiesProvider) -> Self {
+        match provider {
+            MunicipalitiesProvider::DadosAbertos => ProviderSet::DADOS_ABERTOS,
+            MunicipalitiesProvider::Gov => ProviderSet::GOV,
+            MunicipalitiesProvider::Wikipedia => ProviderSet::WIKIPEDIA,
+        }
+    }
+}
+
+impl From<ProviderSet> for Vec<MunicipalitiesProvider> {
+    fn from(providers: ProviderSet) -> Self {
+        providers.providers()
+    }
+}
+
+impl FromIterator<MunicipalitiesProvider> for ProviderSet {
+    fn from_iter<I: IntoIterator<Item = MunicipalitiesProvider>>(providers: I) -> Self {
+        providers
+            .into_iter()
+            .fold(ProviderSet::empty(), |set, provider| set | provider)
+    }
+}
+
+impl BitOr for MunicipalitiesProvider {
+    type Output = ProviderSet;
+
+    fn bitor(self, other: Self) -> ProviderSet {
+        ProviderSet::from(self) | ProviderSet::from(other)
+    }
+}
+
+impl BitOr<MunicipalitiesProvider> for ProviderSet {
+    type Output = ProviderSet;
+
+    fn bitor(self, other: MunicipalitiesProvider) -> ProviderSet {
+        self | ProviderSet::from(other)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct IbgeService {
     base_url: String,
 }
@@ -80,8 +714,67 @@ pub struct IbgeService {
 impl IbgeService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
+        }
+    }
+
+    /// Monta a URL de um endpoint, codificando os segmentos do caminho e os parâmetros.
+    ///
+    /// Caso `base_url` não seja uma URL válida, os segmentos são apenas concatenados e o erro é
+    /// retornado pelo cliente HTTP ao realizar a requisição.
+    fn endpoint_url(&self, segments: &[&str], query: &[(&str, &str)]) -> String {
+        let mut url = match reqwest::Url::parse(&self.base_url) {
+            Ok(url) if !url.cannot_be_a_base() => url,
+            _ => return format!("{}/{}", self.base_url, segments.join("/")),
+        };
+
+        url.path_segments_mut()
+            .expect("url can be a base")
+            .pop_if_empty()
+            .extend(segments);
+
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
         }
+
+        url.into()
+    }
+
+    /// Retorna a URL consultada por `get_municipalities`, sem realizar a requisição.
+    ///
+    /// O parâmetro `providers` só é incluído quando ao menos um provedor é informado.
+    pub fn municipalities_url(
+        &self,
+        uf: &str,
+        providers: Option<&[MunicipalitiesProvider]>,
+    ) -> String {
+        let providers = providers
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .collect::<ProviderSet>()
+            .providers()
+            .iter()
+            .map(|provider| provider.to_string())
+            .collect::<Vec<&str>>()
+            .join(",");
+
+        let query = match providers.is_empty() {
+            true => vec![],
+            false => vec![("providers", providers.as_str())],
+        };
+
+        self.endpoint_url(&["api", "ibge", "municipios", "v1", uf], &query)
+    }
+
+    /// Retorna a URL consultada por `get_all_states`, sem realizar a requisição.
+    pub fn states_url(&self) -> String {
+        self.endpoint_url(&["api", "ibge", "uf", "v1"], &[])
+    }
+
+    /// Retorna a URL consultada por `get_state`, sem realizar a requisição.
+    pub fn state_url(&self, code: &str) -> String {
+        self.endpoint_url(&["api", "ibge", "uf", "v1", code], &[])
     }
 
     async fn get_municipalities_request(
@@ -89,177 +782,3101 @@ impl IbgeService {
         uf: &str,
         providers: Option<Vec<MunicipalitiesProvider>>,
     ) -> Result<reqwest::Response, Error> {
-        let providers = match providers {
-            Some(providers) => providers
-                .iter()
-                .map(|provider| provider.to_string())
-                .collect::<Vec<&str>>()
-                .join(","),
-            None => "".to_string(),
-        };
+        self.municipalities_request(uf, providers, true).await
+    }
 
-        let url = format!(
-            "{}/api/ibge/municipios/v1/{}?providers={}",
-            self.base_url, uf, providers
-        );
+    /// Com `cached` igual a `false`, a requisição não passa pelo cache, como exigido pelas
+    /// leituras incrementais do corpo.
+    async fn municipalities_request(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        cached: bool,
+    ) -> Result<reqwest::Response, Error> {
+        let uf = resolve_uf(uf, client::resolves_state_names())?;
+        validate_providers(providers.as_deref().unwrap_or_default())?;
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
+        let url = self.municipalities_url(uf.as_str(), providers.as_deref());
+        match cached {
+            true => client::get(&url).await,
+            false => client::get_uncached(&url).await,
         }
     }
 
     async fn get_all_states_request(&self) -> Result<reqwest::Response, Error> {
-        let url = format!("{}/api/ibge/uf/v1", self.base_url);
+        let url = self.states_url();
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 
     async fn get_state_request(&self, code: &str) -> Result<reqwest::Response, Error> {
-        let url = format!("{}/api/ibge/uf/v1/{}", self.base_url, code);
+        let code = normalize_state_code(code, client::resolves_state_names())?;
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        let url = self.state_url(&code);
+
+        client::get(&url).await
     }
-}
 
-/// #### `get_municipalities(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
-/// Retorna uma lista de municípios de um estado.
-///
-/// ### Argumentos
-/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
-/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
-///
-/// ### Retorno
-/// * `Result<Vec<Municipality>, Error>`
-///
-/// # Exemplo
-/// ```
-/// use brasilapi::ibge;
-///
-/// #[tokio::main]
-/// async fn main() {
-///    let municipalities = ibge::get_municipalities("SP", None).await.unwrap();
-/// }
-/// ```
-pub async fn get_municipalities(
-    uf: &str,
-    providers: Option<Vec<MunicipalitiesProvider>>,
-) -> Result<Vec<Municipality>, Error> {
-    let ibge_service = IbgeService::new(BRASIL_API_URL);
+    async fn fetch_state(&self, code: &str) -> Result<State, Error> {
+        let response = self.get_state_request(code).await?;
 
-    let response = ibge_service
-        .get_municipalities_request(uf, providers)
-        .await?;
+        client::read_json(response).await
+    }
 
-    let body = response.text().await.unwrap();
-    let municipalities: Vec<Municipality> = serde_json::from_str(&body).unwrap();
+    async fn fetch_states(&self, codes: &[&str], batch: &Batch) -> Result<Vec<State>, Error> {
+        // Os códigos são copiados antes do lote: um iterador sobre `&[&str]` mantido entre os
+        // `.await` impede que o future seja `Send`.
+        let codes: Vec<String> = codes.iter().map(|code| code.to_string()).collect();
 
-    Ok(municipalities)
-}
+        batch
+            .run(codes, |code| async move {
+                self.fetch_state(&code)
+                    .await
+                    .map_err(|error| error.with_context(format!("uf {code}")))
+            })
+            .await
+            .into_iter()
+            .collect()
+    }
 
-pub async fn find_municipality_by_state_and_name(
-    uf: &str,
-    city_name: &str,
-    providers: Option<Vec<MunicipalitiesProvider>>,
-) -> Result<Option<Municipality>, Error> {
-    let municipalities = get_municipalities(uf, providers).await?;
+    pub(crate) async fn fetch_municipalities(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<Vec<Municipality>, Error> {
+        let merge = providers
+            .as_ref()
+            .is_some_and(|providers| providers.len() > 1);
 
-    let municipality = municipalities
-        .into_iter()
-        .find(|municipality| municipality.get_name().eq_ignore_ascii_case(city_name));
+        let response = self.get_municipalities_request(uf, providers).await?;
 
-    Ok(municipality)
-}
+        let municipalities: Vec<Municipality> = client::read_list(response).await?;
 
-/// #### `get_all_states()`
-/// Retorna informações de todos estados do Brasil
-///
-/// ### Retorno
-/// * `Result<Vec<State>, Error>`
-///
-/// # Exemplo
-/// ```
-/// use brasilapi::ibge;
-///
-/// #[tokio::main]
-/// async fn main() {
-///    let states = ibge::get_all_states().await.unwrap();
-/// }
-/// ```
+        match merge {
+            true => Ok(dedup_municipalities(municipalities)),
+            false => Ok(municipalities),
+        }
+    }
+
+    async fn fetch_municipalities_timed(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<(Vec<Municipality>, Timings), Error> {
+        let merge = providers
+            .as_ref()
+            .is_some_and(|providers| providers.len() > 1);
+
+        let (municipalities, timings) =
+            parse::timed_list(self.get_municipalities_request(uf, providers)).await?;
+
+        match merge {
+            true => Ok((dedup_municipalities(municipalities), timings)),
+            false => Ok((municipalities, timings)),
+        }
+    }
+
+    async fn fetch_municipalities_with_progress<F: Fn(u64)>(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        on_progress: F,
+    ) -> Result<Vec<Municipality>, Error> {
+        let merge = providers
+            .as_ref()
+            .is_some_and(|providers| providers.len() > 1);
+
+        let response = self.municipalities_request(uf, providers, false).await?;
+
+        let municipalities: Vec<Municipality> =
+            client::read_list_with_progress(response, on_progress).await?;
+
+        match merge {
+            true => Ok(dedup_municipalities(municipalities)),
+            false => Ok(municipalities),
+        }
+    }
+
+    async fn fetch_municipalities_mapped<U, F>(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        transform: F,
+    ) -> Result<Vec<U>, Error>
+    where
+        F: FnMut(Municipality) -> U,
+    {
+        let merge = providers
+            .as_ref()
+            .is_some_and(|providers| providers.len() > 1);
+
+        // `parse_list_mapped` desserializa a lista de forma estrita, então os demais modos de
+        // `Config::parse_mode` passam por `fetch_municipalities`.
+        if merge || client::parse_mode() != ParseMode::Strict {
+            let municipalities = self.fetch_municipalities(uf, providers).await?;
+
+            return Ok(municipalities.into_iter().map(transform).collect());
+        }
+
+        let response = self.get_municipalities_request(uf, providers).await?;
+
+        let body = client::read_body_bytes(response).await?;
+
+        parse::parse_list_mapped(&body, transform)
+    }
+
+    async fn fetch_municipality_pairs(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<Vec<(String, String)>, Error> {
+        let mut pairs = self
+            .fetch_municipalities_mapped(uf, providers, |municipality| {
+                (municipality.codigo_ibge, municipality.nome)
+            })
+            .await?;
+
+        pairs.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        Ok(pairs)
+    }
+
+    async fn fetch_municipalities_with_mode(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        mode: ParseMode,
+    ) -> Result<Parsed<Municipality>, Error> {
+        let merge = providers
+            .as_ref()
+            .is_some_and(|providers| providers.len() > 1);
+
+        let response = self.get_municipalities_request(uf, providers).await?;
+
+        let body = client::read_body_bytes(response).await?;
+        let parsed = parse::parse_list(&body, mode)?;
+
+        match merge {
+            true => Ok(parsed.map_items(dedup_municipalities)),
+            false => Ok(parsed),
+        }
+    }
+
+    /// Consulta cada provedor em uma requisição separada, retornando o resultado de cada um.
+    async fn fetch_municipalities_per_provider(
+        &self,
+        uf: &str,
+        providers: ProviderSet,
+    ) -> Vec<(MunicipalitiesProvider, Result<Vec<Municipality>, Error>)> {
+        let requests = providers
+            .providers()
+            .into_iter()
+            .map(|provider| async move {
+                let municipalities = self
+                    .fetch_municipalities(uf, Some(vec![provider]))
+                    .await
+                    .map_err(|error| {
+                        error.with_context(format!("provider {}", provider.to_string()))
+                    });
+
+                (provider, municipalities)
+            });
+
+        futures::future::join_all(requests).await
+    }
+
+    async fn fetch_municipalities_resilient(
+        &self,
+        uf: &str,
+        providers: ProviderSet,
+    ) -> Result<PartialMunicipalities, Error> {
+        let mut municipalities = Vec::new();
+        let mut failures = Vec::new();
+
+        for (provider, result) in self.fetch_municipalities_per_provider(uf, providers).await {
+            match result {
+                Ok(provider_municipalities) => municipalities.extend(provider_municipalities),
+                Err(error) => failures.push((provider, error)),
+            }
+        }
+
+        if !failures.is_empty() && failures.len() == providers.providers().len() {
+            return Err(failures.swap_remove(0).1);
+        }
+
+        Ok(PartialMunicipalities {
+            municipalities: dedup_municipalities(municipalities),
+            failures,
+        })
+    }
+
+    async fn count_municipalities_by_provider(
+        &self,
+        uf: &str,
+    ) -> Result<HashMap<String, usize>, Error> {
+        let mut counts = HashMap::new();
+
+        for (provider, result) in self
+            .fetch_municipalities_per_provider(uf, ProviderSet::ALL)
+            .await
+        {
+            counts.insert(provider.to_string().to_string(), result?.len());
+        }
+
+        Ok(counts)
+    }
+
+    async fn fetch_municipalities_by_provider(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<HashMap<String, Vec<Municipality>>, Error> {
+        let providers = match providers {
+            Some(providers) => providers.into_iter().collect(),
+            None => ProviderSet::ALL,
+        };
+
+        self.fetch_municipalities_per_provider(uf, providers)
+            .await
+            .into_iter()
+            .map(|(provider, result)| Ok((provider.to_string().to_string(), result?)))
+            .collect()
+    }
+
+    async fn fetch_all_municipalities(
+        &self,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        batch: &Batch,
+    ) -> Result<Vec<(String, Vec<Municipality>)>, Error> {
+        batch
+            .run(Uf::ALL, |uf| {
+                let providers = providers.clone();
+
+                async move {
+                    self.fetch_municipalities(uf.as_str(), providers)
+                        .await
+                        .map(|municipalities| (uf.to_string(), municipalities))
+                        .map_err(|error| error.with_context(format!("uf {uf}")))
+                }
+            })
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    async fn fetch_municipalities_bulk(
+        &self,
+        ufs: &[Uf],
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        batch: &Batch,
+    ) -> Result<HashMap<Uf, Vec<Municipality>>, Error> {
+        let mut ufs = ufs.to_vec();
+        ufs.sort();
+        ufs.dedup();
+
+        batch
+            .run(ufs, |uf| {
+                let providers = providers.clone();
+
+                async move {
+                    self.fetch_municipalities(uf.as_str(), providers)
+                        .await
+                        .map(|municipalities| (uf, municipalities))
+                        .map_err(|error| error.with_context(format!("uf {uf}")))
+                }
+            })
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    async fn find_code_collisions(
+        &self,
+        uf: &str,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+    ) -> Result<Vec<(String, Vec<String>)>, Error> {
+        let providers = match providers {
+            Some(providers) => providers.into_iter().collect(),
+            None => ProviderSet::ALL,
+        };
+
+        let mut municipalities = Vec::new();
+
+        for (_, result) in self.fetch_municipalities_per_provider(uf, providers).await {
+            municipalities.extend(result?);
+        }
+
+        Ok(code_collisions(municipalities))
+    }
+
+    async fn find_municipality(&self, code: &str) -> Result<Municipality, Error> {
+        let uf = municipality_uf(code)?;
+
+        self.fetch_municipalities(uf.as_str(), None)
+            .await?
+            .into_iter()
+            .find(|municipality| municipality.codigo_ibge == code)
+            .ok_or_else(|| {
+                Error::new(
+                    String::from("municipality not found"),
+                    Errored::NotFound,
+                    Some(404),
+                )
+            })
+    }
+
+    async fn fetch_municipalities_preferring(
+        &self,
+        uf: &str,
+        primary: MunicipalitiesProvider,
+        fallback: MunicipalitiesProvider,
+    ) -> Result<Vec<Municipality>, Error> {
+        match self.fetch_municipalities(uf, Some(vec![primary])).await {
+            Ok(municipalities) if !municipalities.is_empty() => Ok(municipalities),
+            Err(error)
+                if matches!(
+                    error.error,
+                    Errored::InvalidInput | Errored::UnsupportedProvider
+                ) =>
+            {
+                Err(error)
+            }
+            _ => self.fetch_municipalities(uf, Some(vec![fallback])).await,
+        }
+    }
+
+    async fn export_municipalities_ndjson<W>(
+        &self,
+        writer: &mut W,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        batch: &Batch,
+        progress: Option<&ProgressAggregator>,
+    ) -> Result<usize, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(progress) = progress {
+            progress.start(Uf::ALL.len());
+        }
+
+        let result = self
+            .write_municipalities_ndjson(writer, providers, batch, progress)
+            .await;
+
+        // Qualquer falha, inclusive na escrita, encerra a exportação, e `is_finished` precisa
+        // refleti-la.
+        if let (Err(_), Some(progress)) = (&result, progress) {
+            progress.record_failure();
+        }
+
+        result
+    }
+
+    async fn write_municipalities_ndjson<W>(
+        &self,
+        writer: &mut W,
+        providers: Option<Vec<MunicipalitiesProvider>>,
+        batch: &Batch,
+        progress: Option<&ProgressAggregator>,
+    ) -> Result<usize, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut states = batch.stream(Uf::ALL, |uf| {
+            let providers = providers.clone();
+
+            async move {
+                self.fetch_municipalities(uf.as_str(), providers)
+                    .await
+                    .map(|municipalities| (uf, municipalities))
+                    .map_err(|error| error.with_context(format!("uf {uf}")))
+            }
+        });
+
+        let write_error =
+            |error: std::io::Error| Error::new(error.to_string(), Errored::Unexpected, None);
+        let mut written = 0;
+
+        while let Some(state) = states.next().await {
+            let (uf, municipalities) = state?;
+
+            for municipality in &municipalities {
+                let mut line = serde_json::to_vec(&NdjsonRecord {
+                    uf: uf.as_str(),
+                    municipality,
+                })
+                .map_err(Error::from_json_error)?;
+                line.push(b'\n');
+
+                writer.write_all(&line).await.map_err(write_error)?;
+                written += 1;
+            }
+
+            if let Some(progress) = progress {
+                progress.record_success();
+            }
+        }
+
+        writer.flush().await.map_err(write_error)?;
+
+        Ok(written)
+    }
+
+    async fn find_provider_disagreements(&self, uf: &str) -> Result<Vec<Disagreement>, Error> {
+        let mut names: BTreeMap<(usize, String), Vec<(MunicipalitiesProvider, String)>> =
+            BTreeMap::new();
+
+        for (provider, result) in self
+            .fetch_municipalities_per_provider(uf, ProviderSet::ALL)
+            .await
+        {
+            for municipality in result? {
+                names
+                    .entry((municipality.codigo_ibge.len(), municipality.codigo_ibge))
+                    .or_default()
+                    .push((provider, municipality.nome));
+            }
+        }
+
+        let disagreements = names
+            .into_iter()
+            .filter(|(_, names)| {
+                names
+                    .iter()
+                    .any(|(_, name)| name.to_lowercase() != names[0].1.to_lowercase())
+            })
+            .map(|((_, ibge_code), names)| Disagreement { ibge_code, names })
+            .collect();
+
+        Ok(disagreements)
+    }
+}
+
+/// Município cujo nome difere entre os provedores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    ibge_code: String,
+    names: Vec<(MunicipalitiesProvider, String)>,
+}
+
+impl Disagreement {
+    pub fn get_ibge_code(&self) -> &str {
+        &self.ibge_code
+    }
+
+    /// Nome informado por cada provedor que retornou o município.
+    pub fn get_names(&self) -> &[(MunicipalitiesProvider, String)] {
+        &self.names
+    }
+}
+
+/// Resultado de uma consulta a vários provedores em que parte deles pode ter falhado.
+#[derive(Debug, Clone)]
+pub struct PartialMunicipalities {
+    municipalities: Vec<Municipality>,
+    failures: Vec<(MunicipalitiesProvider, Error)>,
+}
+
+impl PartialMunicipalities {
+    /// Municípios retornados pelos provedores que responderam, sem repetições.
+    pub fn get_municipalities(&self) -> &[Municipality] {
+        &self.municipalities
+    }
+
+    /// Provedores que falharam e o erro retornado por cada um.
+    pub fn get_failures(&self) -> &[(MunicipalitiesProvider, Error)] {
+        &self.failures
+    }
+
+    /// Indica se todos os provedores responderam com sucesso.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn into_municipalities(self) -> Vec<Municipality> {
+        self.municipalities
+    }
+}
+
+/// Um estado e a quantidade de municípios que ele possui.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StateSummary {
+    pub state: State,
+    pub municipality_count: usize,
+}
+
+impl IbgeService {
+    async fn summarize_states(&self, batch: &Batch) -> Result<Vec<StateSummary>, Error> {
+        let mut states = self.fetch_all().await?;
+        states.sort_by_key(|state| state.id);
+
+        batch
+            .run(states, |state| async move {
+                let municipality_count = self
+                    .fetch_municipalities_mapped(state.sigla.as_str(), None, |_| ())
+                    .await
+                    .map_err(|error| error.with_context(format!("uf {}", state.sigla)))?
+                    .len();
+
+                Ok(StateSummary {
+                    state,
+                    municipality_count,
+                })
+            })
+            .await
+            .into_iter()
+            .collect()
+    }
+}
+
+impl ListEndpoint<State> for IbgeService {
+    async fn fetch_all(&self) -> Result<Vec<State>, Error> {
+        let response = self.get_all_states_request().await?;
+
+        let states: Vec<State> = client::read_list(response).await?;
+
+        Ok(states)
+    }
+}
+
+/// #### `get_municipalities(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna uma lista de municípios de um estado.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<Vec<Municipality>, Error>`
+///
+/// Uma UF desconhecida retorna um erro `Errored::InvalidInput` com a mensagem
+/// `INVALID_UF_MESSAGE`, sem que a API seja consultada. Com `Config::resolve_state_names`, o
+/// nome completo do estado também é aceito.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipalities = ibge::get_municipalities("SP", None).await.unwrap();
+/// }
+/// ```
+pub async fn get_municipalities(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<Municipality>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_municipalities(uf, providers).await
+}
+
+/// #### `get_municipalities_by_uf(uf: Uf, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Igual a `get_municipalities`, recebendo a unidade federativa já validada.
+///
+/// ### Argumentos
+/// * `uf:Uf` => Unidade federativa.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<Vec<Municipality>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, Uf};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let state = ibge::get_state("SP").await.unwrap();
+///    let municipalities = ibge::get_municipalities_by_uf(Uf::from(&state), None).await.unwrap();
+/// }
+/// ```
+pub async fn get_municipalities_by_uf(
+    uf: Uf,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<Municipality>, Error> {
+    get_municipalities(uf.as_str(), providers).await
+}
+
+/// #### `get_municipalities_mapped(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>, transform: F)`
+/// Retorna os municípios de um estado já convertidos por `transform`.
+///
+/// Cada município é repassado a `transform` assim que é desserializado, sem que a lista de
+/// `Municipality` seja criada. Com mais de um provedor, os municípios precisam ser combinados
+/// antes da conversão, e com `Config::parse_mode` diferente de `ParseMode::Strict` os elementos
+/// inválidos precisam ser descartados, então a lista intermediária é criada nesses casos.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+/// * `transform:F` => Função aplicada a cada município.
+///
+/// ### Retorno
+/// * `Result<Vec<U>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let pairs = ibge::get_municipalities_mapped("SP", None, |m| {
+///        (m.get_ibge_code().to_owned(), m.get_name().to_owned())
+///    })
+///    .await
+///    .unwrap();
+/// }
+/// ```
+pub async fn get_municipalities_mapped<U, F>(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+    transform: F,
+) -> Result<Vec<U>, Error>
+where
+    F: FnMut(Municipality) -> U,
+{
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_mapped(uf, providers, transform)
+        .await
+}
+
+/// #### `get_municipality_pairs(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna os municípios de um estado como pares `(codigo_ibge, nome)`, ordenados pelo código.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<Vec<(String, String)>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let pairs = ibge::get_municipality_pairs("SP", None).await.unwrap();
+/// }
+/// ```
+pub async fn get_municipality_pairs(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<(String, String)>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_municipality_pairs(uf, providers).await
+}
+
+/// #### `get_municipalities_with_mode(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>, mode: ParseMode)`
+/// Retorna uma lista de municípios de um estado, escolhendo como tratar elementos inválidos.
+///
+/// Em `ParseMode::Strict` qualquer elemento inválido resulta em um erro `Errored::Deserialization`.
+/// Em `ParseMode::Lenient` cada elemento é desserializado separadamente, e os inválidos são
+/// ignorados e registrados em `Parsed::get_failures`.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+/// * `mode:ParseMode` => Modo de desserialização.
+///
+/// ### Retorno
+/// * `Result<Parsed<Municipality>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{ibge, parse::ParseMode};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let parsed = ibge::get_municipalities_with_mode("SP", None, ParseMode::Lenient).await.unwrap();
+///
+///    for failure in parsed.get_failures() {
+///        println!("elemento {} ignorado: {}", failure.get_index(), failure.get_message());
+///    }
+/// }
+/// ```
+pub async fn get_municipalities_with_mode(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+    mode: ParseMode,
+) -> Result<Parsed<Municipality>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_with_mode(uf, providers, mode)
+        .await
+}
+
+/// #### `get_municipalities_timed(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Igual a `get_municipalities`, mas também retorna o tempo gasto na requisição e na
+/// desserialização da resposta.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<(Vec<Municipality>, Timings), Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (municipalities, timings) = ibge::get_municipalities_timed("SP", None).await.unwrap();
+///
+///    println!("{} municípios", municipalities.len());
+///    println!("rede: {:?}, desserialização: {:?}", timings.get_network(), timings.get_parse());
+/// }
+/// ```
+pub async fn get_municipalities_timed(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<(Vec<Municipality>, Timings), Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_municipalities_timed(uf, providers).await
+}
+
+/// #### `get_municipalities_with_progress(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>, on_progress: F)`
+/// Igual a `get_municipalities`, chamando `on_progress` com o total de bytes recebidos conforme
+/// a resposta é baixada, por exemplo para exibir uma barra de progresso. A requisição não passa
+/// pelo cache de `cache::enable`, que entregaria a resposta já baixada.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+/// * `on_progress:F` => Função chamada com o total de bytes recebidos até o momento.
+///
+/// ### Retorno
+/// * `Result<Vec<Municipality>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipalities = ibge::get_municipalities_with_progress("MG", None, |received| {
+///        eprint!("\r{} KiB", received / 1024);
+///    })
+///    .await
+///    .unwrap();
+/// }
+/// ```
+pub async fn get_municipalities_with_progress<F>(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+    on_progress: F,
+) -> Result<Vec<Municipality>, Error>
+where
+    F: Fn(u64) + Send,
+{
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_with_progress(uf, providers, on_progress)
+        .await
+}
+
+/// #### `get_municipality(code: &str)`
+/// Busca um município pelo código IBGE de 7 dígitos, por exemplo `3550308` para São Paulo.
+///
+/// A BrasilAPI não possui um endpoint de consulta por código, então os municípios do estado
+/// indicado pelos dois primeiros dígitos do código são consultados e filtrados.
+///
+/// ### Argumento
+/// * `code:&str` => Código IBGE do município.
+///
+/// ### Retorno
+/// * `Result<Municipality, Error>`
+///
+/// Um código que não possua 7 dígitos ou não comece com o código de um estado retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_MUNICIPALITY_CODE_MESSAGE`, sem que a API
+/// seja consultada. Um código inexistente no estado retorna um erro `Errored::NotFound`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipality = ibge::get_municipality("3550308").await.unwrap();
+/// }
+/// ```
+pub async fn get_municipality(code: &str) -> Result<Municipality, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.find_municipality(code).await
+}
+
+/// #### `get_municipalities_preferring(uf: &str, primary: MunicipalitiesProvider, fallback: MunicipalitiesProvider)`
+/// Retorna os municípios de um estado segundo o provedor `primary`, consultando `fallback`
+/// quando `primary` não retorna nenhum município ou falha.
+///
+/// Qualquer erro de `primary`, inclusive uma resposta que não pode ser desserializada, leva à
+/// consulta de `fallback`. Apenas erros de validação feitos antes da consulta, como uma UF
+/// desconhecida (`Errored::InvalidInput`) ou um provedor não aceito
+/// (`Errored::UnsupportedProvider`), são retornados diretamente.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `primary:MunicipalitiesProvider` => Provedor consultado primeiro.
+/// * `fallback:MunicipalitiesProvider` => Provedor consultado caso `primary` falhe.
+///
+/// ### Retorno
+/// * `Result<Vec<Municipality>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, MunicipalitiesProvider};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipalities = ibge::get_municipalities_preferring(
+///        "SP",
+///        MunicipalitiesProvider::Gov,
+///        MunicipalitiesProvider::DadosAbertos,
+///    )
+///    .await
+///    .unwrap();
+/// }
+/// ```
+pub async fn get_municipalities_preferring(
+    uf: &str,
+    primary: MunicipalitiesProvider,
+    fallback: MunicipalitiesProvider,
+) -> Result<Vec<Municipality>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_preferring(uf, primary, fallback)
+        .await
+}
+
+/// #### `get_municipalities_resilient(uf: &str, providers: ProviderSet)`
+/// Retorna os municípios de um estado consultando cada provedor separadamente.
+///
+/// Diferente de `get_municipalities`, a falha de um provedor não interrompe a consulta: os
+/// municípios dos provedores que responderam são combinados (sem repetições) e os provedores que
+/// falharam são informados em `PartialMunicipalities::get_failures`. Um erro só é retornado
+/// quando todos os provedores falham.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:ProviderSet` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<PartialMunicipalities, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, ProviderSet};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let result = ibge::get_municipalities_resilient("SC", ProviderSet::ALL).await.unwrap();
+///
+///    for (provider, error) in result.get_failures() {
+///        println!("{} falhou: {}", provider.to_string(), error.message);
+///    }
+/// }
+/// ```
+pub async fn get_municipalities_resilient(
+    uf: &str,
+    providers: ProviderSet,
+) -> Result<PartialMunicipalities, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_resilient(uf, providers)
+        .await
+}
+
+/// Pontua um nome de município: nomes acentuados e com capitalização de nome próprio são
+/// preferidos a grafias sem acento ou inteiramente em maiúsculo.
+fn name_quality(name: &str) -> (bool, bool) {
+    (
+        normalize::has_accents(name),
+        normalize::title_case(name) == name,
+    )
+}
+
+/// #### `dedup_municipalities(municipalities: Vec<Municipality>)`
+/// Remove municípios repetidos, usando o código IBGE como identificador.
+///
+/// Quando provedores diferentes retornam o mesmo município com grafias distintas, é mantido o
+/// nome acentuado e com capitalização de nome próprio. A ordem da primeira ocorrência de cada
+/// código é preservada.
+///
+/// ### Argumento
+/// * `municipalities:Vec<Municipality>` => Municípios, possivelmente de vários provedores.
+///
+/// ### Retorno
+/// * `Vec<Municipality>`
+pub fn dedup_municipalities(municipalities: Vec<Municipality>) -> Vec<Municipality> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut deduped: Vec<Municipality> = Vec::with_capacity(municipalities.len());
+
+    for municipality in municipalities {
+        match positions.get(&municipality.codigo_ibge) {
+            Some(&position) => {
+                let current = &mut deduped[position];
+
+                if name_quality(&municipality.nome) > name_quality(&current.nome) {
+                    current.nome = municipality.nome;
+                }
+            }
+            None => {
+                positions.insert(municipality.codigo_ibge.clone(), deduped.len());
+                deduped.push(municipality);
+            }
+        }
+    }
+
+    deduped
+}
+
+/// #### `provider_disagreements(uf: &str)`
+/// Retorna os municípios de um estado cujo nome difere entre os provedores.
+///
+/// Cada provedor é consultado separadamente e os resultados são combinados pelo código IBGE.
+/// Diferenças apenas de capitalização são ignoradas, mas diferenças de acentuação ou grafia são
+/// reportadas. Municípios ausentes em algum provedor não são considerados divergências; use
+/// `municipality_counts_by_provider` para identificar falhas de cobertura. Um erro é retornado
+/// se qualquer um dos provedores falhar.
+///
+/// ### Argumento
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+///
+/// ### Retorno
+/// * `Result<Vec<Disagreement>, Error>` => Divergências ordenadas pelo código IBGE.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for disagreement in ibge::provider_disagreements("RR").await.unwrap() {
+///        for (provider, name) in disagreement.get_names() {
+///            println!("{} {}: {}", disagreement.get_ibge_code(), provider.to_string(), name);
+///        }
+///    }
+/// }
+/// ```
+pub async fn provider_disagreements(uf: &str) -> Result<Vec<Disagreement>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.find_provider_disagreements(uf).await
+}
+
+/// #### `municipality_counts_by_provider(uf: &str)`
+/// Retorna quantos municípios cada provedor informa para um estado.
+///
+/// Cada provedor é consultado separadamente, o que permite identificar provedores com cobertura
+/// incompleta. Um erro é retornado se qualquer um dos provedores falhar.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+///
+/// ### Retorno
+/// * `Result<HashMap<String, usize>, Error>` => Quantidade de municípios indexada pelo nome do provedor.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let counts = ibge::municipality_counts_by_provider("RR").await.unwrap();
+///
+///    for (provider, count) in counts {
+///        println!("{}: {}", provider, count);
+///    }
+/// }
+/// ```
+pub async fn municipality_counts_by_provider(uf: &str) -> Result<HashMap<String, usize>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.count_municipalities_by_provider(uf).await
+}
+
+/// #### `get_municipalities_per_provider(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna a lista de municípios de cada provedor separadamente, sem combiná-las.
+///
+/// Cada provedor é consultado em uma requisição própria, mantendo a origem de cada município,
+/// por exemplo para comparar os provedores lado a lado. Um erro é retornado se qualquer um dos
+/// provedores falhar.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores consultados, por padrão todos.
+///
+/// ### Retorno
+/// * `Result<HashMap<String, Vec<Municipality>>, Error>` => Municípios indexados pelo nome do
+///   provedor.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let per_provider = ibge::get_municipalities_per_provider("RR", None).await.unwrap();
+///
+///    for (provider, municipalities) in per_provider {
+///        println!("{}: {}", provider, municipalities.len());
+///    }
+/// }
+/// ```
+pub async fn get_municipalities_per_provider(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<HashMap<String, Vec<Municipality>>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_by_provider(uf, providers)
+        .await
+}
+
+/// #### `get_municipality_names(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna apenas os nomes dos municípios de um estado.
+///
+/// Os demais campos da resposta são descartados durante a desserialização, evitando alocações
+/// desnecessárias quando apenas o nome é utilizado (por exemplo, para popular um dropdown).
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<Vec<String>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let names = ibge::get_municipality_names("SP", None).await.unwrap();
+/// }
+/// ```
+pub async fn get_municipality_names(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<String>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    let response = ibge_service
+        .get_municipalities_request(uf, providers)
+        .await?;
+
+    let names: Vec<MunicipalityName> = client::read_list(response).await?;
+
+    Ok(names.into_iter().map(|name| name.nome).collect())
+}
+
+/// Municípios de `uf` cujo código IBGE está vazio, não possui 7 dígitos ou pertence a outro
+/// estado.
+fn with_missing_codes(municipalities: Vec<Municipality>, uf: Uf) -> Vec<Municipality> {
+    municipalities
+        .into_iter()
+        .filter(|municipality| municipality_uf(municipality.codigo_ibge.trim()).ok() != Some(uf))
+        .collect()
+}
+
+/// #### `municipalities_missing_codes(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Busca os municípios de um estado e retorna aqueles cujo código IBGE está ausente ou mal
+/// formado, para identificar falhas nos dados dos provedores.
+///
+/// Um código é considerado mal formado quando não possui 7 dígitos ou quando os dois primeiros
+/// dígitos não correspondem ao estado consultado.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<Vec<Municipality>, Error>` => Municípios com problemas no código, na ordem da API.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let missing = ibge::municipalities_missing_codes("SP", None).await.unwrap();
+///
+///    for municipality in missing {
+///        println!("{}: {:?}", municipality.get_name(), municipality.get_ibge_code());
+///    }
+/// }
+/// ```
+pub async fn municipalities_missing_codes(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<Municipality>, Error> {
+    let municipalities = get_municipalities(uf, providers).await?;
+
+    Ok(with_missing_codes(
+        municipalities,
+        resolve_uf(uf, client::resolves_state_names())?,
+    ))
+}
+
+/// Códigos IBGE de `municipalities` associados a mais de um nome, ignorando diferenças de
+/// acentuação e capitalização, ordenados pelo código. Os nomes aparecem na ordem da primeira ocorrência.
+fn code_collisions(municipalities: Vec<Municipality>) -> Vec<(String, Vec<String>)> {
+    let mut names: BTreeMap<(usize, String), Vec<String>> = BTreeMap::new();
+
+    for municipality in municipalities {
+        let code = municipality.codigo_ibge.trim().to_string();
+        let code_names = names.entry((code.len(), code)).or_default();
+
+        if !code_names
+            .iter()
+            .any(|name| index_key(name) == index_key(&municipality.nome))
+        {
+            code_names.push(municipality.nome);
+        }
+    }
+
+    names
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|((_, code), names)| (code, names))
+        .collect()
+}
+
+/// #### `find_code_collisions(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna os códigos IBGE de um estado associados a mais de um nome, o que indica um erro nos
+/// dados: cada código deve identificar um único município.
+///
+/// Cada provedor é consultado separadamente e os nomes de todos eles são comparados, inclusive
+/// códigos repetidos em um mesmo provedor. Diferenças apenas de acentuação ou capitalização,
+/// reportadas por `provider_disagreements`, não são consideradas colisões. Um erro é retornado se
+/// qualquer um dos provedores falhar.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores consultados, por padrão todos.
+///
+/// ### Retorno
+/// * `Result<Vec<(String, Vec<String>)>, Error>` => Códigos e seus nomes distintos, ordenados
+///   pelo código.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for (code, names) in ibge::find_code_collisions("RR", None).await.unwrap() {
+///        println!("{}: {}", code, names.join(", "));
+///    }
+/// }
+/// ```
+pub async fn find_code_collisions(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Vec<(String, Vec<String>)>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.find_code_collisions(uf, providers).await
+}
+
+/// Linha do NDJSON exportado por `export_all_municipalities_ndjson`.
+#[derive(Serialize)]
+struct NdjsonRecord<'a> {
+    uf: &'a str,
+    #[serde(flatten)]
+    municipality: &'a Municipality,
+}
+
+/// #### `export_all_municipalities_ndjson(writer: &mut W, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Escreve os municípios de todas as unidades federativas em `writer`, um objeto JSON por linha.
+///
+/// Os estados são consultados em paralelo, e os municípios de cada estado são escritos assim que
+/// ele e os anteriores ficam prontos, na ordem dos códigos IBGE dos estados. Dessa forma apenas
+/// os estados em andamento ficam em memória. Cada linha contém a sigla do estado (`uf`), o
+/// `nome` e o `codigo_ibge` do município.
+///
+/// ### Argumentos
+/// * `writer:&mut W` => Destino das linhas, por exemplo um `tokio::fs::File`.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<usize, Error>` => Quantidade de municípios escritos.
+///
+/// # Exemplo
+/// ```no_run
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let mut output: Vec<u8> = Vec::new();
+///    let written = ibge::export_all_municipalities_ndjson(&mut output, None).await.unwrap();
+/// }
+/// ```
+pub async fn export_all_municipalities_ndjson<W>(
+    writer: &mut W,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<usize, Error>
+where
+    W: AsyncWrite + Unpin,
+{
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .export_municipalities_ndjson(writer, providers, &Batch::new(), None)
+        .await
+}
+
+/// #### `export_all_municipalities_ndjson_with_progress(writer: &mut W, providers: Option<Vec<MunicipalitiesProvider>>, progress: &ProgressAggregator)`
+/// Igual a `export_all_municipalities_ndjson`, atualizando `progress` a cada estado exportado.
+///
+/// O total do agregador é a quantidade de unidades federativas, e cada estado conta como
+/// concluído assim que seus municípios são escritos. Uma falha é registrada antes de o erro ser
+/// retornado.
+///
+/// ### Argumentos
+/// * `writer:&mut W` => Destino das linhas, por exemplo um `tokio::fs::File`.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+/// * `progress:&ProgressAggregator` => Agregador alimentado durante a exportação.
+///
+/// ### Retorno
+/// * `Result<usize, Error>` => Quantidade de municípios escritos.
+pub async fn export_all_municipalities_ndjson_with_progress<W>(
+    writer: &mut W,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+    progress: &ProgressAggregator,
+) -> Result<usize, Error>
+where
+    W: AsyncWrite + Unpin,
+{
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .export_municipalities_ndjson(writer, providers, &Batch::new(), Some(progress))
+        .await
+}
+
+/// Municípios de um estado com a data da consulta, para gravar em disco um retrato dos dados
+/// que possa ser lido novamente ou comparado com consultas futuras.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StateExport {
+    /// Sigla da unidade federativa.
+    pub uf: String,
+    /// Data e hora da consulta em UTC, no formato RFC 3339, por exemplo `2024-01-31T12:00:00Z`.
+    pub fetched_at: String,
+    /// Municípios ordenados pelo código IBGE.
+    pub municipalities: Vec<Municipality>,
+}
+
+/// Data e hora de `time` em UTC no formato RFC 3339, com precisão de segundos.
+fn rfc3339_utc(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+
+    // Conversão de dias desde 1970-01-01 para o calendário gregoriano, contando eras de 400 anos
+    // a partir de 0000-03-01.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+/// Monta o `StateExport` de `uf`, ordenando os municípios pelo código IBGE.
+fn state_export(
+    uf: Uf,
+    mut municipalities: Vec<Municipality>,
+    fetched_at: SystemTime,
+) -> StateExport {
+    municipalities.sort_by(|a, b| {
+        (a.codigo_ibge.len(), &a.codigo_ibge).cmp(&(b.codigo_ibge.len(), &b.codigo_ibge))
+    });
+
+    StateExport {
+        uf: uf.as_str().to_string(),
+        fetched_at: rfc3339_utc(fetched_at),
+        municipalities,
+    }
+}
+
+/// #### `export_state(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Busca os municípios de um estado e os retorna em um `StateExport`, com a data da consulta.
+///
+/// Os municípios são ordenados pelo código IBGE, de forma que duas exportações dos mesmos dados
+/// resultam no mesmo JSON, exceto pela data.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<StateExport, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let export = ibge::export_state("SC", None).await.unwrap();
+///
+///    let json = serde_json::to_string(&export).unwrap();
+///
+///    println!("{} municípios em {}", export.municipalities.len(), export.fetched_at);
+/// }
+/// ```
+pub async fn export_state(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<StateExport, Error> {
+    let uf = resolve_uf(uf, client::resolves_state_names())?;
+    let municipalities = get_municipalities(uf.as_str(), providers).await?;
+
+    Ok(state_export(uf, municipalities, SystemTime::now()))
+}
+
+/// #### `state_summaries(batch: &Batch)`
+/// Retorna todos os estados com a quantidade de municípios de cada um.
+///
+/// Os estados são consultados uma única vez e os municípios de cada estado são contados em
+/// seguida, uma requisição por estado, respeitando a concorrência e a janela de espalhamento
+/// configuradas em `batch`. Os municípios são descartados durante a desserialização, sem que as
+/// listas sejam mantidas em memória. O resultado segue a ordem dos códigos IBGE dos estados.
+///
+/// ### Argumentos
+/// * `batch:&Batch` => Configuração das requisições em lote.
+///
+/// ### Retorno
+/// * `Result<Vec<StateSummary>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{batch::Batch, ibge};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let summaries = ibge::state_summaries(&Batch::new()).await.unwrap();
+///
+///    for summary in summaries {
+///        println!("{}: {}", summary.state.get_name(), summary.municipality_count);
+///    }
+/// }
+/// ```
+pub async fn state_summaries(batch: &Batch) -> Result<Vec<StateSummary>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.summarize_states(batch).await
+}
+
+/// #### `get_all_municipalities(providers: Option<Vec<MunicipalitiesProvider>>, batch: &Batch)`
+/// Retorna os municípios de todas as unidades federativas.
+///
+/// Uma requisição é feita por estado, respeitando a concorrência e a janela de espalhamento
+/// configuradas em `batch`. O resultado segue a ordem dos códigos IBGE dos estados.
+///
+/// ### Argumentos
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+/// * `batch:&Batch` => Configuração das requisições em lote.
+///
+/// ### Retorno
+/// * `Result<Vec<(String, Vec<Municipality>)>, Error>` => Pares (sigla, municípios).
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{batch::Batch, ibge};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let batch = Batch::new().concurrency(5).spread(Duration::from_secs(1));
+///    let country = ibge::get_all_municipalities(None, &batch).await.unwrap();
+/// }
+/// ```
+pub async fn get_all_municipalities(
+    providers: Option<Vec<MunicipalitiesProvider>>,
+    batch: &Batch,
+) -> Result<Vec<(String, Vec<Municipality>)>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_all_municipalities(providers, batch)
+        .await
+}
+
+/// #### `get_municipalities_bulk(ufs: &[Uf], providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Retorna os municípios de vários estados, indexados pelo estado.
+///
+/// A BrasilAPI não possui um endpoint para consultar vários estados em uma única requisição,
+/// então uma requisição é feita por estado, de forma concorrente com a configuração padrão de
+/// `Batch`. Estados repetidos em `ufs` são consultados uma única vez. Um erro é retornado se a
+/// consulta de qualquer um dos estados falhar.
+///
+/// ### Argumentos
+/// * `ufs:&[Uf]` => Unidades federativas consultadas.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<HashMap<Uf, Vec<Municipality>>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, Uf};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipalities = ibge::get_municipalities_bulk(&[Uf::Pr, Uf::Sc, Uf::Rs], None)
+///        .await
+///        .unwrap();
+///
+///    println!("{}", municipalities[&Uf::Sc].len());
+/// }
+/// ```
+pub async fn get_municipalities_bulk(
+    ufs: &[Uf],
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<HashMap<Uf, Vec<Municipality>>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service
+        .fetch_municipalities_bulk(ufs, providers, &Batch::default())
+        .await
+}
+
+/// #### `sorted_by_code(municipalities: Vec<Municipality>)`
+/// Ordena municípios pelo código IBGE, garantindo uma ordem determinística independente da
+/// ordem retornada pelos provedores.
+///
+/// ### Argumento
+/// * `municipalities:Vec<Municipality>` => Municípios a serem ordenados.
+///
+/// ### Retorno
+/// * `Vec<Municipality>`
+pub fn sorted_by_code(mut municipalities: Vec<Municipality>) -> Vec<Municipality> {
+    municipalities.sort_by(|a, b| {
+        (a.codigo_ibge.len(), &a.codigo_ibge).cmp(&(b.codigo_ibge.len(), &b.codigo_ibge))
+    });
+
+    municipalities
+}
+
+/// #### `merge_sorted(lists: Vec<Vec<Municipality>>)`
+/// Combina listas de municípios, por exemplo de vários estados, em uma única lista sem
+/// repetições e ordenada pelo nome.
+///
+/// As repetições são removidas como em `dedup_municipalities`, e a ordenação ignora acentos e
+/// diferenças entre maiúsculas e minúsculas. Nomes iguais são ordenados pelo código IBGE.
+///
+/// ### Argumento
+/// * `lists:Vec<Vec<Municipality>>` => Listas de municípios.
+///
+/// ### Retorno
+/// * `Vec<Municipality>`
+pub fn merge_sorted(lists: Vec<Vec<Municipality>>) -> Vec<Municipality> {
+    let mut merged = dedup_municipalities(lists.into_iter().flatten().collect());
+
+    merged.sort_by_cached_key(|municipality| {
+        (
+            index_key(&municipality.nome),
+            municipality.codigo_ibge.len(),
+            municipality.codigo_ibge.clone(),
+        )
+    });
+
+    merged
+}
+
+/// Lista de municípios vigente em um ano de referência.
+///
+/// A BrasilAPI retorna apenas a lista atual, então os recortes de anos anteriores devem ser
+/// obtidos pelo usuário, por exemplo a partir de consultas arquivadas ou dos dados do IBGE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MunicipalitySnapshot {
+    year: u16,
+    municipalities: Vec<Municipality>,
+}
+
+impl MunicipalitySnapshot {
+    pub fn new(year: u16, municipalities: Vec<Municipality>) -> Self {
+        Self {
+            year,
+            municipalities,
+        }
+    }
+
+    pub fn get_year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn get_municipalities(&self) -> &[Municipality] {
+        &self.municipalities
+    }
+}
+
+/// Alterações administrativas entre dois recortes de municípios.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MunicipalityChanges {
+    from_year: u16,
+    to_year: u16,
+    created: Vec<Municipality>,
+    dissolved: Vec<Municipality>,
+    renamed: Vec<(Municipality, Municipality)>,
+}
+
+impl MunicipalityChanges {
+    pub fn get_from_year(&self) -> u16 {
+        self.from_year
+    }
+
+    pub fn get_to_year(&self) -> u16 {
+        self.to_year
+    }
+
+    /// Municípios presentes apenas no recorte mais recente.
+    pub fn get_created(&self) -> &[Municipality] {
+        &self.created
+    }
+
+    /// Municípios presentes apenas no recorte mais antigo.
+    pub fn get_dissolved(&self) -> &[Municipality] {
+        &self.dissolved
+    }
+
+    /// Pares (antes, depois) de municípios cujo nome mudou, mantendo o código IBGE.
+    pub fn get_renamed(&self) -> &[(Municipality, Municipality)] {
+        &self.renamed
+    }
+
+    /// Indica se não houve nenhuma alteração entre os recortes.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.dissolved.is_empty() && self.renamed.is_empty()
+    }
+}
+
+/// #### `municipality_changes(from: &MunicipalitySnapshot, to: &MunicipalitySnapshot)`
+/// Compara dois recortes de municípios usando o código IBGE como identificador.
+///
+/// Diferenças apenas de acentuação ou de capitalização entre provedores não são consideradas
+/// mudanças de nome. Os resultados são ordenados pelo código IBGE.
+///
+/// ### Argumentos
+/// * `from:&MunicipalitySnapshot` => Recorte mais antigo.
+/// * `to:&MunicipalitySnapshot` => Recorte mais recente.
+///
+/// ### Retorno
+/// * `MunicipalityChanges`
+pub fn municipality_changes(
+    from: &MunicipalitySnapshot,
+    to: &MunicipalitySnapshot,
+) -> MunicipalityChanges {
+    let before: HashMap<&str, &Municipality> = from
+        .municipalities
+        .iter()
+        .map(|municipality| (municipality.codigo_ibge.as_str(), municipality))
+        .collect();
+    let after: HashMap<&str, &Municipality> = to
+        .municipalities
+        .iter()
+        .map(|municipality| (municipality.codigo_ibge.as_str(), municipality))
+        .collect();
+
+    let comparable = |name: &str| normalize::fold_accents(name).to_lowercase();
+
+    let created = after
+        .iter()
+        .filter(|(code, _)| !before.contains_key(*code))
+        .map(|(_, municipality)| (*municipality).clone())
+        .collect();
+    let dissolved = before
+        .iter()
+        .filter(|(code, _)| !after.contains_key(*code))
+        .map(|(_, municipality)| (*municipality).clone())
+        .collect();
+
+    let mut renamed: Vec<(Municipality, Municipality)> = before
+        .iter()
+        .filter_map(|(code, old)| {
+            let new = after.get(code)?;
+
+            match comparable(&old.nome) == comparable(&new.nome) {
+                true => None,
+                false => Some(((*old).clone(), (*new).clone())),
+            }
+        })
+        .collect();
+    renamed.sort_by(|(a, _), (b, _)| {
+        (a.codigo_ibge.len(), &a.codigo_ibge).cmp(&(b.codigo_ibge.len(), &b.codigo_ibge))
+    });
+
+    MunicipalityChanges {
+        from_year: from.year,
+        to_year: to.year,
+        created: sorted_by_code(created),
+        dissolved: sorted_by_code(dissolved),
+        renamed,
+    }
+}
+
+/// Chave usada para municípios cujo nome não começa com uma letra.
+pub const NON_LETTER_INITIAL: char = '#';
+
+/// #### `group_municipalities_by_initial(municipalities: &[Municipality])`
+/// Agrupa municípios pela inicial do nome, sem acento e em maiúsculo, por exemplo `Á` em `A`.
+///
+/// Nomes que não começam com uma letra são agrupados em `NON_LETTER_INITIAL`.
+///
+/// ### Argumento
+/// * `municipalities:&[Municipality]` => Municípios a serem agrupados.
+///
+/// ### Retorno
+/// * `BTreeMap<char, Vec<&Municipality>>`
+pub fn group_municipalities_by_initial(
+    municipalities: &[Municipality],
+) -> BTreeMap<char, Vec<&Municipality>> {
+    let mut groups: BTreeMap<char, Vec<&Municipality>> = BTreeMap::new();
+
+    for municipality in municipalities {
+        let initial = normalize::fold_accents(municipality.get_name())
+            .chars()
+            .find(|c| !c.is_whitespace())
+            .filter(|c| c.is_alphabetic())
+            .map(|c| c.to_uppercase().next().unwrap_or(c))
+            .unwrap_or(NON_LETTER_INITIAL);
+
+        groups.entry(initial).or_default().push(municipality);
+    }
+
+    groups
+}
+
+/// #### `filter_municipalities(list: &[Municipality], re: &regex::Regex)`
+/// Filtra os municípios cujo nome, sem acentos, corresponde à expressão regular `re`.
+///
+/// Disponível com a feature `regex`. Como a comparação é feita sobre o nome sem acentos, a
+/// expressão também deve ser escrita sem eles, por exemplo `Sao` para `São`. A caixa das letras
+/// é mantida; use `(?i)` para ignorá-la.
+///
+/// ### Argumentos
+/// * `list:&[Municipality]` => Municípios, por exemplo o retorno de `get_municipalities`.
+/// * `re:&regex::Regex` => Expressão regular aplicada aos nomes.
+///
+/// ### Retorno
+/// * `Vec<&Municipality>` => Municípios encontrados, na ordem de `list`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+/// use regex::Regex;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let municipalities = ibge::get_municipalities("SC", None).await.unwrap();
+///    let santas = ibge::filter_municipalities(&municipalities, &Regex::new("(?i)santa").unwrap());
+/// }
+/// ```
+#[cfg(feature = "regex")]
+pub fn filter_municipalities<'a>(
+    list: &'a [Municipality],
+    re: &regex::Regex,
+) -> Vec<&'a Municipality> {
+    list.iter()
+        .filter(|municipality| re.is_match(&normalize::fold_accents(&municipality.nome)))
+        .collect()
+}
+
+/// Chave usada no índice de municípios: nome sem acentos e em minúsculo.
+fn index_key(name: &str) -> String {
+    normalize::fold_accents(name).to_lowercase()
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    municipalities: Vec<usize>,
+}
+
+/// Índice de municípios para busca por prefixo do nome, como em campos de autocompletar.
+///
+/// A busca ignora acentos e diferenças entre maiúsculas e minúsculas, então `sao` encontra
+/// `São Paulo` e `SÃO PAULO`. O índice é montado uma única vez e cada busca percorre apenas os
+/// nomes que começam com o prefixo.
+///
+/// Para consultas repetidas pelo nome completo, `find_exact` usa um mapa montado junto com o
+/// índice, sem percorrer a lista a cada busca.
+#[derive(Debug, Clone)]
+pub struct MunicipalityIndex {
+    municipalities: Vec<Municipality>,
+    nodes: Vec<TrieNode>,
+    by_name: HashMap<String, usize>,
+}
+
+impl MunicipalityIndex {
+    pub fn new(municipalities: Vec<Municipality>) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+        let mut by_name = HashMap::new();
+
+        for (position, municipality) in municipalities.iter().enumerate() {
+            let key = index_key(&municipality.nome);
+            let mut node = 0;
+
+            for c in key.chars() {
+                node = match nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+
+            nodes[node].municipalities.push(position);
+            by_name.entry(key).or_insert(position);
+        }
+
+        Self {
+            municipalities,
+            nodes,
+            by_name,
+        }
+    }
+
+    /// Município com o nome `name`, sem diferenciar acentos e maiúsculas. Havendo nomes repetidos,
+    /// retorna o primeiro da lista usada na montagem do índice.
+    pub fn find_exact(&self, name: &str) -> Option<&Municipality> {
+        self.by_name
+            .get(&index_key(name.trim()))
+            .map(|&position| &self.municipalities[position])
+    }
+
+    /// Municípios cujo nome começa com `prefix`, em ordem alfabética do nome sem acentos.
+    pub fn prefix(&self, prefix: &str) -> Vec<&Municipality> {
+        let mut node = 0;
+
+        for c in index_key(prefix).chars() {
+            match self.nodes[node].children.get(&c) {
+                Some(&child) => node = child,
+                None => return Vec::new(),
+            }
+        }
+
+        let mut found = Vec::new();
+        let mut pending = vec![node];
+
+        while let Some(node) = pending.pop() {
+            let node = &self.nodes[node];
+
+            found.extend(
+                node.municipalities
+                    .iter()
+                    .map(|&position| &self.municipalities[position]),
+            );
+            pending.extend(node.children.values().rev());
+        }
+
+        found
+    }
+
+    pub fn get_municipalities(&self) -> &[Municipality] {
+        &self.municipalities
+    }
+
+    pub fn len(&self) -> usize {
+        self.municipalities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.municipalities.is_empty()
+    }
+}
+
+/// #### `build_municipality_index(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Busca os municípios de um estado e monta um `MunicipalityIndex` para busca por prefixo.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<MunicipalityIndex, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let index = ibge::build_municipality_index("SP", None).await.unwrap();
+///
+///    for municipality in index.prefix("sao j") {
+///        println!("{}", municipality.get_name());
+///    }
+/// }
+/// ```
+pub async fn build_municipality_index(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<MunicipalityIndex, Error> {
+    let municipalities = get_municipalities(uf, providers).await?;
+
+    Ok(MunicipalityIndex::new(municipalities))
+}
+
+pub async fn find_municipality_by_state_and_name(
+    uf: &str,
+    city_name: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<Option<Municipality>, Error> {
+    let municipalities = get_municipalities(uf, providers).await?;
+
+    let municipality = municipalities
+        .into_iter()
+        .find(|municipality| municipality.get_name().eq_ignore_ascii_case(city_name));
+
+    Ok(municipality)
+}
+
+/// Critério de ordenação dos resultados de `find_municipalities_by_name`, para que municípios
+/// homônimos de estados diferentes sejam retornados sempre na mesma ordem.
+///
+/// Os provedores atuais não informam a população dos municípios, então ela não está disponível
+/// como critério.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MunicipalityOrder {
+    /// Pela sigla do estado, em ordem alfabética, e em seguida pelo código IBGE.
+    #[default]
+    Uf,
+    /// Pelo código IBGE do município, que agrupa os estados por região.
+    Code,
+}
+
+/// Municípios de `country` com o nome `name`, sem diferenciar acentos e maiúsculas, ordenados
+/// por `order`.
+fn municipalities_named(
+    country: Vec<(String, Vec<Municipality>)>,
+    name: &str,
+    order: MunicipalityOrder,
+) -> Vec<(Uf, Municipality)> {
+    let key = index_key(name.trim());
+
+    let mut found: Vec<(Uf, Municipality)> = country
+        .into_iter()
+        .filter_map(|(uf, municipalities)| {
+            Uf::try_from(uf.as_str())
+                .ok()
+                .map(|uf| (uf, municipalities))
+        })
+        .flat_map(|(uf, municipalities)| {
+            municipalities
+                .into_iter()
+                .filter(|municipality| index_key(&municipality.nome) == key)
+                .map(move |municipality| (uf, municipality))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    match order {
+        MunicipalityOrder::Uf => found.sort_by(|(a_uf, a), (b_uf, b)| {
+            (a_uf.as_str(), &a.codigo_ibge).cmp(&(b_uf.as_str(), &b.codigo_ibge))
+        }),
+        MunicipalityOrder::Code => {
+            found.sort_by(|(_, a), (_, b)| a.codigo_ibge.cmp(&b.codigo_ibge))
+        }
+    }
+
+    found
+}
+
+/// #### `region_breakdown(items: &[(Uf, Municipality)])`
+/// Conta quantos municípios de `items` pertencem a cada região, a partir do estado de cada um.
+///
+/// ### Argumento
+/// * `items:&[(Uf, Municipality)]` => Municípios e seus estados, por exemplo o retorno de
+///   `find_municipalities_by_name`.
+///
+/// ### Retorno
+/// * `HashMap<Region, usize>` => Apenas as regiões com ao menos um município.
+pub fn region_breakdown(items: &[(Uf, Municipality)]) -> HashMap<Region, usize> {
+    let mut breakdown = HashMap::new();
+
+    for (uf, _) in items {
+        *breakdown.entry(uf.region()).or_insert(0) += 1;
+    }
+
+    breakdown
+}
+
+/// #### `find_municipalities_by_name(name: &str, order: MunicipalityOrder, batch: &Batch)`
+/// Busca os municípios com o nome informado em todos os estados, por exemplo as várias cidades
+/// chamadas `Bom Jesus`.
+///
+/// A comparação ignora acentos e a caixa das letras. Uma requisição é feita por estado,
+/// respeitando a configuração de `batch`, e os resultados são ordenados por `order`, de forma
+/// que a mesma consulta retorna sempre a mesma ordem.
+///
+/// ### Argumentos
+/// * `name:&str` => Nome do município.
+/// * `order:MunicipalityOrder` => Critério de ordenação dos homônimos.
+/// * `batch:&Batch` => Configuração das requisições em lote.
+///
+/// ### Retorno
+/// * `Result<Vec<(Uf, Municipality)>, Error>` => Estado e município de cada resultado.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{batch::Batch, ibge::{self, MunicipalityOrder}};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let found = ibge::find_municipalities_by_name("Bom Jesus", MunicipalityOrder::Uf, &Batch::new())
+///        .await
+///        .unwrap();
+///
+///    for (uf, municipality) in found {
+///        println!("{} - {}", uf, municipality.get_ibge_code());
+///    }
+/// }
+/// ```
+pub async fn find_municipalities_by_name(
+    name: &str,
+    order: MunicipalityOrder,
+    batch: &Batch,
+) -> Result<Vec<(Uf, Municipality)>, Error> {
+    let country = get_all_municipalities(None, batch).await?;
+
+    Ok(municipalities_named(country, name, order))
+}
+
+/// #### `get_all_states()`
+/// Retorna informações de todos estados do Brasil
+///
+/// ### Retorno
+/// * `Result<Vec<State>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let states = ibge::get_all_states().await.unwrap();
+/// }
+/// ```
 pub async fn get_all_states() -> Result<Vec<State>, Error> {
-    let ibge_service = IbgeService::new(BRASIL_API_URL);
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_all().await
+}
+
+/// Estado cuja região informada pela API difere da região esperada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    sigla: String,
+    expected: Region,
+    actual: Region,
+}
+
+impl Mismatch {
+    pub fn get_sigla(&self) -> &str {
+        &self.sigla
+    }
+
+    /// Região informada na referência.
+    pub fn get_expected(&self) -> &Region {
+        &self.expected
+    }
+
+    /// Região informada pela API.
+    pub fn get_actual(&self) -> &Region {
+        &self.actual
+    }
+}
+
+/// Estados de `states` cuja região difere da indicada em `reference`, ordenados pela sigla.
+/// As siglas da referência são comparadas sem diferenciar maiúsculas e espaços.
+fn region_mismatches(states: Vec<State>, reference: &HashMap<String, Region>) -> Vec<Mismatch> {
+    let reference: HashMap<String, &Region> = reference
+        .iter()
+        .map(|(sigla, region)| (sigla.trim().to_uppercase(), region))
+        .collect();
+
+    let mut mismatches: Vec<Mismatch> = states
+        .into_iter()
+        .filter_map(|state| {
+            let expected = *reference.get(state.get_sigla())?;
+            let actual = state.get_region().as_enum();
+
+            (actual != expected).then(|| Mismatch {
+                sigla: state.get_sigla().to_string(),
+                expected: expected.clone(),
+                actual: actual.clone(),
+            })
+        })
+        .collect();
+
+    mismatches.sort_by(|a, b| a.sigla.cmp(&b.sigla));
+
+    mismatches
+}
+
+/// #### `validate_region_assignments(reference: &HashMap<String, Region>)`
+/// Busca todos os estados e retorna aqueles cuja região informada pela API difere da região
+/// esperada, para detectar mudanças nos dados do IBGE.
+///
+/// Estados ausentes em `reference` não são verificados.
+///
+/// ### Argumento
+/// * `reference:&HashMap<String, Region>` => Região esperada, indexada pela sigla do estado.
+///
+/// ### Retorno
+/// * `Result<Vec<Mismatch>, Error>` => Divergências ordenadas pela sigla.
+///
+/// # Exemplo
+/// ```
+/// use std::collections::HashMap;
+/// use brasilapi::ibge::{self, Region};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let reference = HashMap::from([
+///        ("SP".to_string(), Region::Sudeste),
+///        ("BA".to_string(), Region::Nordeste),
+///    ]);
+///
+///    for mismatch in ibge::validate_region_assignments(&reference).await.unwrap() {
+///        println!("{}: {} != {}", mismatch.get_sigla(), mismatch.get_actual(), mismatch.get_expected());
+///    }
+/// }
+/// ```
+pub async fn validate_region_assignments(
+    reference: &HashMap<String, Region>,
+) -> Result<Vec<Mismatch>, Error> {
+    let states = get_all_states().await?;
+
+    Ok(region_mismatches(states, reference))
+}
+
+/// Quantidade de unidades federativas.
+const STATE_COUNT: usize = 27;
+
+/// Quantidade de regiões.
+const REGION_COUNT: usize = 5;
+
+/// #### `validate_invariants(states: &[State])`
+/// Confere as cardinalidades conhecidas da lista de estados: 27 unidades federativas distintas,
+/// distribuídas em 5 regiões.
+///
+/// ### Argumento
+/// * `states:&[State]` => Estados retornados por `get_all_states`.
+///
+/// ### Retorno
+/// * `Result<(), Error>` => Um erro `Errored::InvariantViolation` descrevendo a primeira
+///   divergência encontrada.
+pub fn validate_invariants(states: &[State]) -> Result<(), Error> {
+    let violation = |message: String| Err(Error::new(message, Errored::InvariantViolation, None));
+
+    if states.len() != STATE_COUNT {
+        return violation(format!(
+            "expected {} states, got {}",
+            STATE_COUNT,
+            states.len()
+        ));
+    }
+
+    let ufs: std::collections::HashSet<Uf> = states.iter().map(|state| state.sigla).collect();
+
+    if ufs.len() != STATE_COUNT {
+        return violation(format!(
+            "expected {} distinct states, got {}",
+            STATE_COUNT,
+            ufs.len()
+        ));
+    }
+
+    let regions = distinct_regions(states).len();
+
+    if regions != REGION_COUNT {
+        return violation(format!(
+            "expected {} regions, got {}",
+            REGION_COUNT, regions
+        ));
+    }
+
+    Ok(())
+}
+
+/// Define como `get_all_states_checked` trata uma resposta que não passa em
+/// `validate_invariants`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvariantCheck {
+    /// Retorna o erro de `validate_invariants`.
+    #[default]
+    Reject,
+    /// Retorna os estados mesmo assim, registrando a divergência em um evento de nível `warn`
+    /// com a feature `tracing`.
+    Warn,
+}
+
+/// #### `get_all_states_checked(check: InvariantCheck)`
+/// Igual a `get_all_states`, conferindo a resposta com `validate_invariants`.
+///
+/// Em `InvariantCheck::Reject` uma divergência retorna o erro de `validate_invariants`. Em
+/// `InvariantCheck::Warn` os estados são retornados mesmo assim e, com a feature `tracing`, a
+/// divergência é registrada como aviso.
+///
+/// ### Argumento
+/// * `check:InvariantCheck` => Como tratar divergências.
+///
+/// ### Retorno
+/// * `Result<Vec<State>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, InvariantCheck};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let states = ibge::get_all_states_checked(InvariantCheck::Reject).await.unwrap();
+/// }
+/// ```
+pub async fn get_all_states_checked(check: InvariantCheck) -> Result<Vec<State>, Error> {
+    let states = get_all_states().await?;
+
+    check_invariants(states, check)
+}
+
+fn check_invariants(states: Vec<State>, check: InvariantCheck) -> Result<Vec<State>, Error> {
+    match (validate_invariants(&states), check) {
+        (Err(error), InvariantCheck::Reject) => Err(error),
+        #[cfg(feature = "tracing")]
+        (Err(error), InvariantCheck::Warn) => {
+            tracing::warn!(message = %error.message, "unexpected states response");
+            Ok(states)
+        }
+        _ => Ok(states),
+    }
+}
+
+/// #### `get_states_map()`
+/// Retorna todos os estados do Brasil indexados pela unidade federativa.
+///
+/// ### Retorno
+/// * `Result<HashMap<Uf, State>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge::{self, Uf};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let states = ibge::get_states_map().await.unwrap();
+///
+///    println!("{}", states[&Uf::Sp].get_name());
+/// }
+/// ```
+pub async fn get_states_map() -> Result<HashMap<Uf, State>, Error> {
+    let states = get_all_states().await?;
+
+    Ok(states_by_uf(states))
+}
+
+fn states_by_uf(states: Vec<State>) -> HashMap<Uf, State> {
+    states
+        .into_iter()
+        .map(|state| (state.sigla, state))
+        .collect()
+}
+
+/// Regiões dos estados, sem repetições e ordenadas pelo id.
+fn distinct_regions(states: &[State]) -> Vec<StateRegion> {
+    let regions: BTreeMap<i32, &StateRegion> = states
+        .iter()
+        .map(|state| (state.regiao.id, &state.regiao))
+        .collect();
+
+    regions.into_values().cloned().collect()
+}
+
+/// #### `get_regions()`
+/// Retorna as 5 regiões do Brasil, a partir das regiões informadas em cada estado.
+///
+/// ### Retorno
+/// * `Result<Vec<StateRegion>, Error>` => Regiões ordenadas pelo id.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let regions = ibge::get_regions().await.unwrap();
+/// }
+/// ```
+pub async fn get_regions() -> Result<Vec<StateRegion>, Error> {
+    let states = get_all_states().await?;
+
+    Ok(distinct_regions(&states))
+}
+
+/// Estados agrupados pela região, ordenados pelo nome sem acentos dentro de cada uma.
+fn states_by_region(states: Vec<State>) -> BTreeMap<Region, Vec<State>> {
+    let mut regions: BTreeMap<Region, Vec<State>> = BTreeMap::new();
+
+    for state in states {
+        regions
+            .entry(state.regiao.sigla.clone())
+            .or_default()
+            .push(state);
+    }
+
+    for states in regions.values_mut() {
+        states.sort_by_cached_key(|state| index_key(&state.nome));
+    }
+
+    regions
+}
+
+/// #### `get_all_states_grouped()`
+/// Retorna todos os estados do Brasil agrupados pela região, como em um seletor de estados
+/// separado por regiões.
+///
+/// ### Retorno
+/// * `Result<BTreeMap<Region, Vec<State>>, Error>` => As regiões seguem a ordem de `Region`
+///   (Norte, Nordeste, Sudeste, Sul e Centro-Oeste) e os estados de cada região são ordenados
+///   pelo nome, ignorando acentos.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let regions = ibge::get_all_states_grouped().await.unwrap();
+///
+///    for (region, states) in &regions {
+///        println!("{}: {}", region.as_str(), states.len());
+///    }
+/// }
+/// ```
+pub async fn get_all_states_grouped() -> Result<BTreeMap<Region, Vec<State>>, Error> {
+    let states = get_all_states().await?;
+
+    Ok(states_by_region(states))
+}
+
+/// #### `get_state(code: &str)`
+/// Busca as informações de um estado a partir da sigla ou código
+///
+/// ### Argumento
+/// * `code:&str` => Sigla ou código do estado
+///
+/// ### Retorno
+/// * `Result<State, Error>`
+///
+/// Uma sigla ou código desconhecido retorna um erro `Errored::InvalidInput` com a
+/// mensagem `INVALID_STATE_CODE_MESSAGE`, sem que a API seja consultada. Com
+/// `Config::resolve_state_names`, o nome completo do estado também é aceito.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let state = ibge::get_state("SP").await.unwrap();
+/// }
+/// ```
+pub async fn get_state(code: &str) -> Result<State, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_state(code).await
+}
+
+/// #### `get_states(codes: &[&str])`
+/// Busca as informações de vários estados simultaneamente, a partir das siglas ou códigos.
+///
+/// As consultas seguem a configuração padrão de `Batch`. A primeira falha é retornada como erro.
+///
+/// ### Argumento
+/// * `codes:&[&str]` => Siglas ou códigos dos estados.
+///
+/// ### Retorno
+/// * `Result<Vec<State>, Error>` => Estados na mesma ordem de `codes`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let states = ibge::get_states(&["SP", "RJ", "MG"]).await.unwrap();
+/// }
+/// ```
+pub async fn get_states(codes: &[&str]) -> Result<Vec<State>, Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    ibge_service.fetch_states(codes, &Batch::default()).await
+}
+
+/// #### `get_state_with_headers(code: &str)`
+/// Igual a `get_state`, retornando também os cabeçalhos da resposta, como `ETag`,
+/// `Cache-Control` e `X-RateLimit-*`.
+///
+/// ### Argumento
+/// * `code:&str` => Sigla ou código do estado
+///
+/// ### Retorno
+/// * `Result<(State, HeaderMap), Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (state, headers) = ibge::get_state_with_headers("SP").await.unwrap();
+///
+///    println!("{:?}", headers.get("cache-control"));
+/// }
+/// ```
+pub async fn get_state_with_headers(code: &str) -> Result<(State, HeaderMap), Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    let response = ibge_service.get_state_request(code).await?;
+
+    client::read_json_with_headers(response).await
+}
+
+/// #### `get_all_states_with_headers()`
+/// Igual a `get_all_states`, retornando também os cabeçalhos da resposta.
+///
+/// ### Retorno
+/// * `Result<(Vec<State>, HeaderMap), Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (states, headers) = ibge::get_all_states_with_headers().await.unwrap();
+/// }
+/// ```
+pub async fn get_all_states_with_headers() -> Result<(Vec<State>, HeaderMap), Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    let response = ibge_service.get_all_states_request().await?;
+
+    client::read_json_with_headers(response).await
+}
+
+/// #### `get_municipalities_with_headers(uf: &str, providers: Option<Vec<MunicipalitiesProvider>>)`
+/// Igual a `get_municipalities`, retornando também os cabeçalhos da resposta.
+///
+/// Os municípios são retornados como enviados pela API, sem a remoção de repetições feita por
+/// `get_municipalities` quando mais de um provedor é informado.
+///
+/// ### Argumentos
+/// * `uf:&str` => Sigla da unidade federativa, por exemplo SP, RJ, SC, etc.
+/// * `providers:Option<Vec<MunicipalitiesProvider>>` => Provedores de dados para consulta.
+///
+/// ### Retorno
+/// * `Result<(Vec<Municipality>, HeaderMap), Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ibge;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (municipalities, headers) = ibge::get_municipalities_with_headers("SP", None).await.unwrap();
+/// }
+/// ```
+pub async fn get_municipalities_with_headers(
+    uf: &str,
+    providers: Option<Vec<MunicipalitiesProvider>>,
+) -> Result<(Vec<Municipality>, HeaderMap), Error> {
+    let ibge_service = IbgeService::new(client::base_url());
+
+    let response = ibge_service
+        .get_municipalities_request(uf, providers)
+        .await?;
+
+    client::read_json_with_headers(response).await
+}
+
+#[cfg(test)]
+mod ibge_tests {
+    use super::*;
+    use crate::{error::Errored, fixtures, spec::BRASIL_API_URL};
+    use httpmock::MockServer;
+
+    #[tokio::test]
+    async fn test_get_municipalities() {
+        let municipalities = sorted_by_code(get_municipalities("SP", None).await.unwrap());
+
+        assert_eq!(municipalities.len(), 645);
+        assert_eq!(municipalities[0].get_ibge_code(), "3500105");
+
+        let capital = municipalities
+            .iter()
+            .find(|municipality| municipality.get_ibge_code() == "3550308")
+            .unwrap();
+
+        assert_eq!(capital.get_name().to_lowercase(), "são paulo");
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_with_providers() {
+        let providers = vec![
+            MunicipalitiesProvider::DadosAbertos,
+            MunicipalitiesProvider::Wikipedia,
+        ];
+
+        let municipalities = get_municipalities("SC", Some(providers)).await.unwrap();
+
+        assert_eq!(municipalities.len(), 295);
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_with_invalid_uf() {
+        let result = get_municipalities("XX", None).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_uf_message() {
+        let error = get_municipalities("XX", None).await.unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(
+            error.message,
+            "invalid uf: expected a state abbreviation (e.g. SP)"
+        );
+    }
+
+    #[test]
+    fn test_validate_providers() {
+        assert!(validate_providers(&MunicipalitiesProvider::SUPPORTED).is_ok());
+        assert!(validate_providers(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_state_code() {
+        assert_eq!(normalize_state_code("sp", false).unwrap(), "SP");
+        assert!(normalize_state_code("35", false).is_ok());
+        assert_eq!(
+            normalize_state_code("99", false).unwrap_err().message,
+            INVALID_STATE_CODE_MESSAGE
+        );
+    }
+
+    #[test]
+    fn test_uf_conversions() {
+        assert_eq!(Uf::try_from("sp").unwrap(), Uf::Sp);
+        assert_eq!("RJ".parse::<Uf>().unwrap(), Uf::Rj);
+        assert_eq!(Uf::from_ibge_code("53"), Some(Uf::Df));
+        assert_eq!(Uf::Sp.to_string(), "SP");
+        assert_eq!(Uf::try_from("XX").unwrap_err().message, INVALID_UF_MESSAGE);
+
+        let state: State = serde_json::from_str(
+            r#"{"id": 42, "sigla": "SC", "nome": "Santa Catarina", "regiao": {"id": 4, "sigla": "S", "nome": "Sul"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(Uf::from(&state), Uf::Sc);
+        assert_eq!(state.get_sigla(), "SC");
+        assert_eq!(state.get_region().as_enum(), &Region::Sul);
+    }
+
+    #[test]
+    fn test_region_fallback() {
+        let region: StateRegion =
+            serde_json::from_str(r#"{"id": 9, "sigla": "XX", "nome": "Outra"}"#).unwrap();
+
+        assert_eq!(region.as_enum(), &Region::Other("XX".to_string()));
+        assert_eq!(region.get_sigla(), "XX");
+        assert_eq!(
+            serde_json::to_string(&Region::CentroOeste).unwrap(),
+            r#""CO""#
+        );
+    }
+
+    #[test]
+    fn test_resolve_uf() {
+        assert_eq!(Uf::from_name(" sao paulo "), Some(Uf::Sp));
+        assert_eq!(Uf::from_name("RIO GRANDE DO NORTE"), Some(Uf::Rn));
+        assert_eq!(Uf::from_name("Rio Grande"), None);
+        assert_eq!(Uf::Es.get_name(), "Espírito Santo");
+
+        assert_eq!(resolve_uf("sp", false).unwrap(), Uf::Sp);
+        assert_eq!(resolve_uf("Espirito Santo", true).unwrap(), Uf::Es);
+        assert_eq!(
+            resolve_uf("Espirito Santo", false).unwrap_err().message,
+            INVALID_UF_MESSAGE
+        );
+        assert_eq!(normalize_state_code("35", true).unwrap(), "35");
+        assert_eq!(normalize_state_code("são paulo", true).unwrap(), "SP");
+        assert!(normalize_state_code("99", true).is_err());
+    }
+
+    #[test]
+    fn test_region_mismatches() {
+        let states: Vec<State> = serde_json::from_str(
+            r#"[
+                {"id": 35, "sigla": "SP", "nome": "São Paulo", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}},
+                {"id": 29, "sigla": "BA", "nome": "Bahia", "regiao": {"id": 2, "sigla": "NE", "nome": "Nordeste"}},
+                {"id": 17, "sigla": "TO", "nome": "Tocantins", "regiao": {"id": 1, "sigla": "N", "nome": "Norte"}}
+            ]"#,
+        )
+        .unwrap();
+        let reference = HashMap::from([
+            ("sp".to_string(), Region::Sudeste),
+            ("TO".to_string(), Region::CentroOeste),
+        ]);
+
+        let mismatches = region_mismatches(states, &reference);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].get_sigla(), "TO");
+        assert_eq!(mismatches[0].get_expected(), &Region::CentroOeste);
+        assert_eq!(mismatches[0].get_actual(), &Region::Norte);
+    }
+
+    #[tokio::test]
+    async fn test_get_municipality_names() {
+        let names = get_municipality_names("SP", None).await.unwrap();
+
+        assert_eq!(names.len(), 645);
+    }
+
+    #[test]
+    fn test_municipality_name_ignores_ibge_code() {
+        let name: MunicipalityName =
+            serde_json::from_str(r#"{"nome": "BOA VISTA", "codigo_ibge": "1400100"}"#).unwrap();
+
+        assert_eq!(name.get_name(), "BOA VISTA");
+    }
+
+    #[test]
+    fn test_dedup_municipalities() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "SAO PAULO", "codigo_ibge": "3550308"},
+                {"nome": "CAMPINAS", "codigo_ibge": "3509502"},
+                {"nome": "São Paulo", "codigo_ibge": "3550308"},
+                {"nome": "SÃO PAULO", "codigo_ibge": "3550308"}
+            ]"#,
+        )
+        .unwrap();
+
+        let deduped = dedup_municipalities(municipalities);
+
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].get_name(), "São Paulo");
+        assert_eq!(deduped[1].get_name(), "CAMPINAS");
+    }
+
+    #[test]
+    fn test_municipality_changes() {
+        let before: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "BOA VISTA", "codigo_ibge": "1400100"},
+                {"nome": "BONFIM", "codigo_ibge": "1400159"},
+                {"nome": "NORMANDIA", "codigo_ibge": "1400407"}
+            ]"#,
+        )
+        .unwrap();
+        let after: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Boa Vista", "codigo_ibge": "1400100"},
+                {"nome": "Bonfim do Norte", "codigo_ibge": "1400159"},
+                {"nome": "Uiramutã", "codigo_ibge": "1400704"}
+            ]"#,
+        )
+        .unwrap();
+
+        let changes = municipality_changes(
+            &MunicipalitySnapshot::new(2000, before),
+            &MunicipalitySnapshot::new(2010, after),
+        );
+
+        assert_eq!(changes.get_from_year(), 2000);
+        assert_eq!(changes.get_to_year(), 2010);
+        assert_eq!(changes.get_created()[0].get_ibge_code(), "1400704");
+        assert_eq!(changes.get_dissolved()[0].get_ibge_code(), "1400407");
+        assert_eq!(changes.get_renamed().len(), 1);
+        assert_eq!(changes.get_renamed()[0].0.get_name(), "BONFIM");
+        assert_eq!(changes.get_renamed()[0].1.get_name(), "Bonfim do Norte");
+        assert!(!changes.is_empty());
+    }
+
+    #[test]
+    fn test_municipalities_url() {
+        let ibge_service = IbgeService::new(BRASIL_API_URL);
+        let providers = [
+            MunicipalitiesProvider::DadosAbertos,
+            MunicipalitiesProvider::Gov,
+        ];
+
+        assert_eq!(
+            ibge_service.municipalities_url("SP", None),
+            "https://brasilapi.com.br/api/ibge/municipios/v1/SP"
+        );
+        assert_eq!(
+            ibge_service.municipalities_url("SP", Some(&[])),
+            "https://brasilapi.com.br/api/ibge/municipios/v1/SP"
+        );
+        assert_eq!(
+            ibge_service.municipalities_url("SP", Some(&providers)),
+            "https://brasilapi.com.br/api/ibge/municipios/v1/SP?providers=dados-abertos-br%2Cgov"
+        );
+    }
+
+    #[test]
+    fn test_municipalities_url_encoding() {
+        let ibge_service = IbgeService::new("https://gw.company.com/brasilapi/");
+
+        assert_eq!(
+            ibge_service.municipalities_url("S P", None),
+            "https://gw.company.com/brasilapi/api/ibge/municipios/v1/S%20P"
+        );
+        assert_eq!(
+            ibge_service.state_url("S/P?"),
+            "https://gw.company.com/brasilapi/api/ibge/uf/v1/S%2FP%3F"
+        );
+    }
+
+    #[test]
+    fn test_municipalities_url_dedupes_providers() {
+        let ibge_service = IbgeService::new(BRASIL_API_URL);
+        let providers = [
+            MunicipalitiesProvider::Wikipedia,
+            MunicipalitiesProvider::DadosAbertos,
+            MunicipalitiesProvider::Wikipedia,
+        ];
+
+        assert_eq!(
+            ibge_service.municipalities_url("SC", Some(&providers)),
+            "https://brasilapi.com.br/api/ibge/municipios/v1/SC?providers=dados-abertos-br%2Cwikipedia"
+        );
+    }
+
+    #[test]
+    fn test_provider_set() {
+        let providers = MunicipalitiesProvider::DadosAbertos | MunicipalitiesProvider::Wikipedia;
+
+        assert_eq!(
+            providers,
+            ProviderSet::DADOS_ABERTOS | ProviderSet::WIKIPEDIA
+        );
+        assert_eq!(
+            Vec::from(providers | MunicipalitiesProvider::DadosAbertos),
+            vec![
+                MunicipalitiesProvider::DadosAbertos,
+                MunicipalitiesProvider::Wikipedia
+            ]
+        );
+        assert_eq!(ProviderSet::ALL.providers().len(), 3);
+        assert!(ProviderSet::empty().providers().is_empty());
+    }
+
+    #[test]
+    fn test_provider_from_str() {
+        for provider in MunicipalitiesProvider::all() {
+            assert_eq!(
+                MunicipalitiesProvider::from_str(provider.to_string()),
+                Some(*provider)
+            );
+        }
+
+        assert_eq!(
+            MunicipalitiesProvider::from_str(" Wikipedia "),
+            Some(MunicipalitiesProvider::Wikipedia)
+        );
+        assert_eq!(MunicipalitiesProvider::from_str("ibge"), None);
+    }
+
+    #[test]
+    fn test_states_url() {
+        let ibge_service = IbgeService::new(BRASIL_API_URL);
+
+        assert_eq!(
+            ibge_service.states_url(),
+            "https://brasilapi.com.br/api/ibge/uf/v1"
+        );
+        assert_eq!(
+            ibge_service.state_url("SP"),
+            "https://brasilapi.com.br/api/ibge/uf/v1/SP"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_all_municipalities() {
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        let rr = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(&fixture);
+            })
+            .await;
+        let ap = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/AP");
+                then.status(200)
+                    .body(r#"[{"nome": "MACAPÁ", "codigo_ibge": "1600303"}]"#);
+            })
+            .await;
+        let others = server
+            .mock_async(|when, then| {
+                when.method("GET").path_contains("/api/ibge/municipios/v1/");
+                then.status(200).body("[]");
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let country = ibge_service
+            .fetch_all_municipalities(None, &Batch::new().concurrency(2))
+            .await
+            .unwrap();
+
+        rr.assert_async().await;
+        ap.assert_async().await;
+        others.assert_hits_async(25).await;
+
+        let ufs: Vec<&str> = country.iter().map(|(uf, _)| uf.as_str()).collect();
+        let expected: Vec<&str> = Uf::ALL.iter().map(|uf| uf.as_str()).collect();
+
+        assert_eq!(ufs, expected);
+        assert!(country
+            .iter()
+            .any(|(uf, municipalities)| uf == "RR" && municipalities.len() == 15));
+        assert!(country
+            .iter()
+            .any(|(uf, municipalities)| uf == "AP" && municipalities.len() == 1));
+    }
+
+    #[test]
+    fn test_sorted_by_code() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Campinas", "codigo_ibge": "3509502"},
+                {"nome": "Adamantina", "codigo_ibge": "3500105"},
+                {"nome": "São Paulo", "codigo_ibge": "3550308"}
+            ]"#,
+        )
+        .unwrap();
+
+        let codes: Vec<String> = sorted_by_code(municipalities)
+            .iter()
+            .map(|municipality| municipality.get_ibge_code().to_string())
+            .collect();
+
+        assert_eq!(codes, vec!["3500105", "3509502", "3550308"]);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let sp: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "SÃO PAULO", "codigo_ibge": "3550308"},
+                {"nome": "Águas de Lindóia", "codigo_ibge": "3500501"},
+                {"nome": "Campinas", "codigo_ibge": "3509502"}
+            ]"#,
+        )
+        .unwrap();
+        let rr: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "BOA VISTA", "codigo_ibge": "1400100"},
+                {"nome": "São Paulo", "codigo_ibge": "3550308"}
+            ]"#,
+        )
+        .unwrap();
+
+        let merged = merge_sorted(vec![sp, rr]);
+        let names: Vec<&str> = merged.iter().map(|m| m.get_name()).collect();
+
+        assert_eq!(
+            names,
+            vec!["Águas de Lindóia", "BOA VISTA", "Campinas", "São Paulo"]
+        );
+    }
+
+    #[test]
+    fn test_same_municipality() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "SÃO PAULO", "codigo_ibge": "3550308"},
+                {"nome": "São Paulo", "codigo_ibge": "3550308"},
+                {"nome": "Campinas", "codigo_ibge": "3509502"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_ne!(municipalities[0], municipalities[1]);
+        assert!(municipalities[0].same_municipality(&municipalities[1]));
+        assert!(!municipalities[0].same_municipality(&municipalities[2]));
+
+        let set: std::collections::HashSet<&Municipality> = municipalities.iter().collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_municipality_index() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "SÃO PAULO", "codigo_ibge": "3550308"},
+                {"nome": "São José dos Campos", "codigo_ibge": "3549904"},
+                {"nome": "Santos", "codigo_ibge": "3548500"},
+                {"nome": "Campinas", "codigo_ibge": "3509502"},
+                {"nome": "São Paulo do Sul", "codigo_ibge": "9999999"}
+            ]"#,
+        )
+        .unwrap();
+
+        let index = MunicipalityIndex::new(municipalities);
+        let names = |prefix: &str| {
+            index
+                .prefix(prefix)
+                .into_iter()
+                .map(|municipality| municipality.get_name())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(index.len(), 5);
+        assert_eq!(
+            names("sao"),
+            vec!["São José dos Campos", "SÃO PAULO", "São Paulo do Sul"]
+        );
+        assert_eq!(names("SÃO P"), vec!["SÃO PAULO", "São Paulo do Sul"]);
+        assert_eq!(names("san"), vec!["Santos"]);
+        assert_eq!(names("").len(), 5);
+        assert!(names("rio").is_empty());
+
+        assert_eq!(
+            index.find_exact(" sao paulo ").unwrap().get_ibge_code(),
+            "3550308"
+        );
+        assert_eq!(index.find_exact("SANTOS").unwrap().get_name(), "Santos");
+        assert!(index.find_exact("São").is_none());
+    }
+
+    #[test]
+    fn test_group_municipalities_by_initial() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Água Boa", "codigo_ibge": "5100201"},
+                {"nome": "Alta Floresta", "codigo_ibge": "5100250"},
+                {"nome": "barra do Garças", "codigo_ibge": "5101803"},
+                {"nome": "1º de Maio", "codigo_ibge": "0000000"}
+            ]"#,
+        )
+        .unwrap();
+
+        let groups = group_municipalities_by_initial(&municipalities);
 
-    let response = ibge_service.get_all_states_request().await?;
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec![&'#', &'A', &'B']);
+        assert_eq!(groups[&'A'].len(), 2);
+        assert_eq!(groups[&'B'][0].get_name(), "barra do Garças");
+    }
 
-    let body = response.text().await.unwrap();
-    let states: Vec<State> = serde_json::from_str(&body).unwrap();
+    #[test]
+    fn test_with_missing_codes() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "São Paulo", "codigo_ibge": 3550308},
+                {"nome": "Sem Código", "codigo_ibge": ""},
+                {"nome": "Curto", "codigo_ibge": "35503"},
+                {"nome": "Rio de Janeiro", "codigo_ibge": "3304557"}
+            ]"#,
+        )
+        .unwrap();
 
-    Ok(states)
-}
+        let missing = with_missing_codes(municipalities, Uf::Sp);
 
-/// #### `get_state(code: &str)`
-/// Busca as informações de um estado a partir da sigla ou código
-///
-/// ### Argumento
-/// * `code:&str` => Sigla ou código do estado
-///
-/// ### Retorno
-/// * `Result<State, Error>`
-///
-/// # Exemplo
-/// ```
-/// use brasilapi::ibge;
-///
-/// #[tokio::main]
-/// async fn main() {
-///   let state = ibge::get_state("SP").await.unwrap();
-/// }
-/// ```
-pub async fn get_state(code: &str) -> Result<State, Error> {
-    let ibge_service = IbgeService::new(BRASIL_API_URL);
+        assert_eq!(
+            missing
+                .iter()
+                .map(Municipality::get_name)
+                .collect::<Vec<_>>(),
+            vec!["Sem Código", "Curto", "Rio de Janeiro"]
+        );
+    }
 
-    let response = ibge_service.get_state_request(code).await?;
+    #[test]
+    fn test_state_export() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Joinville", "codigo_ibge": "4209102"},
+                {"nome": "Florianópolis", "codigo_ibge": "4205407"}
+            ]"#,
+        )
+        .unwrap();
 
-    let body = response.text().await.unwrap();
-    let state: State = serde_json::from_str(&body).unwrap();
+        let fetched_at = UNIX_EPOCH + std::time::Duration::from_secs(1_709_210_096);
+        let export = state_export(Uf::Sc, municipalities, fetched_at);
 
-    Ok(state)
-}
+        assert_eq!(export.uf, "SC");
+        assert_eq!(export.fetched_at, "2024-02-29T12:34:56Z");
+        assert_eq!(export.municipalities[0].get_name(), "Florianópolis");
+        assert_eq!(rfc3339_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
 
-#[cfg(test)]
-mod ibge_tests {
-    use super::*;
+        let json = serde_json::to_string(&export).unwrap();
 
-    #[tokio::test]
-    async fn test_get_municipalities() {
-        let municipalities = get_municipalities("SP", None).await.unwrap();
+        assert_eq!(serde_json::from_str::<StateExport>(&json).unwrap(), export);
+    }
 
-        assert_eq!(municipalities.len(), 645);
+    #[test]
+    fn test_code_collisions() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Boa Vista", "codigo_ibge": "1400100"},
+                {"nome": "Caracaraí", "codigo_ibge": "1400209"},
+                {"nome": "BOA VISTA", "codigo_ibge": "1400100"},
+                {"nome": "Caracarai", "codigo_ibge": "1400209"},
+                {"nome": "Mucajaí", "codigo_ibge": "1400209"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            code_collisions(municipalities),
+            vec![(
+                "1400209".to_string(),
+                vec!["Caracaraí".to_string(), "Mucajaí".to_string()]
+            )]
+        );
     }
 
-    #[tokio::test]
-    async fn test_get_municipalities_with_providers() {
-        let providers = vec![
-            MunicipalitiesProvider::DadosAbertos,
-            MunicipalitiesProvider::Wikipedia,
+    #[test]
+    fn test_municipalities_named() {
+        let country = |uf: &str, body: &str| {
+            (
+                uf.to_string(),
+                serde_json::from_str::<Vec<Municipality>>(body).unwrap(),
+            )
+        };
+        let country = vec![
+            country("RS", r#"[{"nome": "Bom Jesus", "codigo_ibge": "4302303"}]"#),
+            country(
+                "PI",
+                r#"[{"nome": "BOM JESUS", "codigo_ibge": "2201903"}, {"nome": "Teresina", "codigo_ibge": "2211001"}]"#,
+            ),
+            country(
+                "GO",
+                r#"[{"nome": "Bom Jesus de Goiás", "codigo_ibge": "5203500"}]"#,
+            ),
         ];
 
-        let municipalities = get_municipalities("SC", Some(providers)).await.unwrap();
+        let by_uf = municipalities_named(country.clone(), "bom jesus", MunicipalityOrder::Uf);
+        let by_code = municipalities_named(country, "Bom Jesus", MunicipalityOrder::Code);
 
-        assert_eq!(municipalities.len(), 295);
+        assert_eq!(
+            by_uf.iter().map(|(uf, _)| *uf).collect::<Vec<_>>(),
+            vec![Uf::Pi, Uf::Rs]
+        );
+        assert_eq!(by_code[0].1.get_ibge_code(), "2201903");
+        assert_eq!(by_code.len(), 2);
+
+        let breakdown = region_breakdown(&by_uf);
+
+        assert_eq!(breakdown.get(&Region::Nordeste), Some(&1));
+        assert_eq!(breakdown.get(&Region::Sul), Some(&1));
+        assert_eq!(breakdown.get(&Region::Norte), None);
     }
 
-    #[tokio::test]
-    async fn test_get_municipalities_with_invalid_uf() {
-        let result = get_municipalities("XX", None).await;
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_filter_municipalities() {
+        let municipalities: Vec<Municipality> = serde_json::from_str(
+            r#"[
+                {"nome": "Santa Rosa de Lima", "codigo_ibge": "4215604"},
+                {"nome": "São José", "codigo_ibge": "4216602"},
+                {"nome": "Balneário Camboriú", "codigo_ibge": "4202008"}
+            ]"#,
+        )
+        .unwrap();
 
-        assert!(result.is_err());
+        let santas = filter_municipalities(&municipalities, &regex::Regex::new("^Santa").unwrap());
+        let sao = filter_municipalities(&municipalities, &regex::Regex::new("(?i)^sao ").unwrap());
+
+        assert_eq!(santas.len(), 1);
+        assert_eq!(sao[0].get_name(), "São José");
+        assert!(
+            filter_municipalities(&municipalities, &regex::Regex::new("^Rio").unwrap()).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_state_to_english() {
+        let state: State = serde_json::from_str(
+            r#"{"id": 35, "sigla": "SP", "nome": "São Paulo", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(state.to_english()).unwrap(),
+            serde_json::json!({
+                "id": 35,
+                "abbreviation": "SP",
+                "name": "São Paulo",
+                "region": {"id": 3, "abbreviation": "SE", "name": "Sudeste"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_municipality_to_english() {
+        let municipality: Municipality =
+            serde_json::from_str(r#"{"nome": "BOA VISTA", "codigo_ibge": "1400100"}"#).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(municipality.to_english()).unwrap(),
+            serde_json::json!({"name": "BOA VISTA", "ibge_code": "1400100"})
+        );
+    }
+
+    #[test]
+    fn test_municipality_to_record() {
+        let municipality: Municipality =
+            serde_json::from_str(r#"{"nome": "BOA VISTA", "codigo_ibge": "1400100"}"#).unwrap();
+        let record = municipality.to_record();
+
+        assert_eq!(record.code, "1400100");
+        assert_eq!(record.name, "BOA VISTA");
+        assert_eq!(MunicipalityRecord::from(&municipality), record);
     }
 
     #[tokio::test]
@@ -282,4 +3899,881 @@ mod ibge_tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_get_municipalities_from_fixture() {
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let response = ibge_service
+            .get_municipalities_request("RR", None)
+            .await
+            .unwrap();
+        let municipalities: Vec<Municipality> =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+        mock.assert_async().await;
+
+        let municipalities = sorted_by_code(municipalities);
+
+        assert_eq!(municipalities.len(), 15);
+        assert_eq!(municipalities[0].get_ibge_code(), "1400027");
+        assert_eq!(municipalities[0].get_name(), "AMAJARI");
+        assert_eq!(municipalities[14].get_ibge_code(), "1400704");
+        assert_eq!(municipalities[14].get_name(), "UIRAMUTÃ");
+
+        let capital = municipalities
+            .iter()
+            .find(|municipality| municipality.get_ibge_code() == "1400100")
+            .unwrap();
+
+        assert_eq!(capital.get_name(), "BOA VISTA");
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_resilient_with_failed_provider() {
+        let server = MockServer::start_async().await;
+        let gov = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov");
+                then.status(500).body("{}");
+            })
+            .await;
+        let wikipedia = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "wikipedia");
+                then.status(200)
+                    .body(r#"[{"nome": "Boa Vista", "codigo_ibge": "1400100"}]"#);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let result = ibge_service
+            .fetch_municipalities_resilient("RR", ProviderSet::GOV | ProviderSet::WIKIPEDIA)
+            .await
+            .unwrap();
+
+        gov.assert_async().await;
+        wikipedia.assert_async().await;
+
+        assert!(!result.is_complete());
+        assert_eq!(result.get_municipalities().len(), 1);
+        assert_eq!(result.get_failures()[0].0, MunicipalitiesProvider::Gov);
+        assert_eq!(result.get_failures()[0].1.code, Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_municipalities_timed_merges_providers() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov,wikipedia");
+                then.status(200).body(
+                    r#"[
+                        {"nome": "BOA VISTA", "codigo_ibge": "1400100"},
+                        {"nome": "Boa Vista", "codigo_ibge": "1400100"},
+                        {"nome": "Caracaraí", "codigo_ibge": "1400209"}
+                    ]"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let (municipalities, _) = ibge_service
+            .fetch_municipalities_timed(
+                "RR",
+                Some(vec![
+                    MunicipalitiesProvider::Gov,
+                    MunicipalitiesProvider::Wikipedia,
+                ]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(municipalities.len(), 2);
+        assert_eq!(municipalities[0].get_ibge_code(), "1400100");
+        assert_eq!(municipalities[1].get_ibge_code(), "1400209");
+    }
+
+    #[tokio::test]
+    async fn test_get_municipality() {
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+
+        let municipality = ibge_service.find_municipality("1400100").await.unwrap();
+        assert_eq!(municipality.get_name(), "BOA VISTA");
+
+        let error = ibge_service.find_municipality("1499999").await.unwrap_err();
+        assert_eq!(error.error, Errored::NotFound);
+
+        for code in ["140010", "9900100", "14001OO"] {
+            let error = ibge_service.find_municipality(code).await.unwrap_err();
+            assert_eq!(error.message, INVALID_MUNICIPALITY_CODE_MESSAGE);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cloned_service_in_spawned_tasks() {
+        let fixture = fixtures::load("ibge_uf_sp", "/api/ibge/uf/v1/SP").await;
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1/SP");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let ibge_service = ibge_service.clone();
+
+                tokio::spawn(async move {
+                    let response = ibge_service.get_state_request("SP").await?;
+
+                    client::read_json::<State>(response).await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap().get_sigla(), "SP");
+        }
+
+        mock.assert_hits_async(4).await;
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_preferring() {
+        let server = MockServer::start_async().await;
+        let gov = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov");
+                then.status(500).body("{}");
+            })
+            .await;
+        let wikipedia = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "wikipedia");
+                then.status(200).body("[]");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "dados-abertos-br");
+                then.status(200)
+                    .body(r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}]"#);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+
+        let municipalities = ibge_service
+            .fetch_municipalities_preferring(
+                "RR",
+                MunicipalitiesProvider::Gov,
+                MunicipalitiesProvider::DadosAbertos,
+            )
+            .await
+            .unwrap();
+
+        gov.assert_async().await;
+        assert_eq!(municipalities[0].get_name(), "BOA VISTA");
+
+        let municipalities = ibge_service
+            .fetch_municipalities_preferring(
+                "RR",
+                MunicipalitiesProvider::Wikipedia,
+                MunicipalitiesProvider::DadosAbertos,
+            )
+            .await
+            .unwrap();
+
+        wikipedia.assert_async().await;
+        assert_eq!(municipalities.len(), 1);
+
+        let error = ibge_service
+            .fetch_municipalities_preferring(
+                "XX",
+                MunicipalitiesProvider::Gov,
+                MunicipalitiesProvider::DadosAbertos,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov");
+                then.status(200).body("<html>");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "wikipedia");
+                then.status(200)
+                    .body(r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}]"#);
+            })
+            .await;
+
+        let municipalities = IbgeService::new(&server.base_url())
+            .fetch_municipalities_preferring(
+                "RR",
+                MunicipalitiesProvider::Gov,
+                MunicipalitiesProvider::Wikipedia,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(municipalities.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_municipalities_ndjson() {
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path_contains("/api/ibge/municipios/v1/");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let mut output: Vec<u8> = Vec::new();
+        let progress = ProgressAggregator::new();
+
+        let written = ibge_service
+            .export_municipalities_ndjson(&mut output, None, &Batch::new(), Some(&progress))
+            .await
+            .unwrap();
+
+        assert_eq!(progress.total(), 27);
+        assert_eq!(progress.completed(), 27);
+        assert_eq!(progress.failures(), 0);
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(written, 27 * 15);
+        assert_eq!(lines.len(), written);
+        assert_eq!(lines[0]["uf"], "RO");
+        assert_eq!(lines[written - 1]["uf"], "DF");
+        assert!(lines[0]["codigo_ibge"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_export_municipalities_ndjson_write_failure() {
+        struct FailingWriter;
+
+        impl AsyncWrite for FailingWriter {
+            fn poll_write(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                _: &[u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                std::task::Poll::Ready(Err(std::io::Error::other("disco cheio")))
+            }
+
+            fn poll_flush(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                std::task::Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                std::task::Poll::Ready(Ok(()))
+            }
+        }
+
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path_contains("/api/ibge/municipios/v1/");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let progress = ProgressAggregator::new();
+
+        let error = ibge_service
+            .export_municipalities_ndjson(&mut FailingWriter, None, &Batch::new(), Some(&progress))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.message, "disco cheio");
+        assert_eq!(progress.failures(), 1);
+        assert!(progress.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_from_gov() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov");
+                then.status(200).body(
+                    r#"[{"nome": "Boa Vista", "codigo_ibge": 1400100}, {"nome": "Bonfim", "codigo_ibge": "1400159"}]"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let municipalities = ibge_service
+            .fetch_municipalities("RR", Some(vec![MunicipalitiesProvider::Gov]))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(municipalities[0].get_ibge_code(), "1400100");
+        assert_eq!(municipalities[1].get_ibge_code(), "1400159");
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_mapped() {
+        let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let codes = ibge_service
+            .fetch_municipalities_mapped("RR", None, |municipality| {
+                municipality.get_ibge_code().to_owned()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(codes.len(), 15);
+        assert!(codes.contains(&"1400100".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_state_summaries() {
+        let states = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let municipalities =
+            fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1");
+                then.status(200).body(&states);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path_contains("/api/ibge/municipios/v1/");
+                then.status(200).body(&municipalities);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let summaries = ibge_service
+            .summarize_states(&Batch::new().concurrency(8))
+            .await
+            .unwrap();
+
+        assert_eq!(summaries.len(), 27);
+        assert_eq!(summaries[0].state.get_sigla(), "RO");
+        assert!(summaries
+            .iter()
+            .all(|summary| summary.municipality_count == 15));
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_per_provider() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "gov");
+                then.status(200)
+                    .body(r#"[{"nome": "Boa Vista", "codigo_ibge": 1400100}]"#);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ibge/municipios/v1/RR")
+                    .query_param("providers", "wikipedia");
+                then.status(200).body(
+                    r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}, {"nome": "BONFIM", "codigo_ibge": "1400159"}]"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let per_provider = ibge_service
+            .fetch_municipalities_by_provider(
+                "RR",
+                Some(vec![
+                    MunicipalitiesProvider::Gov,
+                    MunicipalitiesProvider::Wikipedia,
+                ]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(per_provider.len(), 2);
+        assert_eq!(per_provider["gov"][0].get_name(), "Boa Vista");
+        assert_eq!(per_provider["wikipedia"].len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_municipality_pairs() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(
+                    r#"[{"nome": "BONFIM", "codigo_ibge": "1400159"}, {"nome": "BOA VISTA", "codigo_ibge": "1400100"}]"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let pairs = ibge_service
+            .fetch_municipality_pairs("RR", None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("1400100".to_string(), "BOA VISTA".to_string()),
+                ("1400159".to_string(), "BONFIM".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_with_lenient_mode() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200).body(
+                    r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}, {"nome": "BONFIM"}]"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+
+        let parsed = ibge_service
+            .fetch_municipalities_with_mode("RR", None, ParseMode::Lenient)
+            .await
+            .unwrap();
+
+        assert_eq!(parsed.get_items().len(), 1);
+        assert_eq!(parsed.get_failures()[0].get_index(), 1);
+
+        let error = ibge_service
+            .fetch_municipalities_with_mode("RR", None, ParseMode::Strict)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[tokio::test]
+    async fn test_provider_disagreements() {
+        let server = MockServer::start_async().await;
+        for (provider, body) in [
+            (
+                "dados-abertos-br",
+                r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}, {"nome": "UIRAMUTA", "codigo_ibge": "1400704"}]"#,
+            ),
+            (
+                "gov",
+                r#"[{"nome": "Boa Vista", "codigo_ibge": "1400100"}, {"nome": "Uiramutã", "codigo_ibge": "1400704"}]"#,
+            ),
+            (
+                "wikipedia",
+                r#"[{"nome": "Boa Vista", "codigo_ibge": "1400100"}]"#,
+            ),
+        ] {
+            server
+                .mock_async(|when, then| {
+                    when.method("GET")
+                        .path("/api/ibge/municipios/v1/RR")
+                        .query_param("providers", provider);
+                    then.status(200).body(body);
+                })
+                .await;
+        }
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let disagreements = ibge_service
+            .find_provider_disagreements("RR")
+            .await
+            .unwrap();
+
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].get_ibge_code(), "1400704");
+        assert_eq!(
+            disagreements[0].get_names(),
+            &[
+                (MunicipalitiesProvider::DadosAbertos, "UIRAMUTA".to_string()),
+                (MunicipalitiesProvider::Gov, "Uiramutã".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_municipality_counts_by_provider() {
+        let server = MockServer::start_async().await;
+        for (provider, body) in [
+            (
+                "dados-abertos-br",
+                r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}, {"nome": "BONFIM", "codigo_ibge": "1400159"}]"#,
+            ),
+            (
+                "gov",
+                r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}, {"nome": "BONFIM", "codigo_ibge": "1400159"}]"#,
+            ),
+            (
+                "wikipedia",
+                r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}]"#,
+            ),
+        ] {
+            server
+                .mock_async(|when, then| {
+                    when.method("GET")
+                        .path("/api/ibge/municipios/v1/RR")
+                        .query_param("providers", provider);
+                    then.status(200).body(body);
+                })
+                .await;
+        }
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let counts = ibge_service
+            .count_municipalities_by_provider("RR")
+            .await
+            .unwrap();
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["dados-abertos-br"], 2);
+        assert_eq!(counts["gov"], 2);
+        assert_eq!(counts["wikipedia"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_municipalities_resilient_all_failed() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(500).body("{}");
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let result = ibge_service
+            .fetch_municipalities_resilient("RR", ProviderSet::ALL)
+            .await;
+
+        assert_eq!(result.unwrap_err().code, Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_validate_invariants() {
+        let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let states: Vec<State> = serde_json::from_str(&fixture).unwrap();
+
+        assert!(validate_invariants(&states).is_ok());
+
+        let mut truncated = states.clone();
+        truncated.pop();
+
+        let error = check_invariants(truncated.clone(), InvariantCheck::Reject).unwrap_err();
+
+        assert_eq!(error.error, Errored::InvariantViolation);
+        assert_eq!(error.message, "expected 27 states, got 26");
+        assert_eq!(
+            check_invariants(truncated, InvariantCheck::Warn)
+                .unwrap()
+                .len(),
+            26
+        );
+
+        let mut repeated = states;
+        repeated[0] = repeated[1].clone();
+
+        assert_eq!(
+            validate_invariants(&repeated).unwrap_err().message,
+            "expected 27 distinct states, got 26"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_states_by_uf() {
+        let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let states: Vec<State> = serde_json::from_str(&fixture).unwrap();
+
+        let states = states_by_uf(states);
+
+        assert_eq!(states.len(), 27);
+        assert!(Uf::ALL.iter().all(|uf| states.contains_key(uf)));
+        assert_eq!(states[&Uf::Sp].get_name(), "São Paulo");
+    }
+
+    #[tokio::test]
+    async fn test_distinct_regions() {
+        let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let states: Vec<State> = serde_json::from_str(&fixture).unwrap();
+
+        let regions = distinct_regions(&states);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            regions.iter().map(|region| region.id).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(regions[2].get_sigla(), "SE");
+        assert_eq!(regions[2].as_enum(), &Region::Sudeste);
+        assert!(states
+            .iter()
+            .all(|state| &state.get_uf().region() == state.get_region().as_enum()));
+
+        let state = |uf: Uf| states.iter().find(|state| state.sigla == uf).unwrap();
+
+        assert!(state(Uf::Sp).same_region_as(state(Uf::Rj)));
+        assert!(!state(Uf::Sp).same_region_as(state(Uf::Ba)));
+    }
+
+    #[tokio::test]
+    async fn test_states_by_region() {
+        let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let states: Vec<State> = serde_json::from_str(&fixture).unwrap();
+
+        let regions = states_by_region(states);
+        let names = |region: Region| {
+            regions[&region]
+                .iter()
+                .map(State::get_sigla)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            regions.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                Region::Norte,
+                Region::Nordeste,
+                Region::Sudeste,
+                Region::Sul,
+                Region::CentroOeste
+            ]
+        );
+        assert_eq!(names(Region::Sudeste), vec!["ES", "MG", "RJ", "SP"]);
+        assert_eq!(names(Region::Sul), vec!["PR", "RS", "SC"]);
+        assert_eq!(names(Region::CentroOeste), vec!["DF", "GO", "MT", "MS"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_states_from_fixture() {
+        let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let states = ibge_service.fetch_all().await.unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(states.len(), 27);
+    }
+
+    #[tokio::test]
+    async fn test_get_state_from_fixture() {
+        let fixture = fixtures::load("ibge_uf_sp", "/api/ibge/uf/v1/SP").await;
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1/SP");
+                then.status(200).body(&fixture);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let response = ibge_service.get_state_request("SP").await.unwrap();
+        let state: State = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(state.get_sigla(), "SP");
+        assert_eq!(state.get_region().get_name(), "Sudeste");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_states_in_order() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1/RJ");
+                then.status(200)
+                    .delay(std::time::Duration::from_millis(100))
+                    .body(r#"{"id": 33, "sigla": "RJ", "nome": "Rio de Janeiro", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}}"#);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1/42");
+                then.status(200)
+                    .body(r#"{"id": 42, "sigla": "SC", "nome": "Santa Catarina", "regiao": {"id": 4, "sigla": "S", "nome": "Sul"}}"#);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let states = ibge_service
+            .fetch_states(&["RJ", "42"], &Batch::new().concurrency(2))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            states.iter().map(State::get_sigla).collect::<Vec<_>>(),
+            vec!["RJ", "SC"]
+        );
+
+        let error = ibge_service
+            .fetch_states(&["RJ", "XX"], &Batch::new())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.message, INVALID_STATE_CODE_MESSAGE);
+        assert_eq!(error.context.as_deref(), Some("uf XX"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_combination_bad_request() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(400).body(
+                    r#"{"message": "Requisição inválida", "type": "bad_request", "name": "BadRequestError"}"#,
+                );
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let error = ibge_service
+            .fetch_municipalities(
+                "RR",
+                Some(vec![
+                    MunicipalitiesProvider::Wikipedia,
+                    MunicipalitiesProvider::Gov,
+                ]),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::BadRequest);
+        assert_eq!(error.code, Some(400));
+        assert!(error.api_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_municipalities_bulk() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/RR");
+                then.status(200)
+                    .body(r#"[{"nome": "BOA VISTA", "codigo_ibge": "1400100"}]"#);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/municipios/v1/AP");
+                then.status(200)
+                    .body(r#"[{"nome": "MACAPÁ", "codigo_ibge": "1600303"}]"#);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&server.base_url());
+        let municipalities = ibge_service
+            .fetch_municipalities_bulk(&[Uf::Rr, Uf::Ap, Uf::Rr], None, &Batch::new())
+            .await
+            .unwrap();
+
+        assert_eq!(municipalities.len(), 2);
+        assert_eq!(municipalities[&Uf::Ap][0].get_name(), "MACAPÁ");
+        mock.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn test_base_url_trailing_slash() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ibge/uf/v1/42");
+                then.status(200)
+                    .body(r#"{"id": 42, "sigla": "SC", "nome": "Santa Catarina", "regiao": {"id": 4, "sigla": "S", "nome": "Sul"}}"#);
+            })
+            .await;
+
+        let ibge_service = IbgeService::new(&format!("{}/", server.base_url()));
+        let state = ibge_service.fetch_state("42").await.unwrap();
+
+        assert_eq!(state.get_sigla(), "SC");
+        mock.assert_async().await;
+    }
 }
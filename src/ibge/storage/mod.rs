@@ -0,0 +1,8 @@
+//! Esquemas versionados, mantidos pela biblioteca, para armazenar estados e municípios sem
+//! depender dos nomes dos campos retornados pela BrasilAPI.
+//!
+//! `State` e `Municipality` refletem o formato da resposta da API e mudam junto com ela. Os tipos
+//! de cada versão deste módulo, como `v1::StateDto`, não mudam: quando a API renomeia um campo,
+//! apenas as conversões são ajustadas, e os dados já armazenados continuam válidos. Uma mudança
+//! incompatível no esquema é publicada como uma nova versão, por exemplo `v2`.
+pub mod v1;
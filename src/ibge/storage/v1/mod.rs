@@ -0,0 +1,202 @@
+//! Versão 1 do esquema de estados e municípios.
+//!
+//! # Exemplo
+//! ```
+//! use brasilapi::ibge::{self, storage::v1::StateDto};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!    let state = ibge::get_state("SP").await.unwrap();
+//!    let dto = StateDto::from(&state);
+//!
+//!    let json = serde_json::to_string(&dto).unwrap();
+//!    let restored = ibge::State::try_from(serde_json::from_str::<StateDto>(&json).unwrap());
+//! }
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, STATE_CODE_MISMATCH_MESSAGE},
+    ibge::{Municipality, Region, State, StateRegion, Uf},
+};
+
+/// Estado no esquema `v1`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct StateDto {
+    /// Código IBGE do estado, por exemplo `35`.
+    pub id: i32,
+    /// Sigla do estado, por exemplo `SP`.
+    pub uf: String,
+    pub name: String,
+    pub region: RegionDto,
+}
+
+/// Região de um estado no esquema `v1`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct RegionDto {
+    pub id: i32,
+    /// Sigla da região, por exemplo `SE`.
+    pub abbreviation: String,
+    pub name: String,
+}
+
+/// Município no esquema `v1`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct MunicipalityDto {
+    /// Código IBGE de 7 dígitos, por exemplo `3550308`.
+    pub ibge_code: String,
+    pub name: String,
+}
+
+impl From<&State> for StateDto {
+    fn from(state: &State) -> Self {
+        Self {
+            id: state.id,
+            uf: state.sigla.to_string(),
+            name: state.nome.clone(),
+            region: RegionDto::from(&state.regiao),
+        }
+    }
+}
+
+impl From<State> for StateDto {
+    fn from(state: State) -> Self {
+        Self::from(&state)
+    }
+}
+
+impl From<&StateRegion> for RegionDto {
+    fn from(region: &StateRegion) -> Self {
+        Self {
+            id: region.id,
+            abbreviation: region.sigla.to_string(),
+            name: region.nome.clone(),
+        }
+    }
+}
+
+impl From<&Municipality> for MunicipalityDto {
+    fn from(municipality: &Municipality) -> Self {
+        Self {
+            ibge_code: municipality.codigo_ibge.clone(),
+            name: municipality.nome.clone(),
+        }
+    }
+}
+
+impl From<Municipality> for MunicipalityDto {
+    fn from(municipality: Municipality) -> Self {
+        Self {
+            ibge_code: municipality.codigo_ibge,
+            name: municipality.nome,
+        }
+    }
+}
+
+/// Retorna um erro `Errored::InvalidInput` com a mensagem `INVALID_UF_MESSAGE` quando a sigla
+/// não é uma unidade federativa, ou com a mensagem `STATE_CODE_MISMATCH_MESSAGE` quando o código
+/// IBGE não corresponde a ela.
+impl TryFrom<StateDto> for State {
+    type Error = Error;
+
+    fn try_from(dto: StateDto) -> Result<Self, Self::Error> {
+        let uf = Uf::try_from(dto.uf.as_str())?;
+
+        if dto.id.to_string() != uf.get_ibge_code() {
+            return Err(Error::invalid_input(&format!(
+                "{STATE_CODE_MISMATCH_MESSAGE}: id {}, uf {}",
+                dto.id, dto.uf
+            )));
+        }
+
+        Ok(Self {
+            id: dto.id,
+            sigla: uf,
+            nome: dto.name,
+            regiao: StateRegion::from(dto.region),
+        })
+    }
+}
+
+impl From<RegionDto> for StateRegion {
+    fn from(dto: RegionDto) -> Self {
+        Self {
+            id: dto.id,
+            sigla: Region::from(dto.abbreviation.as_str()),
+            nome: dto.name,
+        }
+    }
+}
+
+/// O código IBGE não é validado, assim como na resposta da API: um município armazenado com um
+/// código vazio ou inválido, como os encontrados por `ibge::municipalities_missing_codes`, é
+/// restaurado sem alterações.
+impl From<MunicipalityDto> for Municipality {
+    fn from(dto: MunicipalityDto) -> Self {
+        Self {
+            nome: dto.name,
+            codigo_ibge: dto.ibge_code,
+        }
+    }
+}
+
+#[cfg(test)]
+mod storage_tests {
+    use super::*;
+    use crate::error::{Errored, INVALID_UF_MESSAGE};
+
+    #[test]
+    fn test_state_dto() {
+        let state: State = serde_json::from_str(
+            r#"{"id": 35, "sigla": "SP", "nome": "São Paulo", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}}"#,
+        )
+        .unwrap();
+        let dto = StateDto::from(&state);
+
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            serde_json::json!({
+                "id": 35,
+                "uf": "SP",
+                "name": "São Paulo",
+                "region": {"id": 3, "abbreviation": "SE", "name": "Sudeste"}
+            })
+        );
+        assert_eq!(State::try_from(dto.clone()).unwrap(), state);
+
+        let error = State::try_from(StateDto {
+            id: 33,
+            ..dto.clone()
+        })
+        .unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(
+            error.message,
+            format!("{STATE_CODE_MISMATCH_MESSAGE}: id 33, uf SP")
+        );
+
+        let error = State::try_from(StateDto {
+            uf: "XX".to_string(),
+            ..dto
+        })
+        .unwrap_err();
+
+        assert_eq!(error.message, INVALID_UF_MESSAGE);
+    }
+
+    #[test]
+    fn test_municipality_dto() {
+        let municipality: Municipality =
+            serde_json::from_str(r#"{"nome": "São Paulo", "codigo_ibge": 3550308}"#).unwrap();
+        let dto = MunicipalityDto::from(&municipality);
+
+        assert_eq!(dto.ibge_code, "3550308");
+        assert_eq!(Municipality::from(dto), municipality);
+
+        let missing: Municipality =
+            serde_json::from_str(r#"{"nome": "Sem Código", "codigo_ibge": ""}"#).unwrap();
+
+        assert_eq!(Municipality::from(MunicipalityDto::from(&missing)), missing);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{client, error::*};
+use serde::{Deserialize, Serialize};
+
+/// Livro retornado pelo endpoint `/api/isbn/v1/{isbn}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Book {
+    isbn: String,
+    title: String,
+    subtitle: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    publisher: Option<String>,
+    synopsis: Option<String>,
+    dimensions: Option<Dimensions>,
+    year: Option<u16>,
+    format: Option<String>,
+    page_count: Option<u32>,
+    #[serde(default)]
+    subjects: Vec<String>,
+    location: Option<String>,
+    retail_price: Option<RetailPrice>,
+    cover_url: Option<String>,
+    provider: String,
+}
+
+impl Book {
+    /// ISBN-13, sem pontuação.
+    pub fn get_isbn(&self) -> &str {
+        &self.isbn
+    }
+
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn get_subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    pub fn get_authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    pub fn get_publisher(&self) -> Option<&str> {
+        self.publisher.as_deref()
+    }
+
+    pub fn get_synopsis(&self) -> Option<&str> {
+        self.synopsis.as_deref()
+    }
+
+    pub fn get_dimensions(&self) -> Option<&Dimensions> {
+        self.dimensions.as_ref()
+    }
+
+    /// Ano de publicação.
+    pub fn get_year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// Formato do livro, por exemplo `PHYSICAL` ou `DIGITAL`.
+    pub fn get_format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+
+    pub fn get_page_count(&self) -> Option<u32> {
+        self.page_count
+    }
+
+    pub fn get_subjects(&self) -> &[String] {
+        &self.subjects
+    }
+
+    /// Local de publicação, por exemplo `SÃO PAULO, SP`.
+    pub fn get_location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    pub fn get_retail_price(&self) -> Option<&RetailPrice> {
+        self.retail_price.as_ref()
+    }
+
+    pub fn get_cover_url(&self) -> Option<&str> {
+        self.cover_url.as_deref()
+    }
+
+    /// Provedor que encontrou o livro, por exemplo `cbl` ou `google-books`.
+    pub fn get_provider(&self) -> &str {
+        &self.provider
+    }
+}
+
+/// Dimensões de um livro físico.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Dimensions {
+    width: f64,
+    height: f64,
+    unit: String,
+}
+
+impl Dimensions {
+    pub fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> f64 {
+        self.height
+    }
+
+    /// Unidade das medidas, por exemplo `CENTIMETER`.
+    pub fn get_unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+/// Preço de venda sugerido.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RetailPrice {
+    currency: String,
+    amount: f64,
+}
+
+impl RetailPrice {
+    /// Código da moeda, por exemplo `BRL`.
+    pub fn get_currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn get_amount(&self) -> f64 {
+        self.amount
+    }
+}
+
+/// Remove a pontuação do ISBN, verificando se restam 10 ou 13 caracteres com um dígito
+/// verificador válido. O `X` final de um ISBN-10 é convertido para maiúsculo.
+fn normalize_isbn(isbn: &str) -> Result<String, Error> {
+    let isbn: String = isbn
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let valid = match isbn.len() {
+        10 => valid_isbn_10(&isbn),
+        13 => valid_isbn_13(&isbn),
+        _ => false,
+    };
+
+    match valid {
+        true => Ok(isbn),
+        false => Err(Error::invalid_input(INVALID_ISBN_MESSAGE)),
+    }
+}
+
+/// Confere o dígito verificador (módulo 11) de um ISBN-10, no qual `X` representa 10.
+fn valid_isbn_10(isbn: &str) -> bool {
+    let digits: Option<Vec<u32>> = isbn
+        .chars()
+        .enumerate()
+        .map(|(position, c)| match (position, c) {
+            (9, 'X') => Some(10),
+            _ => c.to_digit(10),
+        })
+        .collect();
+
+    digits.is_some_and(|digits| {
+        let sum: u32 = digits
+            .iter()
+            .enumerate()
+            .map(|(position, digit)| digit * (10 - position as u32))
+            .sum();
+
+        sum.is_multiple_of(11)
+    })
+}
+
+/// Confere o dígito verificador (módulo 10) de um ISBN-13, com pesos alternados 1 e 3.
+fn valid_isbn_13(isbn: &str) -> bool {
+    let digits: Option<Vec<u32>> = isbn.chars().map(|c| c.to_digit(10)).collect();
+
+    digits.is_some_and(|digits| {
+        let sum: u32 = digits
+            .iter()
+            .enumerate()
+            .map(|(position, digit)| digit * if position.is_multiple_of(2) { 1 } else { 3 })
+            .sum();
+
+        sum.is_multiple_of(10)
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct IsbnService {
+    base_url: String,
+}
+
+impl IsbnService {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: client::trim_base_url(base_url).to_string(),
+        }
+    }
+
+    async fn get_isbn_request(&self, isbn: &str) -> Result<reqwest::Response, Error> {
+        let isbn = normalize_isbn(isbn)?;
+        let url = format!("{}/api/isbn/v1/{}", self.base_url, isbn);
+
+        client::get(&url).await
+    }
+
+    async fn fetch_book(&self, isbn: &str) -> Result<Book, Error> {
+        let response = self.get_isbn_request(isbn).await?;
+
+        client::read_json(response).await
+    }
+}
+
+/// #### `validate(isbn: &str)`
+/// Verifica offline se o ISBN é válido, conferindo o dígito verificador.
+///
+/// São aceitos o ISBN-10, cujo dígito verificador usa o módulo 11 e pode ser `X`, e o ISBN-13,
+/// que usa o módulo 10. Hífens e espaços são ignorados.
+///
+/// ### Argumento
+/// * `isbn:&str` => ISBN-10 ou ISBN-13, com ou sem pontuação.
+///
+/// ### Retorno
+/// * `bool`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::isbn;
+///
+/// assert!(isbn::validate("978-85-457-0287-0"));
+/// assert!(isbn::validate("080442957X"));
+/// assert!(!isbn::validate("9788545702871"));
+/// ```
+pub fn validate(isbn: &str) -> bool {
+    normalize_isbn(isbn).is_ok()
+}
+
+/// #### `get_isbn(isbn: &str)`
+/// Busca as informações de um livro pelo ISBN.
+///
+/// ### Argumento
+/// * `isbn:&str` => ISBN-10 ou ISBN-13, com ou sem pontuação.
+///
+/// ### Retorno
+/// * `Result<Book, Error>`
+///
+/// Um ISBN sem 10 ou 13 dígitos, ou com o dígito verificador incorreto, retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_ISBN_MESSAGE`, sem que a API seja consultada.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::isbn;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let book = isbn::get_isbn("9788545702870").await.unwrap();
+///
+///    println!("{}", book.get_title());
+/// }
+/// ```
+pub async fn get_isbn(isbn: &str) -> Result<Book, Error> {
+    let isbn_service = IsbnService::new(client::base_url());
+
+    isbn_service.fetch_book(isbn).await
+}
+
+#[cfg(test)]
+mod isbn_tests {
+    use super::*;
+    use httpmock::MockServer;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("9788545702870"));
+        assert!(validate("978-85-457-0287-0"));
+        assert!(validate("0-306-40615-2"));
+        assert!(validate("080442957x"));
+        assert!(!validate("0306406153"));
+        assert!(!validate("9788545702871"));
+        assert!(!validate("97885457028X0"));
+        assert!(!validate("X804429570"));
+        assert!(!validate("978854570287"));
+    }
+
+    #[tokio::test]
+    async fn test_get_isbn() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/isbn/v1/9788545702870");
+                then.status(200).body(
+                    r#"{"isbn": "9788545702870", "title": "Akira", "subtitle": null, "authors": ["KATSUHIRO OTOMO"], "publisher": "Japorama Editora e Comunicação", "synopsis": null, "dimensions": {"width": 17.5, "height": 25.7, "unit": "CENTIMETER"}, "year": 2017, "format": "PHYSICAL", "page_count": 364, "subjects": ["mangá"], "location": "SÃO PAULO, SP", "retail_price": null, "cover_url": null, "provider": "cbl"}"#,
+                );
+            })
+            .await;
+
+        let isbn_service = IsbnService::new(&server.base_url());
+        let book = isbn_service.fetch_book("978-85-457-0287-0").await.unwrap();
+
+        assert_eq!(book.get_title(), "Akira");
+        assert_eq!(book.get_authors(), ["KATSUHIRO OTOMO"]);
+        assert_eq!(book.get_dimensions().unwrap().get_unit(), "CENTIMETER");
+        assert_eq!(book.get_page_count(), Some(364));
+
+        let error = isbn_service.fetch_book("9788545702871").await.unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(error.message, INVALID_ISBN_MESSAGE);
+        mock.assert_hits_async(1).await;
+    }
+}
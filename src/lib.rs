@@ -23,28 +23,104 @@
 //! }
 //! ```
 //!
+//! ## Conexões
+//! Todas as funções públicas compartilham um único [`reqwest::Client`], criado na primeira
+//! requisição. Conexões ociosas ficam no pool desse cliente e são encerradas automaticamente pelo
+//! reqwest após o tempo limite de inatividade, portanto não há nada que precise ser encerrado
+//! manualmente. Hooks executados em todas as requisições podem ser registrados no módulo
+//! [client](client/index.html).
+//!
+//! O HTTP/2 é usado quando o servidor o oferece. O endereço base, o tempo limite, o `User-Agent`
+//! e o keep-alive desse cliente podem ser definidos uma única vez, antes da primeira requisição,
+//! com [`configure`]:
+//! ```rust
+//! use std::time::Duration;
+//! use brasilapi::Config;
+//!
+//! brasilapi::configure(Config {
+//!     timeout: Some(Duration::from_secs(10)),
+//!     ..Config::default()
+//! })
+//! .unwrap();
+//! ```
+//!
+//! Para que a aplicação só inicie com a API acessível, use [`BrasilApi::connect`], que aplica a
+//! configuração e verifica a conexão. A versão da biblioteca e a configuração em uso, úteis em
+//! relatos de problemas, são retornadas por [`info`].
+//!
+//! ## Métricas
+//! Com a feature `metrics`, cada requisição é registrada pela fachada do crate
+//! [metrics](https://crates.io/crates/metrics), no exportador instalado pela aplicação:
+//! * `brasilapi_requests_total` - contador com os rótulos `endpoint` (`cep`, `ibge`, ...) e
+//!   `status` (`2xx`, `4xx`, `5xx`, ... ou `error` quando não houve resposta).
+//! * `brasilapi_request_duration_seconds` - histograma da latência com o rótulo `endpoint`.
+//!
+//! Respostas servidas pelo [cache](cache/index.html) não são contabilizadas. As consultas ao
+//! cache são registradas em `brasilapi_cache_lookups_total`, com o rótulo `result` (`hit` ou
+//! `miss`).
+//!
 //! ## Módulos
 //! A biblioteca é dividida em módulos, cada um com sua responsabilidade:
 //! * [Bank](bank/index.html) - Informações sobre sistema bancário brasileiro.
+//! * [Batch](batch/index.html) - Configuração das consultas em lote
+//! * [Cache](cache/index.html) - Armazenamento das respostas em memória
 //! * [Cep](cep/index.html) - Informações referentes a CEPs
+//! * [Client](client/index.html) - Configuração e hooks do cliente compartilhado
 //! * [Cnpj](cnpj/index.html) - Busca dados de empresas por CNPJ
 //! * [Corretoras](corretoras/index.html) - Informações referentes a Corretoras ativas listadas na CVM
+//! * [Cpf](cpf/index.html) - Validação e formatação de CPFs
+//! * [Cptec](cptec/index.html) - Tipos das previsões do tempo do CPTEC/INPE
 //! * [Ddd](ddd/index.html) - Informações relacionadas a DDDs
+//! * [Endpoint](endpoint/index.html) - Traits compartilhadas pelos serviços dos módulos
 //! * [Fipe](fipe/index.html) - Informações sobre Preço Médio de Veículos fornecido pela FIPE (Fundação Instituto de Pesquisas Econômicas)
 //! * [Holidays](holidays/index.html) - Informações sobre feriados nacionais
 //! * [Ibge](ibge/index.html) - Informações sobre estados Provenientes do IBGE
+//! * [Isbn](isbn/index.html) - Informações sobre livros a partir do ISBN
+//! * [Ncm](ncm/index.html) - Códigos da Nomenclatura Comum do Mercosul
+//! * [Number](number/index.html) - Conversão de números no formato brasileiro
+//! * [Parse](parse/index.html) - Modos de desserialização das respostas
 //! * [Pix](pix/index.html) - Informações referentes ao PIX
+//! * [Prelude](prelude/index.html) - Tipos e funções mais usados, para `use brasilapi::prelude::*`
 //! * [Registrobr](registrobr/index.html) - Avalia um dominio no registro.br
+//! * [Retry](retry/index.html) - Novas tentativas para erros temporários
+//! * [Schema](schema/index.html) - Detecção de mudanças no formato das respostas da API
+//! * [Transport](transport/index.html) - Substituição do cliente HTTP usado nas requisições
+//! * [Validate](validate/index.html) - Normalização e validação das entradas
 //! * [Error](error/index.html) - Estrutura de erros da biblioteca
 pub mod bank;
+pub mod batch;
+pub mod cache;
 pub mod cep;
+pub mod client;
 pub mod cnpj;
 pub mod corretoras;
+pub mod cpf;
+pub mod cptec;
 pub mod ddd;
+pub mod endpoint;
 pub mod error;
 pub mod fipe;
 pub mod holidays;
 pub mod ibge;
+pub mod isbn;
+pub mod ncm;
+mod normalize;
+pub mod number;
+pub mod parse;
 pub mod pix;
+pub mod prelude;
 pub mod registrobr;
+pub mod retry;
+pub mod schema;
 pub mod spec;
+pub mod transport;
+pub mod validate;
+
+pub use client::{configure, info, BrasilApi, ClientInfo, Config};
+
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+mod samples;
+#[cfg(test)]
+mod send_tests;
@@ -0,0 +1,464 @@
+use crate::{client, endpoint::ListEndpoint, error::*, normalize};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+/// Código da Nomenclatura Comum do Mercosul (NCM), usado na classificação fiscal de mercadorias.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Ncm {
+    codigo: String,
+    descricao: String,
+    data_inicio: Option<String>,
+    data_fim: Option<String>,
+}
+
+impl Ncm {
+    /// Código NCM, por exemplo `3305.10.00`.
+    pub fn get_code(&self) -> &str {
+        &self.codigo
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.descricao
+    }
+
+    /// Início da vigência do código, no formato `AAAA-MM-DD`.
+    pub fn get_start_date(&self) -> Option<&str> {
+        self.data_inicio.as_deref()
+    }
+
+    /// Fim da vigência do código, no formato `AAAA-MM-DD`.
+    pub fn get_end_date(&self) -> Option<&str> {
+        self.data_fim.as_deref()
+    }
+}
+
+/// Página de uma lista de códigos NCM, retornada por `page`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NcmPage<'a> {
+    items: Vec<&'a Ncm>,
+    page: usize,
+    per_page: usize,
+    total: usize,
+}
+
+impl<'a> NcmPage<'a> {
+    /// Códigos da página, ordenados pelo código.
+    pub fn get_items(&self) -> &[&'a Ncm] {
+        &self.items
+    }
+
+    /// Número da página, começando em 1.
+    pub fn get_page(&self) -> usize {
+        self.page
+    }
+
+    pub fn get_per_page(&self) -> usize {
+        self.per_page
+    }
+
+    /// Quantidade de códigos da lista inteira.
+    pub fn get_total(&self) -> usize {
+        self.total
+    }
+
+    /// Quantidade de páginas da lista inteira, `0` se ela estiver vazia ou se `per_page` for `0`.
+    pub fn get_total_pages(&self) -> usize {
+        match self.per_page {
+            0 => 0,
+            per_page => self.total.div_ceil(per_page),
+        }
+    }
+
+    /// `true` se houver uma página depois desta.
+    pub fn has_next(&self) -> bool {
+        self.page < self.get_total_pages()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NcmService {
+    base_url: String,
+}
+
+/// Remove a pontuação do código NCM, verifica se restam exatamente 8 dígitos e os formata como
+/// `0000.00.00`.
+fn normalize_ncm(code: &str) -> Result<String, Error> {
+    let digits: String = code.chars().filter(|c| !matches!(c, '.' | ' ')).collect();
+
+    if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::invalid_input(INVALID_NCM_MESSAGE));
+    }
+
+    Ok(format!(
+        "{}.{}.{}",
+        &digits[..4],
+        &digits[4..6],
+        &digits[6..]
+    ))
+}
+
+impl NcmService {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: client::trim_base_url(base_url).to_string(),
+        }
+    }
+
+    fn all_ncm_url(&self) -> String {
+        format!("{}/api/ncm/v1", self.base_url)
+    }
+
+    async fn get_all_ncm_request(&self) -> Result<reqwest::Response, Error> {
+        client::get(&self.all_ncm_url()).await
+    }
+
+    async fn get_ncm_request(&self, code: &str) -> Result<reqwest::Response, Error> {
+        let code = normalize_ncm(code)?;
+        let url = format!("{}/api/ncm/v1/{}", self.base_url, code);
+
+        client::get(&url).await
+    }
+
+    async fn search_ncm_request(&self, query: &str) -> Result<reqwest::Response, Error> {
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/api/ncm/v1", self.base_url),
+            [("search", query)],
+        )
+        .map_err(|error| Error::invalid_input(&error.to_string()))?;
+
+        client::get(url.as_str()).await
+    }
+
+    async fn fetch_ncm(&self, code: &str) -> Result<Ncm, Error> {
+        let response = self.get_ncm_request(code).await?;
+
+        client::read_json(response).await
+    }
+
+    async fn fetch_search(&self, query: &str) -> Result<Vec<Ncm>, Error> {
+        let response = self.search_ncm_request(query).await?;
+
+        client::read_list(response).await
+    }
+
+    async fn stream_all(&self) -> Result<impl Stream<Item = Result<Ncm, Error>> + Send, Error> {
+        let response = client::get_uncached(&self.all_ncm_url()).await?;
+
+        Ok(client::stream_list(response))
+    }
+}
+
+impl ListEndpoint<Ncm> for NcmService {
+    async fn fetch_all(&self) -> Result<Vec<Ncm>, Error> {
+        let response = self.get_all_ncm_request().await?;
+
+        client::read_list(response).await
+    }
+}
+
+/// #### `get_all_ncm()`
+/// Retorna todos os códigos NCM.
+///
+/// ### Retorno
+/// * `Result<Vec<Ncm>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let codes = ncm::get_all_ncm().await.unwrap();
+/// }
+/// ```
+pub async fn get_all_ncm() -> Result<Vec<Ncm>, Error> {
+    let ncm_service = NcmService::new(client::base_url());
+
+    ncm_service.fetch_all().await
+}
+
+/// #### `stream_all_ncm()`
+/// Igual a `get_all_ncm`, mas retorna os códigos um a um conforme a resposta é recebida, sem
+/// manter a tabela inteira em memória. A requisição não passa pelo cache de `cache::enable`.
+///
+/// ### Retorno
+/// * `Result<impl Stream<Item = Result<Ncm, Error>>, Error>` => Erros da requisição são
+///   retornados antes da stream; erros de leitura ou desserialização encerram a stream após
+///   serem retornados por ela.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+/// use futures::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let mut codes = std::pin::pin!(ncm::stream_all_ncm().await.unwrap());
+///
+///    while let Some(ncm) = codes.next().await {
+///        println!("{}", ncm.unwrap().get_code());
+///    }
+/// }
+/// ```
+pub async fn stream_all_ncm() -> Result<impl Stream<Item = Result<Ncm, Error>> + Send, Error> {
+    let ncm_service = NcmService::new(client::base_url());
+
+    ncm_service.stream_all().await
+}
+
+/// #### `get_ncm(code: &str)`
+/// Busca as informações de um código NCM.
+///
+/// ### Argumento
+/// * `code:&str` => Código NCM, com ou sem pontuação, por exemplo `3305.10.00` ou `33051000`.
+///
+/// ### Retorno
+/// * `Result<Ncm, Error>`
+///
+/// Um código que não possua 8 dígitos (a pontuação é ignorada) retorna um erro
+/// `Errored::InvalidInput` com a mensagem `INVALID_NCM_MESSAGE`, sem que a API seja consultada.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let ncm = ncm::get_ncm("33051000").await.unwrap();
+/// }
+/// ```
+pub async fn get_ncm(code: &str) -> Result<Ncm, Error> {
+    let ncm_service = NcmService::new(client::base_url());
+
+    ncm_service.fetch_ncm(code).await
+}
+
+/// #### `search_ncm(query: &str)`
+/// Busca os códigos NCM cujo código ou descrição contenham `query`.
+///
+/// ### Argumento
+/// * `query:&str` => Texto para busca, por exemplo `xampus`.
+///
+/// ### Retorno
+/// * `Result<Vec<Ncm>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let codes = ncm::search_ncm("xampus").await.unwrap();
+/// }
+/// ```
+pub async fn search_ncm(query: &str) -> Result<Vec<Ncm>, Error> {
+    let ncm_service = NcmService::new(client::base_url());
+
+    ncm_service.fetch_search(query).await
+}
+
+/// #### `filter_by_description(list: &[Ncm], query: &str)`
+/// Filtra uma lista de códigos NCM já consultada pelas descrições que contêm `query`, sem
+/// consultar a API.
+///
+/// A comparação ignora acentos e a caixa das letras, de forma que `"acucar"` encontra
+/// `"Açúcares de cana"`. Uma busca vazia retorna toda a lista.
+///
+/// ### Argumentos
+/// * `list:&[Ncm]` => Códigos NCM, por exemplo o retorno de `get_all_ncm`.
+/// * `query:&str` => Texto para busca.
+///
+/// ### Retorno
+/// * `Vec<&Ncm>` => Códigos encontrados, na ordem de `list`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let codes = ncm::get_all_ncm().await.unwrap();
+///    let sugar = ncm::filter_by_description(&codes, "acucar");
+/// }
+/// ```
+pub fn filter_by_description<'a>(list: &'a [Ncm], query: &str) -> Vec<&'a Ncm> {
+    let fold = |text: &str| normalize::fold_accents(text).to_lowercase();
+    let query = fold(query.trim());
+
+    list.iter()
+        .filter(|ncm| fold(&ncm.descricao).contains(&query))
+        .collect()
+}
+
+/// #### `page(list: &[Ncm], page: usize, per_page: usize)`
+/// Retorna uma página de uma lista de códigos NCM já consultada, sem consultar a API.
+///
+/// A API não pagina a tabela NCM, portanto a lista é ordenada pelo código (e pela descrição,
+/// em caso de códigos repetidos) antes de ser dividida, de forma que a mesma página contenha
+/// sempre os mesmos códigos, independentemente da ordem de `list`. Uma página além da última,
+/// a página `0` ou um `per_page` igual a `0` retornam uma página sem códigos.
+///
+/// ### Argumentos
+/// * `list:&[Ncm]` => Códigos NCM, por exemplo o retorno de `get_all_ncm`.
+/// * `page:usize` => Número da página, começando em 1.
+/// * `per_page:usize` => Quantidade de códigos por página.
+///
+/// ### Retorno
+/// * `NcmPage`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::ncm;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let codes = ncm::get_all_ncm().await.unwrap();
+///    let first = ncm::page(&codes, 1, 50);
+///
+///    println!("{} de {} páginas", first.get_page(), first.get_total_pages());
+/// }
+/// ```
+pub fn page(list: &[Ncm], page: usize, per_page: usize) -> NcmPage<'_> {
+    let mut sorted: Vec<&Ncm> = list.iter().collect();
+    sorted.sort_by(|a, b| (&a.codigo, &a.descricao).cmp(&(&b.codigo, &b.descricao)));
+
+    let start = page.saturating_sub(1).saturating_mul(per_page);
+    let items = match page {
+        0 => Vec::new(),
+        _ => sorted.into_iter().skip(start).take(per_page).collect(),
+    };
+
+    NcmPage {
+        items,
+        page,
+        per_page,
+        total: list.len(),
+    }
+}
+
+#[cfg(test)]
+mod ncm_tests {
+    use super::*;
+    use httpmock::MockServer;
+
+    const NCM_BODY: &str = r#"{"codigo": "3305.10.00", "descricao": "- Xampus", "data_inicio": "2022-04-01", "data_fim": "9999-12-31", "tipo_ato": "Res Camex", "numero_ato": "000272", "ano_ato": "2021"}"#;
+
+    #[test]
+    fn test_normalize_ncm() {
+        assert_eq!(normalize_ncm("33051000").unwrap(), "3305.10.00");
+        assert_eq!(normalize_ncm("3305.10.00").unwrap(), "3305.10.00");
+
+        let error = normalize_ncm("3305.10").unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(error.message, INVALID_NCM_MESSAGE);
+    }
+
+    #[test]
+    fn test_filter_by_description() {
+        let list: Vec<Ncm> = serde_json::from_str(
+            r#"[
+                {"codigo": "1701", "descricao": "Açúcares de cana ou de beterraba"},
+                {"codigo": "3305.10.00", "descricao": "- Xampus"}
+            ]"#,
+        )
+        .unwrap();
+
+        let found = filter_by_description(&list, "ACUCAR");
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_code(), "1701");
+        assert_eq!(filter_by_description(&list, "").len(), 2);
+        assert!(filter_by_description(&list, "café").is_empty());
+    }
+
+    #[test]
+    fn test_page() {
+        let list: Vec<Ncm> = serde_json::from_str(
+            r#"[
+                {"codigo": "3305.10.00", "descricao": "- Xampus"},
+                {"codigo": "01", "descricao": "Animais vivos."},
+                {"codigo": "1701", "descricao": "Açúcares de cana ou de beterraba"}
+            ]"#,
+        )
+        .unwrap();
+
+        let first = page(&list, 1, 2);
+        let codes: Vec<&str> = first.get_items().iter().map(|ncm| ncm.get_code()).collect();
+
+        assert_eq!(codes, ["01", "1701"]);
+        assert_eq!(first.get_total(), 3);
+        assert_eq!(first.get_total_pages(), 2);
+        assert!(first.has_next());
+
+        let last = page(&list, 2, 2);
+
+        assert_eq!(last.get_items().len(), 1);
+        assert_eq!(last.get_items()[0].get_code(), "3305.10.00");
+        assert!(!last.has_next());
+        assert!(page(&list, 3, 2).get_items().is_empty());
+        assert!(page(&list, 0, 2).get_items().is_empty());
+        assert_eq!(page(&list, 1, 0).get_total_pages(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_all_ncm() {
+        use futures::StreamExt;
+
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ncm/v1");
+                then.status(200).body(format!("[{NCM_BODY}, {NCM_BODY}]"));
+            })
+            .await;
+
+        let ncm_service = NcmService::new(&server.base_url());
+        let codes: Vec<Ncm> = ncm_service
+            .stream_all()
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(codes.len(), 2);
+        assert_eq!(codes[1].get_code(), "3305.10.00");
+    }
+
+    #[tokio::test]
+    async fn test_get_ncm() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ncm/v1/3305.10.00");
+                then.status(200).body(NCM_BODY);
+            })
+            .await;
+
+        let ncm_service = NcmService::new(&server.base_url());
+        let ncm = ncm_service.fetch_ncm("33051000").await.unwrap();
+
+        assert_eq!(ncm.get_code(), "3305.10.00");
+        assert_eq!(ncm.get_description(), "- Xampus");
+        assert_eq!(ncm.get_end_date(), Some("9999-12-31"));
+    }
+
+    #[tokio::test]
+    async fn test_search_ncm() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/api/ncm/v1")
+                    .query_param("search", "xampus infantis");
+                then.status(200).body(format!("[{NCM_BODY}]"));
+            })
+            .await;
+
+        let ncm_service = NcmService::new(&server.base_url());
+        let codes = ncm_service.fetch_search("xampus infantis").await.unwrap();
+
+        assert_eq!(codes.len(), 1);
+    }
+}
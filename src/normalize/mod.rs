@@ -0,0 +1,93 @@
+//! Funções auxiliares para normalização de textos em português, usadas na comparação de nomes
+//! de municípios vindos de provedores diferentes.
+
+/// Palavras que permanecem em minúsculo no meio de um nome próprio.
+const LOWERCASE_WORDS: [&str; 6] = ["da", "das", "de", "do", "dos", "e"];
+
+fn fold_char(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+        'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+        'ç' => 'c',
+        'Ç' => 'C',
+        'ñ' => 'n',
+        'Ñ' => 'N',
+        _ => c,
+    }
+}
+
+/// Remove os acentos de `text`, mantendo a caixa das letras.
+pub(crate) fn fold_accents(text: &str) -> String {
+    text.chars().map(fold_char).collect()
+}
+
+/// Verifica se `text` possui ao menos um caractere acentuado.
+pub(crate) fn has_accents(text: &str) -> bool {
+    text.chars().any(|c| fold_char(c) != c)
+}
+
+/// Converte `text` para a capitalização usual de nomes próprios, por exemplo
+/// `"SÃO JOÃO DA BALIZA"` para `"São João da Baliza"`.
+pub(crate) fn title_case(text: &str) -> String {
+    text.split(' ')
+        .enumerate()
+        .map(|(position, word)| {
+            let lower = word.to_lowercase();
+
+            match position > 0 && LOWERCASE_WORDS.contains(&lower.as_str()) {
+                true => lower,
+                false => capitalize(&lower),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Capitaliza a primeira letra de `word` e a primeira letra após cada hífen.
+fn capitalize(word: &str) -> String {
+    let mut capitalized = String::with_capacity(word.len());
+    let mut upper_next = true;
+
+    for c in word.chars() {
+        match upper_next {
+            true => capitalized.extend(c.to_uppercase()),
+            false => capitalized.push(c),
+        }
+
+        upper_next = c == '-';
+    }
+
+    capitalized
+}
+
+#[cfg(test)]
+mod normalize_tests {
+    use super::*;
+
+    #[test]
+    fn fold_accents_test() {
+        assert_eq!(fold_accents("São Paulo"), "Sao Paulo");
+        assert_eq!(fold_accents("ÁGUA BOA"), "AGUA BOA");
+    }
+
+    #[test]
+    fn has_accents_test() {
+        assert!(has_accents("Cantá"));
+        assert!(!has_accents("Canta"));
+    }
+
+    #[test]
+    fn title_case_test() {
+        assert_eq!(title_case("SÃO JOÃO DA BALIZA"), "São João da Baliza");
+        assert_eq!(title_case("embu-guaçu"), "Embu-Guaçu");
+        assert_eq!(title_case("E"), "E");
+    }
+}
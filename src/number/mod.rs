@@ -0,0 +1,196 @@
+//! Conversão de números no formato brasileiro, como `"R$ 150.000,00"` ou `"1,50"`, usados em
+//! campos de preço e taxa retornados pela BrasilAPI.
+//!
+//! Com a feature `decimal` habilitada, os valores também podem ser convertidos para
+//! [`rust_decimal::Decimal`], evitando erros de arredondamento de ponto flutuante em valores
+//! monetários.
+use serde::{de, Deserialize, Deserializer};
+
+/// Remove o símbolo de moeda e os separadores de milhar, retornando o número com `.` como
+/// separador decimal.
+///
+/// O `.` só é aceito como separador de milhar, em grupos de três dígitos antes da `,`. Um valor
+/// como `"1.50"`, com `.` decimal, retorna `None` em vez de ser lido como `150`.
+fn canonical(value: &str) -> Option<String> {
+    let value: String = value
+        .trim()
+        .trim_start_matches("R$")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    let (integer, fraction) = match value.split_once(',') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (value.as_str(), None),
+    };
+    let digits = integer.strip_prefix('-').unwrap_or(integer);
+
+    let mut groups = digits.split('.');
+    let grouped = groups
+        .next()
+        .is_some_and(|first| (1..=3).contains(&first.len()) || !digits.contains('.'))
+        && groups.all(|group| group.len() == 3);
+
+    if !grouped || fraction.is_some_and(|fraction| fraction.contains('.')) {
+        return None;
+    }
+
+    let canonical = match fraction {
+        Some(fraction) => format!("{}.{}", integer.replace('.', ""), fraction),
+        None => integer.replace('.', ""),
+    };
+
+    match canonical.is_empty() {
+        true => None,
+        false => Some(canonical),
+    }
+}
+
+/// #### `parse_f64(value: &str)`
+/// Converte um número no formato brasileiro para `f64`.
+///
+/// ### Argumento
+/// * `value:&str` => Número com `,` como separador decimal e `.` como separador de milhar,
+///   opcionalmente precedido de `R$`.
+///
+/// ### Retorno
+/// * `Option<f64>` => `None` caso o valor não seja um número, inclusive quando o `.` não separa
+///   grupos de três dígitos, como em `"1.50"`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::number;
+///
+/// assert_eq!(number::parse_f64("R$ 150.000,00"), Some(150000.0));
+/// assert_eq!(number::parse_f64("1,50"), Some(1.5));
+/// ```
+pub fn parse_f64(value: &str) -> Option<f64> {
+    canonical(value)?.parse().ok()
+}
+
+/// #### `parse_decimal(value: &str)`
+/// Converte um número no formato brasileiro para `Decimal`, sem perda de precisão.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::number;
+/// use rust_decimal::Decimal;
+///
+/// assert_eq!(number::parse_decimal("R$ 1.234,56"), Some(Decimal::new(123456, 2)));
+/// ```
+#[cfg(feature = "decimal")]
+pub fn parse_decimal(value: &str) -> Option<rust_decimal::Decimal> {
+    canonical(value)?.parse().ok()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Float(f64),
+    Text(String),
+}
+
+/// Desserializa um número no formato brasileiro, aceitando tanto texto quanto números JSON.
+///
+/// Para uso com `#[serde(deserialize_with = "brasilapi::number::deserialize_f64")]`.
+pub fn deserialize_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Number::deserialize(deserializer)? {
+        Number::Float(value) => Ok(value),
+        Number::Text(value) => parse_f64(&value)
+            .ok_or_else(|| de::Error::custom(format!("invalid brazilian number: {}", value))),
+    }
+}
+
+/// Desserializa um número no formato brasileiro para `Decimal`, aceitando tanto texto quanto
+/// números JSON.
+///
+/// Para uso com `#[serde(deserialize_with = "brasilapi::number::deserialize_decimal")]`.
+#[cfg(feature = "decimal")]
+pub fn deserialize_decimal<'de, D>(deserializer: D) -> Result<rust_decimal::Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Números JSON já usam `.` como separador decimal e não passam pela conversão do formato
+    // brasileiro, que trataria o `.` como separador de milhar.
+    match Number::deserialize(deserializer)? {
+        Number::Float(value) => value
+            .to_string()
+            .parse()
+            .map_err(|_| de::Error::custom(format!("invalid number: {}", value))),
+        Number::Text(value) => parse_decimal(&value)
+            .ok_or_else(|| de::Error::custom(format!("invalid brazilian number: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod number_tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Rate {
+        #[serde(deserialize_with = "deserialize_f64")]
+        valor: f64,
+    }
+
+    #[test]
+    fn parse_f64_test() {
+        assert_eq!(parse_f64("R$ 150.000,00"), Some(150000.0));
+        assert_eq!(parse_f64("1,50"), Some(1.5));
+        assert_eq!(parse_f64("-0,25"), Some(-0.25));
+        assert_eq!(parse_f64("13,75 "), Some(13.75));
+        assert_eq!(parse_f64("1.234,5"), Some(1234.5));
+        assert_eq!(parse_f64("1.500"), Some(1500.0));
+        assert_eq!(parse_f64("1.50"), None);
+        assert_eq!(parse_f64("3.5"), None);
+        assert_eq!(parse_f64("1.2345,00"), None);
+        assert_eq!(parse_f64("R$"), None);
+        assert_eq!(parse_f64("abc"), None);
+    }
+
+    #[test]
+    fn deserialize_f64_test() {
+        let rate: Rate = serde_json::from_str(r#"{"valor": "10,65"}"#).unwrap();
+        assert_eq!(rate.valor, 10.65);
+
+        let rate: Rate = serde_json::from_str(r#"{"valor": 10.65}"#).unwrap();
+        assert_eq!(rate.valor, 10.65);
+
+        assert!(serde_json::from_str::<Rate>(r#"{"valor": "dez"}"#).is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn parse_decimal_test() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(
+            parse_decimal("R$ 289.116,00"),
+            Some(Decimal::new(28911600, 2))
+        );
+        assert_eq!(parse_decimal("0,1"), Some(Decimal::new(1, 1)));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn deserialize_decimal_test() {
+        use rust_decimal::Decimal;
+
+        #[derive(Deserialize)]
+        struct Price {
+            #[serde(deserialize_with = "deserialize_decimal")]
+            valor: Decimal,
+        }
+
+        let price: Price = serde_json::from_str(r#"{"valor": 10.65}"#).unwrap();
+        assert_eq!(price.valor, Decimal::new(1065, 2));
+
+        let price: Price = serde_json::from_str(r#"{"valor": 1500}"#).unwrap();
+        assert_eq!(price.valor, Decimal::new(1500, 0));
+
+        let price: Price = serde_json::from_str(r#"{"valor": "R$ 1.234,56"}"#).unwrap();
+        assert_eq!(price.valor, Decimal::new(123456, 2));
+    }
+}
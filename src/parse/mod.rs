@@ -0,0 +1,737 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    Serialize,
+};
+
+use crate::{client, error::*, schema};
+
+/// Define como listas retornadas pela API são desserializadas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Retorna um erro caso qualquer elemento da lista seja inválido.
+    #[default]
+    Strict,
+    /// Ignora os elementos inválidos, registrando-os em `Parsed::get_failures`.
+    Lenient,
+    /// Retorna um erro caso qualquer elemento seja inválido, como `Strict`, e registra em
+    /// `Parsed::get_extras` os campos recebidos que o modelo não possui.
+    CaptureExtras,
+}
+
+/// Define como chaves repetidas em um mesmo objeto JSON são tratadas.
+///
+/// O serde_json mantém silenciosamente o último valor de uma chave repetida ao desserializar
+/// mapas, como em `{"id": 1, "id": 2}`. Uma resposta assim indica um espelho da API com
+/// problemas, e em `DuplicateKeys::Warn` ou `DuplicateKeys::Reject` o corpo é percorrido uma
+/// vez antes da desserialização para encontrá-las.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// Aceita as chaves repetidas, mantendo o comportamento do serde_json.
+    #[default]
+    Allow,
+    /// Aceita as chaves repetidas, registrando a primeira em um evento de nível `warn` com a
+    /// feature `tracing`.
+    Warn,
+    /// Retorna um erro `Errored::Deserialization` com a mensagem `DUPLICATE_KEY_MESSAGE`,
+    /// seguida pelo caminho da primeira chave repetida.
+    Reject,
+}
+
+/// Elemento de uma lista que não pôde ser desserializado.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseFailure {
+    index: usize,
+    message: String,
+    raw: serde_json::Value,
+}
+
+impl ParseFailure {
+    /// Posição do elemento na lista retornada pela API.
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Elemento como retornado pela API.
+    pub fn get_raw(&self) -> &serde_json::Value {
+        &self.raw
+    }
+}
+
+/// Lista desserializada, os elementos que foram ignorados no modo `ParseMode::Lenient` e os
+/// campos não modelados encontrados no modo `ParseMode::CaptureExtras`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed<T> {
+    items: Vec<T>,
+    failures: Vec<ParseFailure>,
+    extras: Vec<String>,
+}
+
+impl<T> Parsed<T> {
+    pub fn get_items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn get_failures(&self) -> &[ParseFailure] {
+        &self.failures
+    }
+
+    /// Campos recebidos que o modelo não possui, cada um registrado uma única vez, com os níveis
+    /// separados por `.` como em `schema::Drift::get_field`, por exemplo `regiao.codigo`.
+    pub fn get_extras(&self) -> &[String] {
+        &self.extras
+    }
+
+    /// Indica se todos os elementos foram desserializados.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Aplica `f` aos elementos desserializados, mantendo as falhas.
+    pub fn map_items<U>(self, f: impl FnOnce(Vec<T>) -> Vec<U>) -> Parsed<U> {
+        Parsed {
+            items: f(self.items),
+            failures: self.failures,
+            extras: self.extras,
+        }
+    }
+}
+
+/// Tempo gasto em cada etapa de uma consulta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timings {
+    network: Duration,
+    parse: Duration,
+}
+
+impl Timings {
+    /// Tempo aguardando a resposta HTTP, incluindo a leitura do corpo.
+    pub fn get_network(&self) -> Duration {
+        self.network
+    }
+
+    /// Tempo gasto na desserialização do corpo da resposta.
+    pub fn get_parse(&self) -> Duration {
+        self.parse
+    }
+
+    pub fn get_total(&self) -> Duration {
+        self.network + self.parse
+    }
+}
+
+/// Desserializa uma lista JSON de acordo com `mode`.
+pub(crate) fn parse_list<T: DeserializeOwned + Serialize>(
+    body: &[u8],
+    mode: ParseMode,
+) -> Result<Parsed<T>, Error> {
+    match mode {
+        ParseMode::Strict => {
+            let items = client::parse_json(body)?;
+
+            Ok(Parsed {
+                items,
+                failures: Vec::new(),
+                extras: Vec::new(),
+            })
+        }
+        ParseMode::Lenient => {
+            let values = parse_values(body)?;
+
+            let mut items = Vec::with_capacity(values.len());
+            let mut failures = Vec::new();
+
+            for (index, value) in values.into_iter().enumerate() {
+                match T::deserialize(&value) {
+                    Ok(item) => items.push(item),
+                    Err(error) => failures.push(ParseFailure {
+                        index,
+                        message: error.to_string(),
+                        raw: value,
+                    }),
+                }
+            }
+
+            Ok(Parsed {
+                items,
+                failures,
+                extras: Vec::new(),
+            })
+        }
+        ParseMode::CaptureExtras => {
+            let values = parse_values(body)?;
+
+            let mut items = Vec::with_capacity(values.len());
+            let mut extras = Vec::new();
+
+            for value in values {
+                let item = T::deserialize(&value).map_err(Error::from_json_error)?;
+
+                push_extras(&value, &item, &mut extras)?;
+                items.push(item);
+            }
+
+            Ok(Parsed {
+                items,
+                failures: Vec::new(),
+                extras,
+            })
+        }
+    }
+}
+
+/// Lê o corpo como uma lista de valores JSON, antes da desserialização de cada elemento.
+fn parse_values(body: &[u8]) -> Result<Vec<serde_json::Value>, Error> {
+    if body.trim_ascii().is_empty() {
+        return Err(Error::empty_response());
+    }
+
+    serde_json::from_slice(body).map_err(|error| Error::from_json_body_error(body, error))
+}
+
+/// Acrescenta a `extras` os campos de `raw` que não existem em `item` serializado novamente,
+/// como em `schema::check`.
+pub(crate) fn push_extras<T: Serialize>(
+    raw: &serde_json::Value,
+    item: &T,
+    extras: &mut Vec<String>,
+) -> Result<(), Error> {
+    let modeled = serde_json::to_value(item).map_err(Error::from_json_error)?;
+
+    for field in schema::unmodeled_fields(raw, &modeled) {
+        if !extras.contains(&field) {
+            extras.push(field);
+        }
+    }
+
+    Ok(())
+}
+
+/// Desserializa uma lista aplicando `transform` a cada elemento assim que ele é lido.
+struct MappedList<T, F> {
+    transform: F,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T, U, F> DeserializeSeed<'de> for MappedList<T, F>
+where
+    T: de::Deserialize<'de>,
+    F: FnMut(T) -> U,
+{
+    type Value = Vec<U>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T, U, F> Visitor<'de> for MappedList<T, F>
+where
+    T: de::Deserialize<'de>,
+    F: FnMut(T) -> U,
+{
+    type Value = Vec<U>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(item) = seq.next_element::<T>()? {
+            items.push((self.transform)(item));
+        }
+
+        Ok(items)
+    }
+}
+
+/// Percorre um valor JSON retornando o caminho da primeira chave repetida em um objeto, com os
+/// níveis separados por `.` e os elementos de listas indicados por `[]`, por exemplo
+/// `regiao.sigla` ou `[].codigo`.
+struct DuplicateKeyFinder<'a> {
+    path: &'a str,
+}
+
+impl DuplicateKeyFinder<'_> {
+    fn join(&self, key: &str) -> String {
+        match self.path {
+            "" => key.to_string(),
+            path => format!("{path}.{key}"),
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DuplicateKeyFinder<'_> {
+    type Value = Option<String>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DuplicateKeyFinder<'_> {
+    type Value = Option<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any json value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    // Os elementos são percorridos até o fim mesmo após uma chave repetida ser encontrada, pois
+    // o serde_json retorna um erro quando o visitor não consome a lista inteira.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let path = format!("{}[]", self.path);
+        let mut found = None;
+
+        while let Some(nested) = seq.next_element_seed(DuplicateKeyFinder { path: &path })? {
+            found = found.or(nested);
+        }
+
+        Ok(found)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut keys = HashSet::new();
+        let mut found = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            let path = self.join(&key);
+            let nested = map.next_value_seed(DuplicateKeyFinder { path: &path })?;
+            let duplicate = (!keys.insert(key)).then_some(path);
+
+            found = found.or(duplicate).or(nested);
+        }
+
+        Ok(found)
+    }
+}
+
+/// Verifica as chaves repetidas de `body` de acordo com `mode`. Um corpo que não seja JSON
+/// válido é aceito, para que o erro seja retornado pela desserialização.
+pub(crate) fn check_duplicate_keys(body: &[u8], mode: DuplicateKeys) -> Result<(), Error> {
+    if mode == DuplicateKeys::Allow {
+        return Ok(());
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let duplicate = DuplicateKeyFinder { path: "" }
+        .deserialize(&mut deserializer)
+        .ok()
+        .flatten();
+
+    match (duplicate, mode) {
+        (Some(path), DuplicateKeys::Reject) => Err(Error::new(
+            format!("{DUPLICATE_KEY_MESSAGE}: {path}"),
+            Errored::Deserialization,
+            None,
+        )),
+        #[cfg(feature = "tracing")]
+        (Some(path), DuplicateKeys::Warn) => {
+            tracing::warn!(field = %path, "duplicate json key in response");
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Desserializa uma lista JSON aplicando `transform` a cada elemento, sem criar a lista
+/// intermediária de `T`.
+pub(crate) fn parse_list_mapped<T, U, F>(body: &[u8], transform: F) -> Result<Vec<U>, Error>
+where
+    T: DeserializeOwned,
+    F: FnMut(T) -> U,
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+
+    let items = MappedList {
+        transform,
+        marker: PhantomData,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|error| Error::from_json_body_error(body, error))?;
+
+    deserializer.end().map_err(Error::from_json_error)?;
+
+    Ok(items)
+}
+
+/// Posição do leitor de `ArraySplitter` no array JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitterState {
+    /// Antes do `[` inicial.
+    Start,
+    /// Entre dois elementos, ou logo após o `[` quando `expects_item` é falso. Depois de uma
+    /// vírgula, `expects_item` indica que um `]` não é aceito.
+    Between { expects_item: bool },
+    /// Dentro de um elemento, com a profundidade de objetos e arrays abertos.
+    Item {
+        depth: usize,
+        in_string: bool,
+        escaped: bool,
+    },
+    /// Após o `]` final.
+    End,
+}
+
+/// Separa os elementos de um array JSON recebido em partes, sem manter o corpo inteiro em
+/// memória.
+///
+/// Os bytes de cada elemento são retornados assim que ele termina, para serem desserializados
+/// individualmente. Apenas a estrutura do array é verificada aqui; o conteúdo dos elementos é
+/// validado na desserialização.
+#[derive(Debug)]
+pub(crate) struct ArraySplitter {
+    state: SplitterState,
+    item: Vec<u8>,
+    position: usize,
+}
+
+impl ArraySplitter {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: SplitterState::Start,
+            item: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Lê mais uma parte do corpo, retornando os elementos concluídos nela.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+        let mut items = Vec::new();
+
+        for &byte in chunk {
+            self.state = match self.state {
+                SplitterState::Start => match byte {
+                    b'[' => SplitterState::Between {
+                        expects_item: false,
+                    },
+                    _ if byte.is_ascii_whitespace() => SplitterState::Start,
+                    _ => return Err(self.invalid("expected '['")),
+                },
+                SplitterState::Between { expects_item } => match byte {
+                    _ if byte.is_ascii_whitespace() => self.state,
+                    b']' if !expects_item => SplitterState::End,
+                    b']' | b',' => return Err(self.invalid("expected a value")),
+                    _ => self.start_item(byte),
+                },
+                SplitterState::Item {
+                    depth,
+                    in_string: true,
+                    escaped,
+                } => {
+                    self.item.push(byte);
+
+                    SplitterState::Item {
+                        depth,
+                        in_string: escaped || byte != b'"',
+                        escaped: !escaped && byte == b'\\',
+                    }
+                }
+                SplitterState::Item { depth: 0, .. } if matches!(byte, b',' | b']') => {
+                    items.push(std::mem::take(&mut self.item));
+
+                    match byte {
+                        b',' => SplitterState::Between { expects_item: true },
+                        _ => SplitterState::End,
+                    }
+                }
+                SplitterState::Item { depth, .. } => {
+                    self.item.push(byte);
+
+                    let depth = match byte {
+                        b'{' | b'[' => depth + 1,
+                        b'}' | b']' => depth
+                            .checked_sub(1)
+                            .ok_or_else(|| self.invalid("unbalanced brackets"))?,
+                        _ => depth,
+                    };
+
+                    SplitterState::Item {
+                        depth,
+                        in_string: byte == b'"',
+                        escaped: false,
+                    }
+                }
+                SplitterState::End => match byte {
+                    _ if byte.is_ascii_whitespace() => SplitterState::End,
+                    _ => return Err(self.invalid("trailing characters")),
+                },
+            };
+
+            self.position += 1;
+        }
+
+        Ok(items)
+    }
+
+    /// Verifica, ao fim do corpo, se o array foi fechado.
+    pub(crate) fn finish(&self) -> Result<(), Error> {
+        match self.state {
+            SplitterState::End => Ok(()),
+            SplitterState::Start if self.position == 0 => Err(Error::empty_response()),
+            _ => Err(self.invalid("unexpected end of body")),
+        }
+    }
+
+    fn start_item(&mut self, byte: u8) -> SplitterState {
+        self.item.push(byte);
+
+        SplitterState::Item {
+            depth: usize::from(matches!(byte, b'{' | b'[')),
+            in_string: byte == b'"',
+            escaped: false,
+        }
+    }
+
+    fn invalid(&self, reason: &str) -> Error {
+        Error::new(
+            format!(
+                "{INVALID_JSON_ARRAY_MESSAGE}: {reason} at position {}",
+                self.position
+            ),
+            Errored::Deserialization,
+            None,
+        )
+    }
+}
+
+/// Aguarda `request`, lê o corpo e desserializa a lista de acordo com `Config::parse_mode`,
+/// medindo cada etapa separadamente.
+pub(crate) async fn timed_list<T, F>(request: F) -> Result<(Vec<T>, Timings), Error>
+where
+    T: DeserializeOwned + Serialize,
+    F: Future<Output = Result<reqwest::Response, Error>>,
+{
+    let started = Instant::now();
+
+    let response = request.await?;
+    let body = client::read_body_bytes(response).await?;
+
+    let network = started.elapsed();
+    let started = Instant::now();
+
+    let items = client::parse_configured_list(&body)?;
+
+    let timings = Timings {
+        network,
+        parse: started.elapsed(),
+    };
+
+    Ok((items, timings))
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+    use crate::error::Errored;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Item {
+        id: i32,
+    }
+
+    const BODY: &[u8] = br#"[{"id": 1}, {"id": "two"}, {"id": 3}]"#;
+
+    #[test]
+    fn strict_test() {
+        let error = parse_list::<Item>(BODY, ParseMode::Strict).unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[test]
+    fn capture_extras_test() {
+        let parsed = parse_list::<Item>(
+            br#"[{"id": 1, "nome": "SP"}, {"id": 2, "nome": "RJ", "regiao": {"id": 3}}]"#,
+            ParseMode::CaptureExtras,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.get_items(), &[Item { id: 1 }, Item { id: 2 }]);
+        assert_eq!(parsed.get_extras(), &["nome", "regiao"]);
+        assert!(parsed.is_complete());
+
+        let error = parse_list::<Item>(BODY, ParseMode::CaptureExtras).unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[test]
+    fn lenient_test() {
+        let parsed = parse_list::<Item>(BODY, ParseMode::Lenient).unwrap();
+
+        assert_eq!(parsed.get_items(), &[Item { id: 1 }, Item { id: 3 }]);
+        assert_eq!(parsed.get_failures().len(), 1);
+        assert_eq!(parsed.get_failures()[0].get_index(), 1);
+        assert_eq!(
+            parsed.get_failures()[0].get_raw(),
+            &serde_json::json!({"id": "two"})
+        );
+    }
+
+    #[test]
+    fn parse_list_mapped_test() {
+        let ids =
+            parse_list_mapped(br#"[{"id": 1}, {"id": 3}]"#, |item: Item| item.id * 10).unwrap();
+
+        assert_eq!(ids, vec![10, 30]);
+
+        let error = parse_list_mapped(BODY, |item: Item| item.id).unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[tokio::test]
+    async fn timed_list_test() {
+        let server = httpmock::MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/timed");
+                then.status(200)
+                    .body(r#"[{"id": 1}, {"id": 2}]"#)
+                    .delay(Duration::from_millis(20));
+            })
+            .await;
+
+        let (items, timings) = timed_list::<Item, _>(crate::client::get(&server.url("/timed")))
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![Item { id: 1 }, Item { id: 2 }]);
+        assert!(timings.get_network() >= Duration::from_millis(20));
+        assert_eq!(
+            timings.get_total(),
+            timings.get_network() + timings.get_parse()
+        );
+    }
+
+    #[test]
+    fn check_duplicate_keys_test() {
+        let body =
+            br#"[{"id": 1, "regiao": {"id": 3, "sigla": "SE", "sigla": "S"}}, {"id": 2, "id": 3}]"#;
+        let error = check_duplicate_keys(body, DuplicateKeys::Reject).unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+        assert_eq!(
+            error.message,
+            format!("{DUPLICATE_KEY_MESSAGE}: [].regiao.sigla")
+        );
+        assert!(check_duplicate_keys(body, DuplicateKeys::Warn).is_ok());
+        assert!(check_duplicate_keys(body, DuplicateKeys::Allow).is_ok());
+
+        let error = check_duplicate_keys(br#"{"id": 1, "id": 2}"#, DuplicateKeys::Reject);
+
+        assert_eq!(
+            error.unwrap_err().message,
+            format!("{DUPLICATE_KEY_MESSAGE}: id")
+        );
+        assert!(check_duplicate_keys(BODY, DuplicateKeys::Reject).is_ok());
+        assert!(check_duplicate_keys(
+            br#"{"a": {"id": 1}, "b": {"id": 1}}"#,
+            DuplicateKeys::Reject
+        )
+        .is_ok());
+        assert!(check_duplicate_keys(b"not json", DuplicateKeys::Reject).is_ok());
+    }
+
+    #[test]
+    fn lenient_not_a_list_test() {
+        let error = parse_list::<Item>(br#"{"id": 1}"#, ParseMode::Lenient).unwrap_err();
+
+        assert_eq!(error.error, Errored::Deserialization);
+    }
+
+    #[test]
+    fn array_splitter_test() {
+        let body = br#" [{"id": 1, "name": "a \"]\" b"}, [1, {"x": "}"}], 3 , "c,d"] "#;
+        let mut splitter = ArraySplitter::new();
+        let mut items = Vec::new();
+
+        for chunk in body.chunks(3) {
+            items.extend(splitter.push(chunk).unwrap());
+        }
+
+        splitter.finish().unwrap();
+
+        let items: Vec<serde_json::Value> = items
+            .iter()
+            .map(|item| serde_json::from_slice(item).unwrap())
+            .collect();
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0]["name"], r#"a "]" b"#);
+        assert_eq!(items[1][1]["x"], "}");
+        assert_eq!(items[2], 3);
+        assert_eq!(items[3], "c,d");
+
+        let mut empty = ArraySplitter::new();
+
+        assert!(empty.push(b"[ ]").unwrap().is_empty());
+        assert!(empty.finish().is_ok());
+        assert_eq!(
+            ArraySplitter::new().finish().unwrap_err().error,
+            Errored::EmptyResponse
+        );
+
+        for invalid in [&b"{}"[..], b"[1,]", b"[1] 2", b"[,"] {
+            let error = ArraySplitter::new().push(invalid).unwrap_err();
+
+            assert!(error.message.starts_with(INVALID_JSON_ARRAY_MESSAGE));
+        }
+
+        let mut truncated = ArraySplitter::new();
+        truncated.push(br#"[{"id": 1}"#).unwrap();
+
+        assert_eq!(
+            truncated.finish().unwrap_err().error,
+            Errored::Deserialization
+        );
+    }
+}
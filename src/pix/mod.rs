@@ -1,6 +1,14 @@
+use std::collections::HashMap;
+
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-use crate::error::Error;
+use crate::{
+    client,
+    endpoint::ListEndpoint,
+    error::Error,
+    parse::{self, Timings},
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Participant {
@@ -12,6 +20,21 @@ pub struct Participant {
     inicio_operacao: String,
 }
 
+impl Participant {
+    pub fn get_ispb(&self) -> &str {
+        &self.ispb
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.nome
+    }
+
+    pub fn get_short_name(&self) -> &str {
+        &self.nome_reduzido
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PIXService {
     base_url: String,
 }
@@ -19,17 +42,52 @@ pub struct PIXService {
 impl PIXService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
+    fn participants_url(&self) -> String {
+        format!("{}/api/pix/v1/participants", self.base_url)
+    }
+
     async fn get_participant_request(&self) -> Result<reqwest::Response, Error> {
-        let url = format!("{}/api/pix/v1/participants", self.base_url);
+        client::get(&self.participants_url()).await
+    }
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+    async fn fetch_participants_by_ispb(&self) -> Result<HashMap<String, Participant>, Error> {
+        let participants = self.fetch_all().await?;
+
+        Ok(participants
+            .into_iter()
+            .map(|participant| (participant.ispb.clone(), participant))
+            .collect())
+    }
+
+    async fn fetch_all_with_progress<F: Fn(u64)>(
+        &self,
+        on_progress: F,
+    ) -> Result<Vec<Participant>, Error> {
+        let response = client::get_uncached(&self.participants_url()).await?;
+
+        client::read_list_with_progress(response, on_progress).await
+    }
+
+    async fn stream_all(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Participant, Error>> + Send, Error> {
+        let response = client::get_uncached(&self.participants_url()).await?;
+
+        Ok(client::stream_list(response))
+    }
+}
+
+impl ListEndpoint<Participant> for PIXService {
+    async fn fetch_all(&self) -> Result<Vec<Participant>, Error> {
+        let response = self.get_participant_request().await?;
+
+        let participants: Vec<Participant> = client::read_list(response).await?;
+
+        Ok(participants)
     }
 }
 
@@ -49,19 +107,143 @@ impl PIXService {
 /// }
 /// ```
 pub async fn get_participants() -> Result<Vec<Participant>, Error> {
-    let pix_service = PIXService::new("https://brasilapi.com.br");
+    let pix_service = PIXService::new(client::base_url());
+
+    pix_service.fetch_all().await
+}
+
+/// #### `participants_by_ispb()`
+/// Retorna os participantes do PIX indexados pelo ISPB, para converter ISPBs em nomes.
+///
+/// Os participantes raramente mudam ao longo do dia. Com o [cache](crate::cache) ativo, a lista é
+/// baixada uma única vez dentro do tempo de validade e as chamadas seguintes apenas montam o
+/// mapa a partir da resposta armazenada.
+///
+/// ### Retorno
+/// * `Result<HashMap<String, Participant>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{cache, pix};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    cache::enable(Duration::from_secs(3600));
+///
+///    let participants = pix::participants_by_ispb().await.unwrap();
+///
+///    if let Some(participant) = participants.get("00000000") {
+///        println!("{}", participant.get_name());
+///    }
+/// }
+/// ```
+pub async fn participants_by_ispb() -> Result<HashMap<String, Participant>, Error> {
+    let pix_service = PIXService::new(client::base_url());
+
+    pix_service.fetch_participants_by_ispb().await
+}
+
+/// #### `get_participants_timed()`
+/// Igual a `get_participants`, mas também retorna o tempo gasto na requisição e na
+/// desserialização da resposta.
+///
+/// ### Retorno
+/// * `Result<(Vec<Participant>, Timings), Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::pix;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (participants, timings) = pix::get_participants_timed().await.unwrap();
+///
+///    println!("rede: {:?}, desserialização: {:?}", timings.get_network(), timings.get_parse());
+/// }
+/// ```
+pub async fn get_participants_timed() -> Result<(Vec<Participant>, Timings), Error> {
+    let pix_service = PIXService::new(client::base_url());
 
-    let response = pix_service.get_participant_request().await?;
+    parse::timed_list(pix_service.get_participant_request()).await
+}
 
-    let body = response.text().await.unwrap();
-    let participants: Vec<Participant> = serde_json::from_str(&body).unwrap();
+/// #### `get_participants_with_progress(on_progress: F)`
+/// Igual a `get_participants`, chamando `on_progress` com o total de bytes recebidos conforme a
+/// lista é baixada, por exemplo para exibir uma barra de progresso em uma aplicação de terminal.
+/// A requisição não passa pelo cache de `cache::enable`, que entregaria a lista já baixada.
+///
+/// ### Argumento
+/// * `on_progress:F` => Função chamada com o total de bytes recebidos até o momento.
+///
+/// ### Retorno
+/// * `Result<Vec<Participant>, Error>`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::pix;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let participants = pix::get_participants_with_progress(|received| {
+///        eprint!("\r{} KiB", received / 1024);
+///    })
+///    .await
+///    .unwrap();
+/// }
+/// ```
+pub async fn get_participants_with_progress<F>(on_progress: F) -> Result<Vec<Participant>, Error>
+where
+    F: Fn(u64) + Send,
+{
+    let pix_service = PIXService::new(client::base_url());
+
+    pix_service.fetch_all_with_progress(on_progress).await
+}
+
+/// #### `stream_participants()`
+/// Igual a `get_participants`, mas retorna os participantes um a um conforme a resposta é
+/// recebida, sem manter a lista inteira em memória.
+///
+/// A lista de participantes é a maior resposta da API. Ao procurar um único participante, a
+/// leitura pode ser interrompida assim que ele é encontrado, descartando a stream. A requisição
+/// não passa pelo cache de `cache::enable`, que leria a lista inteira antes de retorná-la.
+///
+/// ### Retorno
+/// * `Result<impl Stream<Item = Result<Participant, Error>>, Error>` => Erros da requisição são
+///   retornados antes da stream; erros de leitura ou desserialização encerram a stream após
+///   serem retornados por ela.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::pix;
+/// use futures::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let mut participants = std::pin::pin!(pix::stream_participants().await.unwrap());
+///
+///    while let Some(participant) = participants.next().await {
+///        let participant = participant.unwrap();
+///
+///        if participant.get_ispb() == "00000000" {
+///            println!("{}", participant.get_name());
+///            break;
+///        }
+///    }
+/// }
+/// ```
+pub async fn stream_participants(
+) -> Result<impl Stream<Item = Result<Participant, Error>> + Send, Error> {
+    let pix_service = PIXService::new(client::base_url());
 
-    Ok(participants)
+    pix_service.stream_all().await
 }
 
 #[cfg(test)]
 mod pix_tests {
     use super::*;
+    use httpmock::MockServer;
 
     #[tokio::test]
     async fn test_get_participants() {
@@ -69,4 +251,84 @@ mod pix_tests {
 
         assert!(!participants.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_participants_by_ispb() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/pix/v1/participants");
+                then.status(200).body(
+                    r#"[{"ispb": "00000000", "nome": "BANCO DO BRASIL S.A.", "nome_reduzido": "BCO DO BRASIL S.A.", "modalidade_participacao": "PDCT", "tipo_participacao": "DRCT", "inicio_operacao": "2020-11-03T09:30:00.000Z"}]"#,
+                );
+            })
+            .await;
+
+        let pix_service = PIXService::new(&server.base_url());
+        let participants = pix_service.fetch_participants_by_ispb().await.unwrap();
+
+        assert_eq!(participants.len(), 1);
+        assert_eq!(participants["00000000"].get_name(), "BANCO DO BRASIL S.A.");
+    }
+
+    #[tokio::test]
+    async fn test_participants_with_progress() {
+        let body = r#"[{"ispb": "00000000", "nome": "BANCO DO BRASIL S.A.", "nome_reduzido": "BCO DO BRASIL S.A.", "modalidade_participacao": "PDCT", "tipo_participacao": "DRCT", "inicio_operacao": "2020-11-03T09:30:00.000Z"}]"#;
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/pix/v1/participants");
+                then.status(200).body(body);
+            })
+            .await;
+
+        let received = std::sync::atomic::AtomicU64::new(0);
+        let pix_service = PIXService::new(&server.base_url());
+        let participants = pix_service
+            .fetch_all_with_progress(|bytes| {
+                received.store(bytes, std::sync::atomic::Ordering::Relaxed)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(participants.len(), 1);
+        assert_eq!(received.into_inner(), body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_stream_participants() {
+        use futures::StreamExt;
+
+        let participant = |ispb: &str, name: &str| {
+            format!(
+                r#"{{"ispb": "{ispb}", "nome": "{name}", "nome_reduzido": "{name}", "modalidade_participacao": "PDCT", "tipo_participacao": "DRCT", "inicio_operacao": "2020-11-03T09:30:00.000Z"}}"#
+            )
+        };
+        let body = format!(
+            "[{}, {}]",
+            participant("00000000", r#"BANCO \"[DO]\" BRASIL"#),
+            participant("00360305", "CAIXA, ECONOMICA")
+        );
+
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/pix/v1/participants");
+                then.status(200).body(body);
+            })
+            .await;
+
+        let pix_service = PIXService::new(&server.base_url());
+        let participants: Vec<Participant> = pix_service
+            .stream_all()
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(participants.len(), 2);
+        assert_eq!(participants[0].get_name(), r#"BANCO "[DO]" BRASIL"#);
+        assert_eq!(participants[1].get_ispb(), "00360305");
+    }
 }
@@ -0,0 +1,38 @@
+//! Reexporta os tipos e funções mais usados da biblioteca, para que sejam importados com uma
+//! única linha.
+//!
+//! Os módulos também são reexportados, de forma que as demais funções continuam acessíveis pelo
+//! nome do módulo, por exemplo `ibge::get_all_states`.
+//!
+//! # Exemplo
+//! ```
+//! use brasilapi::prelude::*;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let cep: Cep = get_cep("01001000").await.unwrap();
+//!     let uf = Uf::try_from(cep.state.as_str()).unwrap();
+//!
+//!     println!("{} fica na região {}", cep.city, uf.region());
+//!
+//!     let states: Vec<State> = ibge::get_all_states().await.unwrap();
+//! }
+//! ```
+pub use crate::{
+    bank, cep, client, cnpj, corretoras, cpf, ddd, fipe, holidays, ibge, isbn, ncm, pix,
+    registrobr, retry,
+};
+
+pub use crate::{
+    batch::Batch,
+    cep::{get_cep, Cep},
+    cnpj::{get_cnpj, Cnpj},
+    configure,
+    ddd::{get_ddd, Ddd},
+    error::{Error, Errored},
+    holidays::Holiday,
+    ibge::{get_municipalities, get_state, Municipality, Region, State, Uf},
+    parse::{DuplicateKeys, ParseMode},
+    retry::RetryPolicy,
+    BrasilApi, Config,
+};
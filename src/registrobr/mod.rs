@@ -1,4 +1,4 @@
-use crate::{error::*, spec::BRASIL_API_URL};
+use crate::{client, error::*};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +15,7 @@ pub struct Domain {
     reasons: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone)]
 pub struct RegistroBrService {
     base_url: String,
 }
@@ -22,17 +23,14 @@ pub struct RegistroBrService {
 impl RegistroBrService {
     pub fn new(base_url: &str) -> Self {
         Self {
-            base_url: base_url.to_string(),
+            base_url: client::trim_base_url(base_url).to_string(),
         }
     }
 
     async fn get_domain_by_name(&self, name: &str) -> Result<reqwest::Response, Error> {
         let url = format!("{}/api/registrobr/v1/{}", self.base_url, name);
 
-        match reqwest::get(&url).await {
-            Ok(response) => Error::from_response(response).await,
-            Err(e) => Err(Error::from_error(e)),
-        }
+        client::get(&url).await
     }
 }
 
@@ -55,12 +53,11 @@ impl RegistroBrService {
 /// }
 /// ```
 pub async fn get_domain_by_name(name: &str) -> Result<Domain, Error> {
-    let registro_br_service = RegistroBrService::new(BRASIL_API_URL);
+    let registro_br_service = RegistroBrService::new(client::base_url());
 
     let response = registro_br_service.get_domain_by_name(name).await?;
 
-    let body = response.text().await.unwrap();
-    let domain: Domain = serde_json::from_str(&body).unwrap();
+    let domain: Domain = client::read_json(response).await?;
 
     Ok(domain)
 }
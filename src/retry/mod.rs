@@ -0,0 +1,640 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    client,
+    error::{Error, Errored},
+};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Aguarda entre as tentativas de uma consulta.
+///
+/// A implementação padrão, `TokioSleeper`, usa `tokio::time::sleep`. Testes podem injetar uma
+/// implementação que apenas registra os atrasos pedidos, verificando o backoff sem esperar.
+pub trait Sleeper: Send + Sync {
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// Implementação de `Sleeper` baseada no relógio do tokio.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioSleeper;
+
+impl Sleeper for TokioSleeper {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+/// Critério que indica se um erro deve ser repetido.
+type RetryPredicate = dyn Fn(&Error) -> bool + Send + Sync;
+
+/// Configuração das novas tentativas de uma consulta que falhou com um erro temporário.
+///
+/// O atraso dobra a cada tentativa, começando em `base_delay` e limitado a `max_delay`. Por
+/// padrão, apenas erros para os quais `Error::is_retryable` retorna `true` são repetidos; o
+/// critério pode ser restringido com `should_retry`.
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{error::Errored, retry::RetryPolicy};
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .base_delay(Duration::from_millis(100));
+///
+/// // Repete apenas falhas de conexão, como um reset, e não respostas 5xx.
+/// let network_only = RetryPolicy::new().should_retry(|error| error.error == Errored::Network);
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    /// Critério definido com `should_retry`; `Error::is_retryable` quando `None`.
+    should_retry: Option<Arc<RetryPredicate>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            should_retry: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Duas políticas são iguais quando possuem os mesmos valores e usam o critério padrão ou
+/// compartilham o mesmo critério `should_retry`.
+impl PartialEq for RetryPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_attempts == other.max_attempts
+            && self.base_delay == other.base_delay
+            && self.max_delay == other.max_delay
+            && match (&self.should_retry, &other.should_retry) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+    }
+}
+
+impl Eq for RetryPolicy {}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define o número máximo de tentativas, incluindo a primeira (mínimo de 1).
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Define o atraso antes da segunda tentativa.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Define o maior atraso entre duas tentativas.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Define quais erros são repetidos, substituindo `Error::is_retryable`.
+    pub fn should_retry<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.should_retry = Some(Arc::new(predicate));
+        self
+    }
+
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn get_base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    pub fn get_max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Indica se `error` deve ser repetido, de acordo com `should_retry`.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match &self.should_retry {
+            Some(predicate) => predicate(error),
+            None => error.is_retryable(),
+        }
+    }
+
+    /// Atraso antes da tentativa seguinte à tentativa `attempt` (começando em 1).
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Fichas disponíveis em um `RetryBudget` e o instante da última reposição.
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    refilled_at: Instant,
+}
+
+/// Limite de novas tentativas compartilhado entre todas as consultas, para que uma
+/// indisponibilidade da API não seja agravada por muitas consultas concorrentes repetindo ao
+/// mesmo tempo.
+///
+/// Funciona como um balde de fichas: cada nova tentativa consome uma ficha, e uma ficha é
+/// reposta a cada `refill_interval`, até `max_tokens`. Sem fichas, as consultas falham com o erro
+/// da última tentativa em vez de aguardar e repetir. Os clones compartilham o mesmo balde.
+///
+/// O limite é aplicado por `with_retry` a todas as consultas quando definido em
+/// `Config::retry_budget`.
+///
+/// # Exemplo
+/// ```
+/// use std::time::Duration;
+/// use brasilapi::{retry::RetryBudget, Config};
+///
+/// // Até 10 novas tentativas de uma vez, repondo uma por segundo.
+/// brasilapi::configure(Config {
+///     retry_budget: Some(RetryBudget::new(10, Duration::from_secs(1))),
+///     ..Config::default()
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    max_tokens: u32,
+    refill_interval: Duration,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+/// Dois limites são iguais quando compartilham o mesmo balde.
+impl PartialEq for RetryBudget {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.bucket, &other.bucket)
+    }
+}
+
+impl Eq for RetryBudget {}
+
+impl RetryBudget {
+    /// Cria um limite com `max_tokens` fichas. Com `refill_interval` igual a zero, as fichas
+    /// não são repostas.
+    pub fn new(max_tokens: u32, refill_interval: Duration) -> Self {
+        Self {
+            max_tokens,
+            refill_interval,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: max_tokens,
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn get_max_tokens(&self) -> u32 {
+        self.max_tokens
+    }
+
+    pub fn get_refill_interval(&self) -> Duration {
+        self.refill_interval
+    }
+
+    /// Fichas disponíveis no momento.
+    pub fn available(&self) -> u32 {
+        self.refilled_bucket().tokens
+    }
+
+    /// Consome uma ficha, retornando `false` quando não há fichas disponíveis.
+    pub fn try_acquire(&self) -> bool {
+        let mut bucket = self.refilled_bucket();
+
+        match bucket.tokens {
+            0 => false,
+            _ => {
+                bucket.tokens -= 1;
+                true
+            }
+        }
+    }
+
+    /// O balde após repor as fichas do tempo decorrido desde a última reposição.
+    fn refilled_bucket(&self) -> std::sync::MutexGuard<'_, Bucket> {
+        let mut bucket = self
+            .bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if bucket.tokens >= self.max_tokens || self.refill_interval.is_zero() {
+            bucket.refilled_at = Instant::now();
+            return bucket;
+        }
+
+        let elapsed = bucket.refilled_at.elapsed();
+        let refills = elapsed.as_nanos() / self.refill_interval.as_nanos();
+        let refills = u32::try_from(refills).unwrap_or(u32::MAX);
+
+        if refills > 0 {
+            bucket.tokens = bucket.tokens.saturating_add(refills).min(self.max_tokens);
+            bucket.refilled_at += self.refill_interval.saturating_mul(refills);
+        }
+
+        bucket
+    }
+}
+
+/// #### `with_retry(policy: &RetryPolicy, operation: F)`
+/// Executa `operation` novamente enquanto ela falhar com um erro temporário.
+///
+/// ### Argumentos
+/// * `policy:&RetryPolicy` => Número de tentativas e atrasos.
+/// * `operation:F` => Função que cria a consulta a cada tentativa.
+///
+/// ### Retorno
+/// * `Result<T, Error>` => Resultado da primeira tentativa bem-sucedida, ou o erro da última.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{ibge, retry::{self, RetryPolicy}};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let state = retry::with_retry(&RetryPolicy::new(), || ibge::get_state("SP")).await;
+/// }
+/// ```
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, operation: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    with_retry_using(policy, &TokioSleeper, operation).await
+}
+
+/// #### `with_retry_using(policy: &RetryPolicy, sleeper: &S, operation: F)`
+/// Igual a `with_retry`, usando `sleeper` para aguardar entre as tentativas.
+///
+/// ### Argumentos
+/// * `policy:&RetryPolicy` => Número de tentativas e atrasos.
+/// * `sleeper:&S` => Implementação de `Sleeper`.
+/// * `operation:F` => Função que cria a consulta a cada tentativa.
+///
+/// ### Retorno
+/// * `Result<T, Error>`
+///
+/// Cada nova tentativa consome uma ficha de `Config::retry_budget`, quando definido. Sem fichas,
+/// o erro da última tentativa é retornado imediatamente. Com `Config::retry_deadline`, a chamada
+/// retorna `Errored::DeadlineExceeded` quando o prazo termina durante uma tentativa ou quando o
+/// atraso da nova tentativa o ultrapassaria.
+///
+/// Com a feature `tracing`, cada tentativa é executada em um span `attempt`, filho de um span
+/// `retry` criado no span atual, e eventos de nível `debug` registram o erro e o atraso de cada
+/// nova tentativa, além do resultado final.
+pub async fn with_retry_using<T, S, F, Fut>(
+    policy: &RetryPolicy,
+    sleeper: &S,
+    operation: F,
+) -> Result<T, Error>
+where
+    S: Sleeper,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let limits = RetryLimits {
+        budget: client::retry_budget(),
+        deadline: client::retry_deadline(),
+    };
+
+    retry_with_limits(policy, sleeper, limits, operation).await
+}
+
+/// Limites da configuração global aplicados a uma chamada de `with_retry`.
+#[derive(Debug, Clone, Copy)]
+struct RetryLimits<'a> {
+    budget: Option<&'a RetryBudget>,
+    deadline: Option<Duration>,
+}
+
+/// Aguarda `future` até `deadline`, retornando `Errored::DeadlineExceeded` se o prazo terminar
+/// antes.
+async fn until_deadline<T, Fut>(deadline: Option<Instant>, future: Fut) -> Result<T, Error>
+where
+    Fut: Future<Output = Result<T, Error>>,
+{
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.into(), future)
+            .await
+            .unwrap_or_else(|_| Err(Error::deadline_exceeded(None))),
+        None => future.await,
+    }
+}
+
+async fn retry_with_limits<T, S, F, Fut>(
+    policy: &RetryPolicy,
+    sleeper: &S,
+    limits: RetryLimits<'_>,
+    mut operation: F,
+) -> Result<T, Error>
+where
+    S: Sleeper,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let deadline = limits.deadline.map(|deadline| Instant::now() + deadline);
+    let exceeds_deadline =
+        |delay: Duration| deadline.is_some_and(|deadline| Instant::now() + delay >= deadline);
+    let mut attempt = 1;
+
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!("retry", max_attempts = policy.max_attempts);
+
+    loop {
+        let future = operation();
+
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(
+            future,
+            tracing::debug_span!(parent: &span, "attempt", attempt),
+        );
+
+        match until_deadline(deadline, future).await {
+            Err(error)
+                if error.error != Errored::DeadlineExceeded
+                    && policy.is_retryable(&error)
+                    && attempt < policy.max_attempts
+                    && exceeds_deadline(policy.delay_after(attempt)) =>
+            {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    parent: &span,
+                    attempt,
+                    kind = ?error.error,
+                    error = %error.message,
+                    "attempt failed, retry deadline exceeded"
+                );
+
+                return Err(Error::deadline_exceeded(Some(&error)));
+            }
+            Err(error)
+                if policy.is_retryable(&error)
+                    && attempt < policy.max_attempts
+                    && limits.budget.is_none_or(RetryBudget::try_acquire) =>
+            {
+                let delay = policy.delay_after(attempt);
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    parent: &span,
+                    attempt,
+                    ?delay,
+                    kind = ?error.error,
+                    error = %error.message,
+                    "attempt failed, retrying"
+                );
+
+                sleeper.sleep(delay).await;
+                attempt += 1;
+            }
+            result => {
+                #[cfg(feature = "tracing")]
+                match &result {
+                    Ok(_) => tracing::debug!(parent: &span, attempt, "attempt succeeded"),
+                    Err(error) => tracing::debug!(
+                        parent: &span,
+                        attempt,
+                        kind = ?error.error,
+                        error = %error.message,
+                        "attempt failed, giving up"
+                    ),
+                }
+
+                return result;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod retry_tests {
+    use super::*;
+    use crate::error::DEADLINE_EXCEEDED_MESSAGE;
+
+    #[derive(Default)]
+    struct RecordingSleeper {
+        delays: Mutex<Vec<Duration>>,
+    }
+
+    impl Sleeper for RecordingSleeper {
+        async fn sleep(&self, duration: Duration) {
+            self.delays.lock().unwrap().push(duration);
+        }
+    }
+
+    fn server_error() -> Error {
+        Error::new("".to_string(), Errored::InternalServerError, Some(500))
+    }
+
+    #[test]
+    fn delay_after_test() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(300));
+
+        assert_eq!(policy.delay_after(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_after(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_after(3), Duration::from_millis(300));
+        assert_eq!(policy.delay_after(40), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn retries_until_success_test() {
+        let sleeper = RecordingSleeper::default();
+        let mut calls = 0;
+
+        let result = with_retry_using(&RetryPolicy::new().max_attempts(4), &sleeper, || {
+            calls += 1;
+            let attempt = calls;
+
+            async move {
+                match attempt {
+                    1 | 2 => Err(server_error()),
+                    _ => Ok(attempt),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(
+            *sleeper.delays.lock().unwrap(),
+            vec![Duration::from_millis(200), Duration::from_millis(400)]
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_test() {
+        let sleeper = RecordingSleeper::default();
+        let mut calls = 0;
+
+        let result: Result<(), Error> =
+            with_retry_using(&RetryPolicy::new().max_attempts(2), &sleeper, || {
+                calls += 1;
+                async { Err(server_error()) }
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code, Some(500));
+        assert_eq!(calls, 2);
+        assert_eq!(sleeper.delays.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors_test() {
+        let sleeper = RecordingSleeper::default();
+        let mut calls = 0;
+
+        let result: Result<(), Error> = with_retry_using(&RetryPolicy::new(), &sleeper, || {
+            calls += 1;
+            async { Err(Error::invalid_input("invalid")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+        assert!(sleeper.delays.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_budget_test() {
+        let sleeper = RecordingSleeper::default();
+        let budget = RetryBudget::new(3, Duration::ZERO);
+        let mut calls = 0;
+
+        for _ in 0..2 {
+            let limits = RetryLimits {
+                budget: Some(&budget),
+                deadline: None,
+            };
+            let result: Result<(), Error> = retry_with_limits(
+                &RetryPolicy::new().max_attempts(3),
+                &sleeper,
+                limits,
+                || {
+                    calls += 1;
+                    async { Err(server_error()) }
+                },
+            )
+            .await;
+
+            assert_eq!(result.unwrap_err().code, Some(500));
+        }
+
+        // A primeira consulta repete duas vezes e a segunda apenas uma, esgotando o limite.
+        assert_eq!(calls, 5);
+        assert_eq!(sleeper.delays.lock().unwrap().len(), 3);
+        assert_eq!(budget.available(), 0);
+        assert!(!budget.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn retry_deadline_test() {
+        let sleeper = RecordingSleeper::default();
+        let limits = RetryLimits {
+            budget: None,
+            deadline: Some(Duration::from_millis(300)),
+        };
+        let mut calls = 0;
+
+        // O atraso após a segunda tentativa, de 400ms, ultrapassaria o prazo.
+        let result: Result<(), Error> = retry_with_limits(
+            &RetryPolicy::new().max_attempts(5),
+            &sleeper,
+            limits,
+            || {
+                calls += 1;
+                async { Err(server_error()) }
+            },
+        )
+        .await;
+        let error = result.unwrap_err();
+
+        assert_eq!(error.error, Errored::DeadlineExceeded);
+        assert_eq!(error.message, format!("{DEADLINE_EXCEEDED_MESSAGE}: "));
+        assert_eq!(calls, 2);
+        assert_eq!(sleeper.delays.lock().unwrap().len(), 1);
+
+        let limits = RetryLimits {
+            budget: None,
+            deadline: Some(Duration::from_millis(20)),
+        };
+        let result: Result<(), Error> =
+            retry_with_limits(&RetryPolicy::new(), &TokioSleeper, limits, || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().message, DEADLINE_EXCEEDED_MESSAGE);
+    }
+
+    #[test]
+    fn retry_budget_refill_test() {
+        let budget = RetryBudget::new(2, Duration::from_millis(10));
+
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(25));
+
+        assert_eq!(budget.available(), 2);
+        assert_eq!(budget, budget.clone());
+        assert_ne!(budget, RetryBudget::new(2, Duration::from_millis(10)));
+    }
+
+    #[tokio::test]
+    async fn should_retry_test() {
+        let sleeper = RecordingSleeper::default();
+        let policy = RetryPolicy::new().should_retry(|error| error.error == Errored::Network);
+        let mut calls = 0;
+
+        let result: Result<(), Error> = with_retry_using(&policy, &sleeper, || {
+            calls += 1;
+            async { Err(server_error()) }
+        })
+        .await;
+
+        assert_eq!(result.unwrap_err().code, Some(500));
+        assert_eq!(calls, 1);
+        assert!(policy.is_retryable(&Error::new(String::new(), Errored::Network, None)));
+        assert_eq!(policy.clone(), policy);
+        assert_ne!(policy, RetryPolicy::new());
+        assert_eq!(RetryPolicy::new(), RetryPolicy::default());
+    }
+}
@@ -0,0 +1,103 @@
+//! Testes de desserialização com as respostas salvas em `tests/fixtures/`.
+//!
+//! Cada tipo modelado é desserializado a partir de uma resposta da BrasilAPI e todos os seus
+//! getters são conferidos, para que mudanças no nome ou no tipo de um campo sejam detectadas
+//! pelos testes antes de chegar aos usuários.
+use crate::{
+    bank::Bank,
+    cep::Cep,
+    fipe::ReferenceTable,
+    fixtures,
+    ibge::{Municipality, State, Uf},
+};
+
+#[tokio::test]
+async fn state_sample_test() {
+    let fixture = fixtures::load("ibge_uf_sp", "/api/ibge/uf/v1/SP").await;
+    let state: State = serde_json::from_str(&fixture).unwrap();
+
+    assert_eq!(state.get_sigla(), "SP");
+    assert_eq!(state.get_uf(), Uf::Sp);
+    assert_eq!(state.get_name(), "São Paulo");
+    assert_eq!(state.get_region().get_sigla(), "SE");
+    assert_eq!(state.get_region().get_name(), "Sudeste");
+    assert_eq!(state.to_english().id, 35);
+    assert_eq!(state.get_region().to_english().id, 3);
+}
+
+#[tokio::test]
+async fn states_sample_test() {
+    let fixture = fixtures::load("ibge_uf", "/api/ibge/uf/v1").await;
+    let states: Vec<State> = serde_json::from_str(&fixture).unwrap();
+
+    assert_eq!(states.len(), 27);
+
+    for state in &states {
+        let english = state.to_english();
+
+        assert_eq!(state.get_uf().get_ibge_code(), english.id.to_string());
+        assert!(!state.get_name().is_empty());
+        assert!((1..=5).contains(&english.region.id));
+    }
+}
+
+#[tokio::test]
+async fn municipalities_sample_test() {
+    let fixture = fixtures::load("ibge_municipios_rr", "/api/ibge/municipios/v1/RR").await;
+    let municipalities: Vec<Municipality> = serde_json::from_str(&fixture).unwrap();
+
+    assert_eq!(municipalities.len(), 15);
+
+    for municipality in &municipalities {
+        assert!(!municipality.get_name().is_empty());
+        assert_eq!(municipality.get_ibge_code().len(), 7);
+        assert!(municipality
+            .get_ibge_code()
+            .starts_with(Uf::Rr.get_ibge_code()));
+    }
+
+    let boa_vista = municipalities
+        .iter()
+        .find(|municipality| municipality.get_ibge_code() == "1400100")
+        .unwrap();
+
+    assert_eq!(boa_vista.get_name(), "BOA VISTA");
+    assert_eq!(boa_vista.to_english().name, "BOA VISTA");
+}
+
+#[tokio::test]
+async fn cep_sample_test() {
+    let fixture = fixtures::load("cep_01001000", "/api/cep/v2/01001000").await;
+    let cep: Cep = serde_json::from_str(&fixture).unwrap();
+
+    assert_eq!(cep.cep, "01001000");
+    assert_eq!(cep.state, "SP");
+    assert_eq!(cep.city, "São Paulo");
+    assert_eq!(cep.neighborhood, "Sé");
+    assert_eq!(cep.street, "Praça da Sé");
+    assert!(!cep.service.is_empty());
+}
+
+#[tokio::test]
+async fn bank_sample_test() {
+    let fixture = fixtures::load("bank_1", "/api/banks/v1/1").await;
+    let bank: Bank = serde_json::from_str(&fixture).unwrap();
+
+    assert_eq!(bank.ispb, "00000000");
+    assert_eq!(bank.name.as_deref(), Some("BCO DO BRASIL S.A."));
+    assert_eq!(bank.code, Some(1));
+    assert_eq!(bank.fullname.as_deref(), Some("Banco do Brasil S.A."));
+}
+
+#[tokio::test]
+async fn reference_tables_sample_test() {
+    let fixture = fixtures::load("fipe_tabelas", "/api/fipe/tabelas/v1").await;
+    let tables: Vec<ReferenceTable> = serde_json::from_str(&fixture).unwrap();
+
+    assert!(!tables.is_empty());
+
+    for table in &tables {
+        assert!(table.get_code() > 0);
+        assert!(table.get_year_month().is_some(), "{}", table.get_month());
+    }
+}
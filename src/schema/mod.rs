@@ -0,0 +1,330 @@
+//! Detecção de mudanças no formato das respostas da BrasilAPI.
+//!
+//! [`check`] consulta uma amostra de cada endpoint e compara os campos recebidos com os modelos
+//! da biblioteca, para que uma mudança na API seja percebida antes de causar erros em produção.
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    bank::Bank, cep::Cep, client, cnpj::Cnpj, corretoras::Corretora, ddd::Ddd, error::*,
+    fipe::ReferenceTable, holidays::Holiday, ibge::Municipality, ibge::State, isbn::Book, ncm::Ncm,
+    pix::Participant, registrobr::Domain,
+};
+
+/// Compara um corpo recebido com um modelo.
+type Check = fn(&Value) -> Vec<(String, DriftKind)>;
+
+/// Endpoints consultados por `check`, com o modelo usado para cada um deles.
+const ENDPOINTS: &[(&str, Check)] = &[
+    ("/api/banks/v1", compare::<Bank>),
+    ("/api/cep/v2/01001000", compare::<Cep>),
+    ("/api/cnpj/v1/00000000000191", compare::<Cnpj>),
+    ("/api/cvm/corretoras/v1", compare::<Corretora>),
+    ("/api/ddd/v1/11", compare::<Ddd>),
+    ("/api/fipe/tabelas/v1", compare::<ReferenceTable>),
+    ("/api/feriados/v1/2023", compare::<Holiday>),
+    ("/api/ibge/uf/v1/SP", compare::<State>),
+    ("/api/ibge/municipios/v1/RR", compare::<Municipality>),
+    ("/api/isbn/v1/9788545702870", compare::<Book>),
+    ("/api/ncm/v1/3305.10.00", compare::<Ncm>),
+    ("/api/pix/v1/participants", compare::<Participant>),
+    ("/api/registrobr/v1/google.com", compare::<Domain>),
+];
+
+/// Tipo de divergência entre uma resposta e o modelo correspondente.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftKind {
+    /// A resposta não pôde ser desserializada no modelo. Contém a mensagem do serde, por exemplo
+    /// ``missing field `nome` ``.
+    Incompatible(String),
+    /// O modelo espera o campo, mas ele não veio na resposta.
+    Missing,
+    /// A resposta contém um campo que o modelo não possui.
+    Unmodeled,
+}
+
+/// Divergência encontrada em um campo de um endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    endpoint: String,
+    field: String,
+    kind: DriftKind,
+}
+
+impl Drift {
+    /// Caminho do endpoint consultado, por exemplo `/api/ibge/uf/v1/SP`.
+    pub fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Caminho do campo, com os níveis separados por `.` e os elementos de listas indicados por
+    /// `[]`, por exemplo `regiao.sigla` ou `qsa[].nome`. Vazio quando a divergência é
+    /// `DriftKind::Incompatible`, que se refere ao corpo inteiro.
+    pub fn get_field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn get_kind(&self) -> &DriftKind {
+        &self.kind
+    }
+}
+
+/// Resultado de `check`.
+#[derive(Debug)]
+pub struct SchemaReport {
+    drifts: Vec<Drift>,
+    failures: Vec<(String, Error)>,
+}
+
+impl SchemaReport {
+    /// Divergências encontradas, na ordem dos endpoints.
+    pub fn get_drifts(&self) -> &[Drift] {
+        &self.drifts
+    }
+
+    /// Endpoints que não puderam ser consultados, com o erro de cada um.
+    pub fn get_failures(&self) -> &[(String, Error)] {
+        &self.failures
+    }
+
+    /// `true` se todos os endpoints foram consultados e nenhuma divergência foi encontrada.
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty() && self.failures.is_empty()
+    }
+}
+
+/// Compara `raw` com o modelo `T`. Uma lista tem cada elemento comparado com `T`, e cada
+/// divergência é registrada uma única vez.
+fn compare<T: DeserializeOwned + Serialize>(raw: &Value) -> Vec<(String, DriftKind)> {
+    let mut drifts = Vec::new();
+
+    match raw {
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| compare_item::<T>(item, &mut drifts)),
+        _ => compare_item::<T>(raw, &mut drifts),
+    }
+
+    drifts
+}
+
+/// O item é desserializado em `T` e serializado novamente, de forma que os campos do modelo
+/// possam ser comparados com os recebidos.
+fn compare_item<T: DeserializeOwned + Serialize>(
+    raw: &Value,
+    drifts: &mut Vec<(String, DriftKind)>,
+) {
+    let modeled = serde_json::from_value::<T>(raw.clone())
+        .map_err(|error| error.to_string())
+        .and_then(|model| serde_json::to_value(model).map_err(|error| error.to_string()));
+
+    match modeled {
+        Ok(modeled) => compare_fields("", raw, &modeled, drifts),
+        Err(message) => push_drift(drifts, String::new(), DriftKind::Incompatible(message)),
+    }
+}
+
+/// Um campo do modelo com valor `null` é tratado como opcional e não é exigido na resposta.
+fn compare_fields(path: &str, raw: &Value, modeled: &Value, drifts: &mut Vec<(String, DriftKind)>) {
+    let join = |key: &str| match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    };
+
+    match (raw, modeled) {
+        (Value::Object(raw), Value::Object(modeled)) => {
+            for (key, value) in raw {
+                match modeled.get(key) {
+                    Some(expected) => compare_fields(&join(key), value, expected, drifts),
+                    None => push_drift(drifts, join(key), DriftKind::Unmodeled),
+                }
+            }
+
+            for (key, expected) in modeled {
+                if !expected.is_null() && !raw.contains_key(key) {
+                    push_drift(drifts, join(key), DriftKind::Missing);
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(modeled)) => {
+            let path = format!("{path}[]");
+
+            for (value, expected) in raw.iter().zip(modeled) {
+                compare_fields(&path, value, expected, drifts);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Campos de `raw` que não existem em `modeled`, usado por `ParseMode::CaptureExtras`.
+pub(crate) fn unmodeled_fields(raw: &Value, modeled: &Value) -> Vec<String> {
+    let mut drifts = Vec::new();
+
+    compare_fields("", raw, modeled, &mut drifts);
+
+    drifts
+        .into_iter()
+        .filter(|(_, kind)| *kind == DriftKind::Unmodeled)
+        .map(|(field, _)| field)
+        .collect()
+}
+
+fn push_drift(drifts: &mut Vec<(String, DriftKind)>, field: String, kind: DriftKind) {
+    let drift = (field, kind);
+
+    if !drifts.contains(&drift) {
+        drifts.push(drift);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaService {
+    base_url: String,
+}
+
+impl SchemaService {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: client::trim_base_url(base_url).to_string(),
+        }
+    }
+
+    async fn fetch_sample(&self, path: &str) -> Result<Value, Error> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = client::get(&url).await?;
+
+        client::read_json(response).await
+    }
+
+    async fn check_endpoints(&self, endpoints: &[(&str, Check)]) -> SchemaReport {
+        let requests = endpoints.iter().map(|(path, check)| async move {
+            (*path, self.fetch_sample(path).await.map(|raw| check(&raw)))
+        });
+        let mut report = SchemaReport {
+            drifts: Vec::new(),
+            failures: Vec::new(),
+        };
+
+        for (path, result) in futures::future::join_all(requests).await {
+            match result {
+                Ok(drifts) => report
+                    .drifts
+                    .extend(drifts.into_iter().map(|(field, kind)| Drift {
+                        endpoint: path.to_string(),
+                        field,
+                        kind,
+                    })),
+                Err(error) => report.failures.push((path.to_string(), error)),
+            }
+        }
+
+        report
+    }
+}
+
+/// #### `check()`
+/// Consulta uma amostra de cada endpoint da BrasilAPI, por exemplo o CEP `01001000` e a UF `SP`,
+/// e compara os campos recebidos com os modelos da biblioteca.
+///
+/// Campos recebidos que o modelo não possui são informados como `DriftKind::Unmodeled`, campos
+/// obrigatórios do modelo ausentes na resposta como `DriftKind::Missing` e respostas que não
+/// podem ser desserializadas como `DriftKind::Incompatible`. Os endpoints são consultados ao
+/// mesmo tempo, e um endpoint que falhe é registrado em `get_failures` sem interromper os demais.
+///
+/// ### Retorno
+/// * `SchemaReport`
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::schema;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let report = schema::check().await;
+///
+///    for drift in report.get_drifts() {
+///        println!("{} {} {:?}", drift.get_endpoint(), drift.get_field(), drift.get_kind());
+///    }
+/// }
+/// ```
+pub async fn check() -> SchemaReport {
+    let schema_service = SchemaService::new(client::base_url());
+
+    schema_service.check_endpoints(ENDPOINTS).await
+}
+
+#[cfg(test)]
+mod schema_tests {
+    use super::*;
+    use httpmock::MockServer;
+    use serde_json::json;
+
+    #[test]
+    fn test_compare() {
+        let state = json!({
+            "id": 35,
+            "sigla": "SP",
+            "nome": "São Paulo",
+            "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste", "codigo": "3"},
+            "capital": "São Paulo"
+        });
+
+        assert_eq!(
+            compare::<State>(&state),
+            vec![
+                ("capital".to_string(), DriftKind::Unmodeled),
+                ("regiao.codigo".to_string(), DriftKind::Unmodeled),
+            ]
+        );
+
+        let missing = json!({"id": 35, "nome": "São Paulo", "regiao": {}});
+        let drifts = compare::<State>(&missing);
+
+        assert_eq!(drifts.len(), 1);
+        assert!(matches!(drifts[0], (ref field, DriftKind::Incompatible(_)) if field.is_empty()));
+
+        let list = json!([
+            {"date": "2023-01-01", "type": "national", "name": "Confraternização mundial", "level": 1},
+            {"date": "2023-04-21", "type": "national", "name": "Tiradentes", "level": 1}
+        ]);
+
+        assert_eq!(
+            compare::<Holiday>(&list),
+            vec![("level".to_string(), DriftKind::Unmodeled)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_endpoints() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ddd/v1/11");
+                then.status(200)
+                    .body(r#"{"state": "SP", "cities": ["SÃO PAULO"], "country": "BR"}"#);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/ncm/v1/3305.10.00");
+                then.status(500).body("{}");
+            })
+            .await;
+
+        let schema_service = SchemaService::new(&server.base_url());
+        let endpoints: &[(&str, Check)] = &[
+            ("/api/ddd/v1/11", compare::<Ddd>),
+            ("/api/ncm/v1/3305.10.00", compare::<Ncm>),
+        ];
+        let report = schema_service.check_endpoints(endpoints).await;
+
+        assert!(!report.is_clean());
+        assert_eq!(report.get_drifts().len(), 1);
+        assert_eq!(report.get_drifts()[0].get_endpoint(), "/api/ddd/v1/11");
+        assert_eq!(report.get_drifts()[0].get_field(), "country");
+        assert_eq!(report.get_drifts()[0].get_kind(), &DriftKind::Unmodeled);
+        assert_eq!(report.get_failures().len(), 1);
+        assert_eq!(report.get_failures()[0].0, "/api/ncm/v1/3305.10.00");
+    }
+}
@@ -0,0 +1,171 @@
+//! Verificação, em tempo de compilação, de que os futures das funções públicas são `Send`.
+//!
+//! Os futures são apenas criados, sem serem executados, portanto nenhuma requisição é feita. Um
+//! tipo que não seja `Send` mantido entre dois `.await` de alguma função faz estes testes
+//! deixarem de compilar, impedindo que a função deixe de funcionar com `tokio::spawn`.
+use std::time::Duration;
+
+use crate::{
+    bank,
+    batch::{Batch, ProgressAggregator},
+    cep, client, cnpj, corretoras, ddd, fipe, holidays, ibge,
+    ibge::{MunicipalitiesProvider, ProviderSet, Uf},
+    isbn, ncm,
+    parse::ParseMode,
+    pix, registrobr, retry, schema, BrasilApi, Config,
+};
+
+fn assert_send<T: Send>(_: T) {}
+
+#[test]
+fn bank_futures_are_send() {
+    assert_send(bank::get_all_banks());
+    assert_send(bank::get_bank(1));
+    assert_send(bank::find_by_ispb("00000000"));
+    assert_send(bank::index());
+}
+
+#[test]
+fn cep_futures_are_send() {
+    assert_send(cep::get_cep("01001000"));
+    assert_send(cep::lookup_many(&["01001000"], 2));
+    assert_send(cep::validate("01001000"));
+    assert_send(cep::resolve_location("01001000"));
+    assert_send(cep::resolve_address("01001000"));
+    assert_send(cep::belongs_to_uf("01001000", "SP"));
+}
+
+#[test]
+fn client_futures_are_send() {
+    assert_send(BrasilApi::connect(Config::default()));
+    assert_send(client::with_timeout(
+        Duration::from_secs(1),
+        ibge::get_state("SP"),
+    ));
+    assert_send(client::with_correlation_id("id", ibge::get_state("SP")));
+    assert_send(client::get_bytes("/api/ibge/uf/v1"));
+    assert_send(client::get_bytes_with_progress("/api/ibge/uf/v1", |_| {}));
+}
+
+#[test]
+fn module_futures_are_send() {
+    assert_send(cnpj::get_cnpj("00000000000191"));
+    assert_send(corretoras::get_corretoras());
+    assert_send(corretoras::get_corretora("02332886000104"));
+    assert_send(ddd::get_ddd("61"));
+    assert_send(ddd::ddd_exists("61"));
+    assert_send(ddd::resolve_state("61"));
+    assert_send(ddd::get_municipalities_with_ddd("GO", None));
+    assert_send(fipe::get_brands(fipe::VehicleType::Car, None));
+    assert_send(fipe::get_models(fipe::VehicleType::Car, "21"));
+    assert_send(fipe::get_vehicles("001004-9", None));
+    assert_send(fipe::get_reference_tables());
+    assert_send(fipe::reference_table_for(2023, 1));
+    assert_send(holidays::get_holidays("2023"));
+    assert_send(holidays::get_holiday("2023", "01", "01"));
+    assert_send(holidays::get_range(2023, 2025));
+    assert_send(isbn::get_isbn("9788545702870"));
+    assert_send(ncm::get_all_ncm());
+    assert_send(ncm::stream_all_ncm());
+    assert_send(ncm::get_ncm("33051000"));
+    assert_send(ncm::search_ncm("xampus"));
+    assert_send(pix::get_participants());
+    assert_send(pix::participants_by_ispb());
+    assert_send(pix::get_participants_timed());
+    assert_send(pix::get_participants_with_progress(|_| {}));
+    assert_send(pix::stream_participants());
+    assert_send(registrobr::get_domain_by_name("google.com"));
+    assert_send(schema::check());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn holiday_date_futures_are_send() {
+    let date = chrono::NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+
+    assert_send(holidays::next_holiday(date));
+    assert_send(holidays::is_holiday(date));
+    assert_send(holidays::is_holiday_detail(date));
+    assert_send(holidays::add_business_days(date, 1));
+}
+
+#[test]
+fn ibge_futures_are_send() {
+    let batch = Batch::new();
+    let progress = ProgressAggregator::default();
+    let mut writer = Vec::new();
+    let mut progress_writer = Vec::new();
+
+    assert_send(ibge::get_municipalities("SP", None));
+    assert_send(ibge::get_municipalities_by_uf(Uf::Sp, None));
+    assert_send(ibge::get_municipalities_mapped(
+        "SP",
+        None,
+        |municipality| municipality.get_name().len(),
+    ));
+    assert_send(ibge::get_municipality_pairs("SP", None));
+    assert_send(ibge::get_municipalities_with_mode(
+        "SP",
+        None,
+        ParseMode::Lenient,
+    ));
+    assert_send(ibge::get_municipalities_timed("SP", None));
+    assert_send(ibge::get_municipalities_with_progress("SP", None, |_| {}));
+    assert_send(ibge::get_municipality("3550308"));
+    assert_send(ibge::get_municipalities_preferring(
+        "SP",
+        MunicipalitiesProvider::Gov,
+        MunicipalitiesProvider::DadosAbertos,
+    ));
+    assert_send(ibge::get_municipalities_resilient("SP", ProviderSet::all()));
+    assert_send(ibge::provider_disagreements("SP"));
+    assert_send(ibge::municipality_counts_by_provider("SP"));
+    assert_send(ibge::get_municipalities_per_provider("SP", None));
+    assert_send(ibge::get_municipality_names("SP", None));
+    assert_send(ibge::municipalities_missing_codes("SP", None));
+    assert_send(ibge::find_code_collisions("SP", None));
+    assert_send(ibge::export_state("SP", None));
+    assert_send(ibge::export_all_municipalities_ndjson(&mut writer, None));
+    assert_send(ibge::export_all_municipalities_ndjson_with_progress(
+        &mut progress_writer,
+        None,
+        &progress,
+    ));
+    assert_send(ibge::state_summaries(&batch));
+    assert_send(ibge::get_all_municipalities(None, &batch));
+    assert_send(ibge::get_municipalities_bulk(&[Uf::Sp, Uf::Rj], None));
+    assert_send(ibge::build_municipality_index("SP", None));
+    assert_send(ibge::find_municipality_by_state_and_name(
+        "SP",
+        "São Paulo",
+        None,
+    ));
+    assert_send(ibge::find_municipalities_by_name(
+        "Bom Jesus",
+        ibge::MunicipalityOrder::Uf,
+        &batch,
+    ));
+    assert_send(ibge::get_all_states());
+    assert_send(ibge::validate_region_assignments(&Default::default()));
+    assert_send(ibge::get_all_states_checked(ibge::InvariantCheck::Reject));
+    assert_send(ibge::get_states_map());
+    assert_send(ibge::get_all_states_grouped());
+    assert_send(ibge::get_regions());
+    assert_send(ibge::get_state("SP"));
+    assert_send(ibge::get_states(&["SP", "RJ"]));
+    assert_send(ibge::get_state_with_headers("SP"));
+    assert_send(ibge::get_all_states_with_headers());
+    assert_send(ibge::get_municipalities_with_headers("SP", None));
+}
+
+#[test]
+fn retry_futures_are_send() {
+    let policy = retry::RetryPolicy::default();
+
+    assert_send(retry::with_retry(&policy, || ibge::get_state("SP")));
+    assert_send(retry::with_retry_using(
+        &policy,
+        &retry::TokioSleeper,
+        || ibge::get_state("SP"),
+    ));
+}
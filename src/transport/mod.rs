@@ -0,0 +1,377 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+#[cfg(feature = "cassette")]
+use std::{path::PathBuf, sync::Mutex};
+
+#[cfg(feature = "cassette")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "cassette")]
+use crate::error::{Errored, CASSETTE_MISS_MESSAGE};
+use crate::{client, error::Error};
+
+/// #### `HttpTransport`
+/// Executa as requisições `GET` feitas pela biblioteca.
+///
+/// Por padrão a biblioteca usa o reqwest. Ambientes que não podem usá-lo registram outra
+/// implementação com `set_transport`, por exemplo baseada no ureq ou no isahc.
+///
+/// ### Retorno
+/// * `Result<(u16, String), Error>` => Status code e corpo da resposta.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::{error::Error, ibge, transport::{self, HttpTransport}};
+///
+/// struct Offline;
+///
+/// impl HttpTransport for Offline {
+///     async fn get(&self, url: &str) -> Result<(u16, String), Error> {
+///         assert!(url.ends_with("/api/ibge/uf/v1/SP"));
+///
+///         let body = r#"{"id": 35, "sigla": "SP", "nome": "São Paulo", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}}"#;
+///
+///         Ok((200, body.to_string()))
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     transport::set_transport(Offline);
+///
+///     let state = ibge::get_state("SP").await.unwrap();
+///
+///     assert_eq!(state.get_name(), "São Paulo");
+/// }
+/// ```
+pub trait HttpTransport: Send + Sync + 'static {
+    fn get(&self, url: &str) -> impl Future<Output = Result<(u16, String), Error>> + Send;
+}
+
+/// Implementação padrão, que usa o cliente reqwest compartilhado pela biblioteca.
+///
+/// Útil para transportes que apenas complementam o padrão, por exemplo registrando as URLs
+/// consultadas antes de delegar a requisição.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReqwestTransport;
+
+impl HttpTransport for ReqwestTransport {
+    async fn get(&self, url: &str) -> Result<(u16, String), Error> {
+        let response = client::http_client()?
+            .get(url)
+            .send()
+            .await
+            .map_err(Error::from_error)?;
+
+        let status = response.status().as_u16();
+        let body = client::read_body(response).await?;
+
+        Ok((status, body))
+    }
+}
+
+/// Requisição e resposta gravadas por um `Cassette`.
+#[cfg(feature = "cassette")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Interaction {
+    pub url: String,
+    pub status: u16,
+    pub body: String,
+}
+
+/// Modo de operação de um `Cassette`.
+#[cfg(feature = "cassette")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Executa as requisições com o reqwest e grava cada interação no arquivo.
+    Record,
+    /// Responde apenas com as interações já gravadas, sem acessar a rede.
+    Replay,
+}
+
+/// #### `Cassette`
+/// Transporte que grava as requisições e respostas reais em um arquivo JSON e depois as
+/// reproduz, no estilo do VCR, tornando testes e exemplos determinísticos e offline.
+///
+/// Disponível com a feature `cassette`. No modo `CassetteMode::Record` o arquivo é reescrito a
+/// cada requisição, mantendo uma única interação por URL. No modo `CassetteMode::Replay` uma URL
+/// sem interação gravada retorna um erro `Errored::Unexpected` com a mensagem
+/// `CASSETTE_MISS_MESSAGE`.
+///
+/// # Exemplo
+/// ```no_run
+/// use brasilapi::{ibge, transport::{self, Cassette}};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Primeira execução: grava as respostas da API.
+///     transport::set_transport(Cassette::record("tests/cassettes/ibge.json"));
+///     ibge::get_state("SP").await.unwrap();
+///
+///     // Demais execuções: reproduz as respostas gravadas.
+///     transport::set_transport(Cassette::replay("tests/cassettes/ibge.json").unwrap());
+///     let state = ibge::get_state("SP").await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "cassette")]
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+#[cfg(feature = "cassette")]
+impl Cassette {
+    /// Cria um cassete vazio que gravará as interações em `path`.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: CassetteMode::Record,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Carrega as interações gravadas em `path` para reprodução.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path).map_err(io_error)?;
+        let interactions = serde_json::from_str(&contents).map_err(Error::from_json_error)?;
+
+        Ok(Self {
+            path,
+            mode: CassetteMode::Replay,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    pub fn get_mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Interações gravadas até o momento.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn find(&self, url: &str) -> Option<Interaction> {
+        self.lock()
+            .iter()
+            .find(|interaction| interaction.url == url)
+            .cloned()
+    }
+
+    /// Grava `interaction`, substituindo a interação anterior da mesma URL, e reescreve o
+    /// arquivo.
+    fn store(&self, interaction: Interaction) -> Result<(), Error> {
+        let mut interactions = self.lock();
+
+        interactions.retain(|recorded| recorded.url != interaction.url);
+        interactions.push(interaction);
+
+        let contents =
+            serde_json::to_string_pretty(&*interactions).map_err(Error::from_json_error)?;
+
+        std::fs::write(&self.path, contents).map_err(io_error)
+    }
+}
+
+#[cfg(feature = "cassette")]
+fn io_error(error: std::io::Error) -> Error {
+    Error::new(error.to_string(), Errored::Unexpected, None)
+}
+
+#[cfg(feature = "cassette")]
+impl HttpTransport for Cassette {
+    async fn get(&self, url: &str) -> Result<(u16, String), Error> {
+        match self.mode {
+            CassetteMode::Replay => self
+                .find(url)
+                .map(|interaction| (interaction.status, interaction.body))
+                .ok_or_else(|| {
+                    Error::new(
+                        format!("{CASSETTE_MISS_MESSAGE}: {url}"),
+                        Errored::Unexpected,
+                        None,
+                    )
+                }),
+            CassetteMode::Record => {
+                let (status, body) = HttpTransport::get(&ReqwestTransport, url).await?;
+
+                self.store(Interaction {
+                    url: url.to_string(),
+                    status,
+                    body: body.clone(),
+                })?;
+
+                Ok((status, body))
+            }
+        }
+    }
+}
+
+type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<(u16, String), Error>> + Send + 'a>>;
+
+/// Versão de `HttpTransport` que pode ser usada como `dyn`.
+pub(crate) trait DynTransport: Send + Sync {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a>;
+}
+
+impl<T: HttpTransport> DynTransport for T {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(HttpTransport::get(self, url))
+    }
+}
+
+static TRANSPORT: RwLock<Option<Arc<dyn DynTransport>>> = RwLock::new(None);
+
+/// Transporte registrado com `set_transport`, caso exista.
+pub(crate) fn current() -> Option<Arc<dyn DynTransport>> {
+    TRANSPORT
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// #### `set_transport(transport: T)`
+/// Substitui o reqwest pelo transporte informado em todas as requisições da biblioteca.
+///
+/// Os hooks registrados no módulo `client` operam sobre requisições do reqwest e, portanto, não
+/// são executados enquanto um transporte estiver registrado.
+///
+/// ### Argumento
+/// * `transport:T` => Implementação de `HttpTransport`.
+pub fn set_transport<T: HttpTransport>(transport: T) {
+    *TRANSPORT
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(transport));
+}
+
+/// #### `clear_transport()`
+/// Remove o transporte registrado com `set_transport`, voltando a usar o reqwest.
+pub fn clear_transport() {
+    *TRANSPORT
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Executa a requisição com `transport` e converte o resultado em uma resposta do reqwest,
+/// para que a leitura e a desserialização sejam as mesmas do transporte padrão.
+pub(crate) async fn get(
+    transport: &dyn DynTransport,
+    url: &str,
+) -> Result<reqwest::Response, Error> {
+    let (status, body) = transport.get(url).await?;
+
+    let response = http::Response::builder()
+        .status(status)
+        .body(body)
+        .map_err(|error| {
+            Error::new(
+                error.to_string(),
+                crate::error::Errored::Unexpected,
+                Some(status),
+            )
+        })?;
+
+    Error::from_response(reqwest::Response::from(response)).await
+}
+
+#[cfg(test)]
+mod transport_tests {
+    use super::*;
+    use crate::error::Errored;
+    use httpmock::MockServer;
+
+    struct Fixed(u16, &'static str);
+
+    impl HttpTransport for Fixed {
+        async fn get(&self, _url: &str) -> Result<(u16, String), Error> {
+            Ok((self.0, self.1.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_transport_test() {
+        let response = get(&Fixed(200, r#"["a", "b"]"#), "https://example.com")
+            .await
+            .unwrap();
+        let items: Vec<String> = client::read_json(response).await.unwrap();
+
+        assert_eq!(items, vec!["a", "b"]);
+
+        let error = get(&Fixed(404, "{}"), "https://example.com")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::NotFound);
+        assert_eq!(error.code, Some(404));
+    }
+
+    #[tokio::test]
+    async fn reqwest_transport_test() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/transport/reqwest");
+                then.status(201).body("created");
+            })
+            .await;
+
+        let (status, body) =
+            HttpTransport::get(&ReqwestTransport, &server.url("/transport/reqwest"))
+                .await
+                .unwrap();
+
+        assert_eq!(status, 201);
+        assert_eq!(body, "created");
+    }
+
+    #[cfg(feature = "cassette")]
+    #[tokio::test]
+    async fn cassette_record_replay_test() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/transport/cassette");
+                then.status(200).body(r#"["a"]"#);
+            })
+            .await;
+
+        let path =
+            std::env::temp_dir().join(format!("brasilapi-cassette-{}.json", std::process::id()));
+        let url = server.url("/transport/cassette");
+
+        let recorder = Cassette::record(&path);
+        HttpTransport::get(&recorder, &url).await.unwrap();
+        HttpTransport::get(&recorder, &url).await.unwrap();
+
+        assert_eq!(recorder.interactions().len(), 1);
+
+        let player = Cassette::replay(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (status, body) = HttpTransport::get(&player, &url).await.unwrap();
+
+        assert_eq!(player.get_mode(), CassetteMode::Replay);
+        assert_eq!((status, body.as_str()), (200, r#"["a"]"#));
+        mock.assert_hits_async(2).await;
+
+        let error = HttpTransport::get(&player, "https://example.com/missing")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error, Errored::Unexpected);
+        assert!(error.message.starts_with(CASSETTE_MISS_MESSAGE));
+    }
+}
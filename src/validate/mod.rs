@@ -0,0 +1,107 @@
+//! Normalização e validação das entradas aceitas pela biblioteca, sem consultar a API.
+//!
+//! As funções de consulta usam as mesmas funções internamente, portanto uma entrada aceita aqui
+//! é enviada à API exatamente na forma retornada.
+use crate::{
+    client,
+    error::{Error, INVALID_CEP_MESSAGE, INVALID_CNPJ_MESSAGE},
+    ibge,
+};
+
+/// #### `normalize_uf(uf: &str)`
+/// Verifica se `uf` é a sigla de uma unidade federativa e a retorna em maiúsculas.
+///
+/// Assim como nas funções do módulo `ibge`, com `Config::resolve_state_names` o nome completo do
+/// estado também é aceito, por exemplo `"São Paulo"`, retornando `"SP"`.
+///
+/// ### Argumento
+/// * `uf:&str` => Sigla do estado, sem diferenciar maiúsculas de minúsculas.
+///
+/// ### Retorno
+/// * `Result<String, Error>` => Sigla em maiúsculas, ou um erro `Errored::InvalidInput` com a
+///   mensagem `INVALID_UF_MESSAGE`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::validate;
+///
+/// assert_eq!(validate::normalize_uf(" sp ").unwrap(), "SP");
+/// assert!(validate::normalize_uf("XX").is_err());
+/// ```
+pub fn normalize_uf(uf: &str) -> Result<String, Error> {
+    ibge::resolve_uf(uf, client::resolves_state_names()).map(|uf| uf.as_str().to_string())
+}
+
+/// #### `normalize_cep(cep: &str)`
+/// Remove a pontuação do CEP e verifica se restam exatamente 8 dígitos.
+///
+/// ### Argumento
+/// * `cep:&str` => CEP, com ou sem pontuação, por exemplo `01001-000`.
+///
+/// ### Retorno
+/// * `Result<String, Error>` => Os 8 dígitos do CEP, ou um erro `Errored::InvalidInput` com a
+///   mensagem `INVALID_CEP_MESSAGE`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::validate;
+///
+/// assert_eq!(validate::normalize_cep("01001-000").unwrap(), "01001000");
+/// ```
+pub fn normalize_cep(cep: &str) -> Result<String, Error> {
+    let digits: String = cep
+        .chars()
+        .filter(|c| !matches!(c, '-' | '.' | ' '))
+        .collect();
+
+    if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::invalid_input(INVALID_CEP_MESSAGE));
+    }
+
+    Ok(digits)
+}
+
+/// #### `normalize_cnpj(cnpj: &str)`
+/// Remove a pontuação do CNPJ e verifica se restam exatamente 14 dígitos.
+///
+/// ### Argumento
+/// * `cnpj:&str` => CNPJ, com ou sem pontuação, por exemplo `00.000.000/0001-91`.
+///
+/// ### Retorno
+/// * `Result<String, Error>` => Os 14 dígitos do CNPJ, ou um erro `Errored::InvalidInput` com
+///   a mensagem `INVALID_CNPJ_MESSAGE`.
+///
+/// # Exemplo
+/// ```
+/// use brasilapi::validate;
+///
+/// assert_eq!(validate::normalize_cnpj("00.000.000/0001-91").unwrap(), "00000000000191");
+/// ```
+pub fn normalize_cnpj(cnpj: &str) -> Result<String, Error> {
+    let digits: String = cnpj
+        .chars()
+        .filter(|c| !matches!(c, '.' | '/' | '-' | ' '))
+        .collect();
+
+    if digits.len() != 14 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::invalid_input(INVALID_CNPJ_MESSAGE));
+    }
+
+    Ok(digits)
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use super::*;
+    use crate::error::{Errored, INVALID_UF_MESSAGE};
+
+    #[test]
+    fn test_normalize_uf() {
+        assert_eq!(normalize_uf("rj").unwrap(), "RJ");
+
+        let error = normalize_uf("São Paulo").unwrap_err();
+
+        assert_eq!(error.error, Errored::InvalidInput);
+        assert_eq!(error.message, INVALID_UF_MESSAGE);
+    }
+}
@@ -0,0 +1,44 @@
+//! `BrasilApi::connect` define a configuração global do processo, portanto é verificado em um
+//! teste de integração próprio, executado em um binário separado dos testes da biblioteca.
+use brasilapi::{BrasilApi, Config};
+use httpmock::MockServer;
+
+#[tokio::test]
+async fn connect_can_be_retried_after_a_failed_probe() {
+    let unavailable = MockServer::start_async().await;
+    unavailable
+        .mock_async(|when, then| {
+            when.method("GET").path("/api/ibge/uf/v1/SP");
+            then.status(500).body(r#"{"message": "indisponível"}"#);
+        })
+        .await;
+
+    let error = BrasilApi::connect(Config {
+        base_url: unavailable.base_url(),
+        ..Default::default()
+    })
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.code, Some(500));
+
+    let server = MockServer::start_async().await;
+    let probe = server
+        .mock_async(|when, then| {
+            when.method("GET").path("/api/ibge/uf/v1/SP");
+            then.status(200)
+                .body(r#"{"id": 35, "sigla": "SP", "nome": "São Paulo", "regiao": {"id": 3, "sigla": "SE", "nome": "Sudeste"}}"#);
+        })
+        .await;
+
+    let api = BrasilApi::connect(Config {
+        base_url: server.base_url(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    probe.assert_async().await;
+    assert_eq!(api.get_base_url(), server.base_url());
+    assert_eq!(brasilapi::info().base_url, server.base_url());
+}
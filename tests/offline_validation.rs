@@ -0,0 +1,17 @@
+//! A configuração é global ao processo, portanto a validação antes de `configure` é verificada
+//! em um teste de integração próprio, executado em um binário separado dos testes da biblioteca.
+use brasilapi::{validate, Config};
+
+#[test]
+fn offline_validation_does_not_lock_the_config() {
+    assert_eq!(validate::normalize_uf("sp").unwrap(), "SP");
+    assert!(validate::normalize_uf("São Paulo").is_err());
+
+    brasilapi::configure(Config {
+        resolve_state_names: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(validate::normalize_uf("São Paulo").unwrap(), "SP");
+}
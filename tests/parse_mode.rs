@@ -0,0 +1,59 @@
+//! `Config::parse_mode` é global ao processo, portanto é verificado em um teste de integração
+//! próprio, executado em um binário separado dos testes da biblioteca.
+use brasilapi::{ibge, parse::ParseMode, pix, Config};
+use httpmock::MockServer;
+
+#[tokio::test]
+async fn lenient_parse_mode_reaches_module_functions() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method("GET").path("/api/pix/v1/participants");
+            then.status(200).body(
+                r#"[
+                    {"ispb": "00000000", "nome": "BANCO DO BRASIL", "nome_reduzido": "BCO DO BRASIL", "modalidade_participacao": "PDCT", "tipo_participacao": "DRCT", "inicio_operacao": "2020-11-03T09:30:00.000Z"},
+                    {"ispb": 360305}
+                ]"#,
+            );
+        })
+        .await;
+    server
+        .mock_async(|when, then| {
+            when.method("GET").path("/api/ibge/municipios/v1/RR");
+            then.status(200).body(
+                r#"[
+                    {"nome": "BOA VISTA", "codigo_ibge": "1400100"},
+                    {"nome": 1400209}
+                ]"#,
+            );
+        })
+        .await;
+
+    brasilapi::configure(Config {
+        base_url: server.base_url(),
+        parse_mode: ParseMode::Lenient,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let participants = pix::get_participants().await.unwrap();
+
+    assert_eq!(participants.len(), 1);
+    assert_eq!(participants[0].get_ispb(), "00000000");
+
+    let (participants, _) = pix::get_participants_timed().await.unwrap();
+
+    assert_eq!(participants.len(), 1);
+
+    let (municipalities, _) = ibge::get_municipalities_timed("RR", None).await.unwrap();
+
+    assert_eq!(municipalities.len(), 1);
+
+    let names = ibge::get_municipalities_mapped("RR", None, |municipality| {
+        municipality.get_name().to_string()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(names, vec!["BOA VISTA"]);
+}